@@ -0,0 +1,73 @@
+//! Plays the whole game from random key presses and checks that the ball
+//! never leaves the frame other than at the bottom and never ends a frame
+//! inside a live brick.
+#![no_main]
+
+use breakout::{
+    breakout::Breakout,
+    common::{BRICK_COLS, BRICK_ROWS, FRAME_OFFSET, HEADING_SIZE, WIDTH},
+    input::{FakeInput, Key},
+    screens::{Screen, play::MIN_VY_RATIO},
+};
+use libfuzzer_sys::fuzz_target;
+
+const KEYS: [Key; 5] = [Key::Left, Key::Right, Key::Space, Key::Enter, Key::Escape];
+const LAYOUT_BYTES: usize = BRICK_ROWS * BRICK_COLS / 8;
+
+fuzz_target!(|data: &[u8]| {
+    if data.len() < 8 + LAYOUT_BYTES {
+        return;
+    }
+    let (seed, rest) = data.split_at(8);
+    let (layout, frames) = rest.split_at(LAYOUT_BYTES);
+
+    let mut game = Breakout::new();
+    game.seed(u64::from_le_bytes(seed.try_into().unwrap()));
+    game.screens_mut().play.bricks_mut().set_layout(|row, col| {
+        let i = row * BRICK_COLS + col;
+        layout[i / 8] & (1 << (i % 8)) != 0
+    });
+
+    let mut input = FakeInput::new();
+    for bits in frames {
+        for (i, key) in KEYS.into_iter().enumerate() {
+            // escape quits, keep it rare so games get played
+            let held = match key {
+                Key::Escape => *bits == 0xFF,
+                _ => bits & (1 << i) != 0,
+            };
+            if held {
+                input.press(key);
+            } else {
+                input.release(key);
+            }
+        }
+        if game.update(&input) {
+            return;
+        }
+        input.next_frame();
+
+        let play = &game.screens().play;
+        if *game.screen() != Screen::Play || !play.has_started() || play.in_transition() {
+            continue;
+        }
+        let ball = play.ball();
+        let pos = ball.circle.pos;
+        assert!(
+            pos.x > FRAME_OFFSET && pos.x < WIDTH - FRAME_OFFSET,
+            "{pos:?}"
+        );
+        assert!(pos.y > HEADING_SIZE, "{pos:?}");
+        let s = ball.velocity.length();
+        assert!(
+            ball.velocity.y.abs() >= MIN_VY_RATIO * s - 1e-3,
+            "{:?}",
+            ball.velocity
+        );
+        for rect in play.bricks().alive_rects() {
+            let inside = (pos.x - rect.pos.x).abs() < rect.size.x * 0.5
+                && (pos.y - rect.pos.y).abs() < rect.size.y * 0.5;
+            assert!(!inside, "{pos:?} in brick at {:?}", rect.pos);
+        }
+    }
+});
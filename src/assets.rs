@@ -0,0 +1,213 @@
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    env, fmt, fs,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use grapes::fonts::Font;
+
+pub const ASSETS_FLAG: &str = "--assets";
+pub const ASSETS_ENV: &str = "BREAKOUT_ASSETS";
+const DATA_DIR: &str = "breakout/assets";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AssetId {
+    MonoFont,
+}
+
+impl AssetId {
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            AssetId::MonoFont => "NotoSansMono.ttf",
+        }
+    }
+
+    /// Copy baked into the binary, used when nothing is found on disk.
+    pub fn embedded(&self) -> &'static [u8] {
+        match self {
+            AssetId::MonoFont => include_bytes!("../assets/NotoSansMono.ttf"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct AssetError {
+    id: AssetId,
+    searched: Vec<PathBuf>,
+    unpack_to: PathBuf,
+    err: std::io::Error,
+}
+
+impl fmt::Display for AssetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "could not find asset '{}', searched:",
+            self.id.file_name()
+        )?;
+        for dir in self.searched.iter() {
+            write!(f, "\n  {}", dir.display())?;
+        }
+        write!(
+            f,
+            "\nunpacking the embedded copy to {} failed: {}",
+            self.unpack_to.display(),
+            self.err
+        )?;
+        write!(
+            f,
+            "\npass {ASSETS_FLAG} <dir> or set {ASSETS_ENV} to the assets directory"
+        )
+    }
+}
+
+impl std::error::Error for AssetError {}
+
+/// Resolves asset files from a search path and caches what has been loaded.
+///
+/// Directories are tried in order: `--assets <dir>`, `$BREAKOUT_ASSETS`,
+/// `assets/` next to the executable, then `breakout/assets` in the XDG data
+/// dirs. If none holds the file the copy embedded in the binary is used.
+pub struct Assets {
+    search_path: Vec<PathBuf>,
+    fonts: HashMap<AssetId, Rc<Font>>,
+}
+
+impl Assets {
+    pub fn new(search_path: Vec<PathBuf>) -> Self {
+        Self {
+            search_path,
+            fonts: HashMap::new(),
+        }
+    }
+
+    /// Builds the search path from the command line and the environment.
+    pub fn from_env() -> Self {
+        let args: Vec<String> = env::args().collect();
+        let mut search_path = Vec::new();
+
+        if let Some(dir) = flag_value(&args, ASSETS_FLAG) {
+            search_path.push(PathBuf::from(dir));
+        }
+        if let Some(dir) = env::var_os(ASSETS_ENV) {
+            search_path.push(PathBuf::from(dir));
+        }
+        if let Some(dir) = env::current_exe()
+            .ok()
+            .and_then(|exe| exe.parent().map(Path::to_path_buf))
+        {
+            search_path.push(dir.join("assets"));
+        }
+        search_path.extend(xdg_data_dirs().into_iter().map(|d| d.join(DATA_DIR)));
+
+        Self::new(search_path)
+    }
+
+    /// First file on the search path for `id`.
+    pub fn locate(&self, id: AssetId) -> Option<PathBuf> {
+        self.search_path
+            .iter()
+            .map(|dir| dir.join(id.file_name()))
+            .find(|path| path.is_file())
+    }
+
+    /// Raw bytes of an asset, read from disk or taken from the binary.
+    pub fn bytes(&self, id: AssetId) -> Cow<'static, [u8]> {
+        match self.locate(id).and_then(|path| fs::read(path).ok()) {
+            Some(data) => Cow::Owned(data),
+            None => Cow::Borrowed(id.embedded()),
+        }
+    }
+
+    pub fn font(&mut self, id: AssetId) -> Result<Rc<Font>, AssetError> {
+        if let Some(font) = self.fonts.get(&id) {
+            return Ok(font.clone());
+        }
+        let path = self.resolve(id)?;
+        println!("[INFO] loading {}", path.display());
+        let font = Rc::new(Font::load(&path.to_string_lossy()));
+        self.fonts.insert(id, font.clone());
+        Ok(font)
+    }
+
+    /// Path to load `id` from. The embedded copy is written out to the cache
+    /// dir first because grapes only loads fonts from files.
+    fn resolve(&self, id: AssetId) -> Result<PathBuf, AssetError> {
+        if let Some(path) = self.locate(id) {
+            return Ok(path);
+        }
+        let dir = cache_dir();
+        let path = dir.join(id.file_name());
+        let unpacked = fs::read(&path).is_ok_and(|data| data == id.embedded());
+        if !unpacked {
+            fs::create_dir_all(&dir)
+                .and_then(|_| fs::write(&path, id.embedded()))
+                .map_err(|err| AssetError {
+                    id,
+                    searched: self.search_path.clone(),
+                    unpack_to: path.clone(),
+                    err,
+                })?;
+        }
+        Ok(path)
+    }
+}
+
+/// Value following `flag`, either as `--flag value` or `--flag=value`.
+pub fn flag_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg == flag {
+            return iter.next().map(String::as_str);
+        }
+        if let Some(value) = arg.strip_prefix(flag).and_then(|v| v.strip_prefix('=')) {
+            return Some(value);
+        }
+    }
+    None
+}
+
+fn home_dir() -> Option<PathBuf> {
+    env::var_os("HOME").map(PathBuf::from)
+}
+
+fn xdg_data_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    match env::var_os("XDG_DATA_HOME") {
+        Some(dir) if !dir.is_empty() => dirs.push(PathBuf::from(dir)),
+        _ => dirs.extend(home_dir().map(|h| h.join(".local/share"))),
+    }
+    let shared = env::var("XDG_DATA_DIRS")
+        .ok()
+        .filter(|d| !d.is_empty())
+        .unwrap_or_else(|| "/usr/local/share:/usr/share".to_string());
+    dirs.extend(
+        shared
+            .split(':')
+            .filter(|d| !d.is_empty())
+            .map(PathBuf::from),
+    );
+    dirs
+}
+
+/// Where the game keeps what the player makes and earns, levels, progress and
+/// scores.
+pub fn data_home() -> PathBuf {
+    match env::var_os("XDG_DATA_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir).join("breakout"),
+        _ => home_dir()
+            .map(|h| h.join(".local/share/breakout"))
+            .unwrap_or_else(|| env::temp_dir().join("breakout")),
+    }
+}
+
+fn cache_dir() -> PathBuf {
+    match env::var_os("XDG_CACHE_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir).join("breakout"),
+        _ => home_dir()
+            .map(|h| h.join(".cache/breakout"))
+            .unwrap_or_else(|| env::temp_dir().join("breakout")),
+    }
+}
@@ -0,0 +1,104 @@
+use std::collections::VecDeque;
+
+use grapes::linal::vertx2::VX2;
+use rand::{Rng, SeedableRng, rngs::StdRng};
+
+use crate::{
+    common::{BALL_RADIUS, FRAME_OFFSET, PADDLE_WIDTH, WIDTH},
+    input::{FakeInput, Key},
+    screens::play::Play,
+};
+
+/// Frames the worst bot lags behind the ball.
+const MAX_DELAY: f32 = 12.0;
+
+/// Plays `Play` through a `FakeInput`. `skill` runs from 0, a slow and sloppy
+/// player, to 1, one that sees the ball right away and moves to where it will
+/// land.
+pub struct Bot {
+    skill: f32,
+    rng: StdRng,
+    /// ball positions and velocities, the oldest is what the bot reacts to
+    seen: VecDeque<(VX2, VX2)>,
+    /// where on the paddle the bot tries to catch the ball
+    aim: f32,
+    falling: bool,
+    input: FakeInput,
+}
+
+impl Bot {
+    pub fn new(skill: f32, seed: u64) -> Self {
+        Self {
+            skill: skill.clamp(0.0, 1.0),
+            rng: StdRng::seed_from_u64(seed),
+            seen: VecDeque::new(),
+            aim: 0.0,
+            falling: false,
+            input: FakeInput::new(),
+        }
+    }
+
+    pub fn skill(&self) -> f32 {
+        self.skill
+    }
+
+    /// Keys for the next frame of `play`.
+    pub fn input(&mut self, play: &Play) -> &FakeInput {
+        self.input.release_all();
+        if play.in_transition() || play.is_paused() {
+            return &self.input;
+        }
+        if !play.has_started() {
+            self.seen.clear();
+            self.input.press(Key::Space);
+            return &self.input;
+        }
+
+        let ball = play.ball();
+        self.seen.push_back((ball.circle.pos, ball.velocity));
+        let delay = ((1.0 - self.skill) * MAX_DELAY).round() as usize;
+        while self.seen.len() > delay + 1 {
+            self.seen.pop_front();
+        }
+        let (pos, vel) = self.seen[0];
+
+        // a new aim for every ball coming down, hits off the middle send the
+        // ball sideways, hitting the middle every time would loop it forever
+        if vel.y > 0.0 && !self.falling {
+            let spread = (0.4 + (1.0 - self.skill) * 0.35) * PADDLE_WIDTH;
+            let side = if self.rng.random() { 1.0 } else { -1.0 };
+            self.aim = side * self.rng.random_range(0.3..=1.0) * spread;
+        }
+        self.falling = vel.y > 0.0;
+
+        let paddle = play.paddle().rect;
+        let target = if vel.y > 0.0 {
+            let landing = landing_x(pos, vel, paddle.pos.y - paddle.size.y * 0.5);
+            pos.x + (landing - pos.x) * self.skill
+        } else {
+            pos.x
+        };
+        let target = target + self.aim;
+        let step = play.paddle().velocity();
+        if target > paddle.pos.x + step {
+            self.input.press(Key::Right);
+        } else if target < paddle.pos.x - step {
+            self.input.press(Key::Left);
+        }
+        &self.input
+    }
+}
+
+/// Where a ball at `pos` moving with `vel` crosses `y`, bouncing off the side
+/// walls on the way.
+fn landing_x(pos: VX2, vel: VX2, y: f32) -> f32 {
+    let (left, right) = (
+        FRAME_OFFSET + BALL_RADIUS,
+        WIDTH - FRAME_OFFSET - BALL_RADIUS,
+    );
+    let x = pos.x + vel.x * (y - pos.y).max(0.0) / vel.y;
+    // fold back and forth between the walls
+    let span = right - left;
+    let t = (x - left).rem_euclid(2.0 * span);
+    left + if t > span { 2.0 * span - t } else { t }
+}
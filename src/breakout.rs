@@ -1,166 +1,711 @@
-use std::io::Write;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process,
+};
 
-use grapes::events::input::Events;
 use grapes::{
-    colors::presets::GrapesColors,
-    objects::line::Line2d,
-    renderer::two_d::{Render, Renderer},
-    state::two_d::State,
-    vx2,
+    linal::vertx2::VX2, objects::line::Line2d, renderer::two_d::Renderer, state::two_d::State,
 };
+use rand::Rng;
 
 use crate::{
-    common::{
-        BALL_BASE_VEL_X, BALL_BASE_VEL_Y, FRAME_OFFSET, GameControl, HEADING_SIZE, HEIGHT, WIDTH,
+    assets::{AssetId, Assets},
+    canvas::{
+        Canvas, Rgba,
+        soft::{CaptureError, SoftCanvas},
+        window::{TextCache, WindowCanvas},
     },
+    code::encode,
+    common::{Drawable, HEIGHT, ScreenMsg, WIDTH, clock_text, frame_segments},
+    difficulty::Difficulty,
+    generator::{Pattern, generate, generate_with},
     heading::Heading,
+    hot_seat::PLAYERS,
+    input::{FakeInput, Input, Key},
+    level::Level,
+    mode::{EndlessStage, MAX_BALLS, Medal, Mode, TIME_ATTACK_DIFFICULTY, TIME_ATTACK_SEED},
+    replay::Replay,
+    rules::Ruleset,
+    scores::{Board, HighScores, scores_path},
     screens::{Screen, Screens},
+    variant::VariantKind,
 };
 
-pub fn get_level(score: usize) -> (f32, usize) {
-    match score {
-        0..100 => (1.0, 1),
-        100..300 => (1.5, 2),
-        300..600 => (2.0, 3),
-        600..800 => (3.0, 4),
-        800..1000 => (4.0, 5),
-        _ => (5.0, 6),
-    }
-}
-
 //TODO
-// - init speed as level with 1
 // - show that ball is lost
 // - goodbye stuff
 
 pub struct Breakout {
-    /// 0: (top_left, top_right)
-    /// 1: (top_right, bottom_right)
-    /// 2: (bottom_left, top_left)
-    /// 3: (bottom_right, bottom_left)
-    /// 4: (game_top_left, game_top_right)
-    frame: [Line2d; 5],
+    /// see `frame_segments`
+    frame: [(VX2, VX2); 5],
     screen: Screen,
     screens: Screens,
+    quit: bool,
     heading: Heading,
     score: usize,
     level: usize,
+    difficulty: Difficulty,
     started: bool,
+    /// playing a level from the editor
+    testing: bool,
+    /// walls cleared in this game, later walls are generated
+    stage: usize,
+    stage_seed: u64,
+    /// the wall in `Play` is not the classic one
+    custom_wall: bool,
+    pack_run: Option<PackRun>,
+    /// only the wall from a level code is played
+    single_wall: bool,
+    /// best results of the ranked modes
+    scores: HighScores,
+    /// scores of a hot seat game, `score` is the active player's
+    player_scores: [usize; PLAYERS],
+}
+
+/// The stages left to play from a level pack.
+struct PackRun {
+    id: String,
+    /// playable stages with their index in the pack
+    stages: Vec<(usize, Level)>,
+    at: usize,
 }
 
 impl Breakout {
+    pub fn new() -> Self {
+        let frame = frame_segments();
+        Breakout {
+            frame,
+            screen: Screen::Menu,
+            quit: false,
+            screens: Screens::init(frame.map(|(from, to)| Line2d::new(from, to))),
+            heading: Heading::init(),
+            score: 0,
+            level: 1,
+            difficulty: Difficulty::default(),
+            started: false,
+            testing: false,
+            stage: 0,
+            stage_seed: rand::rng().random(),
+            custom_wall: false,
+            pack_run: None,
+            single_wall: false,
+            scores: HighScores::new(scores_path()),
+            player_scores: [0; PLAYERS],
+        }
+    }
+
     pub fn reset(&mut self) {
         self.score = 0;
         self.level = 1;
+        self.player_scores = [0; PLAYERS];
+        self.heading.set_balls(self.ruleset().balls());
         self.heading.reset();
+        if self.mode() == Mode::HotSeat {
+            self.heading.set_players(self.player_scores, 0);
+        }
     }
 
-    fn draw(&mut self, renderer: &mut Renderer) {
-        let fg = GrapesColors::Teal;
+    pub fn draw(&self, canvas: &mut dyn Canvas) {
+        canvas.clear(Rgba::BLACK);
         //the borders are displayed on every screen
-        for (i, border) in self.frame.iter().enumerate() {
-            if i < 3 || (self.screen == Screen::Play && i == 4) {
-                border.draw_clr(renderer, fg);
+        for (i, (from, to)) in self.frame.iter().enumerate() {
+            let wall = matches!(self.screen, Screen::Play | Screen::Editor);
+            if i < 3 || (wall && i == 4) {
+                canvas.line(*from, *to, Rgba::TEAL);
             } else {
-                border.draw_clr(renderer, GrapesColors::GrapesBlack);
+                canvas.line(*from, *to, Rgba::BLACK);
             }
         }
         //draw the current screen
-        self.screens[&self.screen].draw(renderer);
+        self.screens[&self.screen].draw(canvas);
         if self.screen == Screen::Play {
-            self.heading.draw(renderer);
+            self.heading.draw(canvas);
         }
     }
 
-    fn update(&mut self, renderer: &mut Renderer, events: Events) -> bool {
-        if let Some(screen) = self.screens[&self.screen].update(renderer, &events) {
-            self.screen = screen;
-            if self.screen == Screen::Play {
-                println!("[INFO] reset initialized");
-                self.reset();
-                self.screens[&self.screen].reset();
+    /// Makes the game repeatable, see `Play::seed`.
+    pub fn seed(&mut self, seed: u64) {
+        self.screens.play.seed(seed);
+        self.screens.versus.seed(seed);
+        self.stage_seed = seed;
+    }
+
+    pub fn stage(&self) -> usize {
+        self.stage
+    }
+
+    /// Id of the pack being played and the index of the current stage in it.
+    pub fn pack_stage(&self) -> Option<(&str, usize)> {
+        self.pack_run
+            .as_ref()
+            .map(|run| (run.id.as_str(), run.stages[run.at].0))
+    }
+
+    pub fn score(&self) -> usize {
+        self.score
+    }
+
+    pub fn level(&self) -> usize {
+        self.level
+    }
+
+    pub fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+
+    /// Takes effect with the next game.
+    pub fn set_difficulty(&mut self, difficulty: Difficulty) {
+        self.difficulty = difficulty;
+        self.screens.menu.set_difficulty(difficulty);
+    }
+
+    pub fn ruleset(&self) -> Ruleset {
+        self.screens.play.ruleset()
+    }
+
+    /// Takes effect with the next game.
+    pub fn set_ruleset(&mut self, ruleset: Ruleset) {
+        self.screens.play.set_ruleset(ruleset);
+        self.screens.menu.set_ruleset(ruleset);
+    }
+
+    pub fn variant(&self) -> VariantKind {
+        self.screens.play.variant()
+    }
+
+    /// Takes effect right away, start a new game after it.
+    pub fn set_variant(&mut self, variant: VariantKind) {
+        self.screens.play.set_variant(variant);
+        self.screens.menu.set_variant(variant);
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.screens.play.mode()
+    }
+
+    /// Takes effect with the next game.
+    pub fn set_mode(&mut self, mode: Mode) {
+        self.screens.play.set_mode(mode);
+        self.screens.menu.set_mode(mode);
+    }
+
+    /// Sets ball and paddle speed for the current score, `force` also when
+    /// the speed level did not go up. Endless goes by the stage instead of
+    /// the score under either ruleset, otherwise the classic rules keep their
+    /// own speed.
+    fn apply_speed(&mut self, force: bool) {
+        if self.mode() == Mode::Endless {
+            if force {
+                self.apply_stage();
             }
+            return;
         }
+        if self.ruleset() == Ruleset::Classic {
+            return;
+        }
+        let speeds = self.difficulty.curve().speeds(self.score);
+        if force || speeds.level > self.level {
+            self.level = speeds.level;
+            self.screens.play.set_speed(speeds.ball, speeds.paddle);
+            self.heading.set_speed(self.level);
+        }
+    }
 
-        if self.screen == Screen::Quit {
-            return true;
+    /// Speeds and paddle of the current Endless stage, the speed level shown
+    /// is the stage.
+    fn apply_stage(&mut self) {
+        let stage = EndlessStage::new(self.stage);
+        let curve = self.difficulty.curve();
+        let base = curve.speeds(0);
+        let ball = (base.ball * stage.speed).min(curve.max_speed);
+        let paddle = base.paddle * (1.0 + 0.5 * (stage.speed - 1.0));
+        self.level = self.stage + 1;
+        self.screens.play.set_speed(ball, paddle);
+        self.screens.play.paddle_mut().set_width(stage.paddle_width);
+        self.heading.set_speed(self.level);
+    }
+
+    pub fn heading(&self) -> &Heading {
+        &self.heading
+    }
+
+    pub fn high_scores(&self) -> &HighScores {
+        &self.scores
+    }
+
+    /// The high score board of the mode and settings picked.
+    pub fn board(&self) -> Board {
+        Board {
+            mode: self.mode(),
+            difficulty: self.difficulty(),
+            ruleset: self.ruleset(),
+            variant: self.variant(),
         }
+    }
 
-        if self.screen == Screen::Play {
-            if !self.started {
-                self.started = true;
+    /// Keeps the high scores in `path` instead of the user's data directory.
+    pub fn set_scores_path(&mut self, path: PathBuf) {
+        self.scores = HighScores::new(path);
+    }
+
+    pub fn screens(&self) -> &Screens {
+        &self.screens
+    }
+
+    pub fn screen(&self) -> &Screen {
+        &self.screen
+    }
+
+    pub fn screens_mut(&mut self) -> &mut Screens {
+        &mut self.screens
+    }
+
+    /// Puts `level` up in `Play` in place of the classic wall.
+    fn set_wall(&mut self, level: &Level) {
+        self.screens.play.set_wall(level);
+        self.custom_wall = true;
+    }
+
+    /// Scores of both players in a hot seat game.
+    pub fn player_scores(&self) -> [usize; PLAYERS] {
+        self.player_scores
+    }
+
+    /// Who won a hot seat game, `None` for a draw.
+    fn winner(&self) -> Option<usize> {
+        let [a, b] = self.player_scores;
+        match a.cmp(&b) {
+            std::cmp::Ordering::Greater => Some(0),
+            std::cmp::Ordering::Less => Some(1),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+
+    /// Names the winner of a two player game for the game over screen, the
+    /// score shown is the better one.
+    fn duel_result(&mut self, winner: Option<usize>, [a, b]: [usize; 2]) -> String {
+        self.screens.game_over.set_score(a.max(b));
+        let text = match winner {
+            Some(player) => format!("Player {} Wins", player + 1),
+            None => "A Draw".to_string(),
+        };
+        println!("[INFO] {text}, {a} to {b}");
+        format!("{text}, {a} To {b}")
+    }
+
+    /// The generated wall of the current stage, Cavity mode always gets one
+    /// with pockets.
+    fn stage_wall(&self) -> Level {
+        let difficulty = match self.mode() {
+            Mode::Endless => EndlessStage::new(self.stage).difficulty,
+            _ => (0.2 + 0.1 * self.stage as f32).min(1.0),
+        };
+        let seed = self.stage_seed.wrapping_add(self.stage as u64);
+        match self.mode() {
+            Mode::Cavity => generate_with(Pattern::Cavities, seed, difficulty),
+            _ => generate(seed, difficulty),
+        }
+    }
+
+    /// Saves the result of a Time or Score Attack that ended and describes
+    /// it for the game over screen. A Time Attack only counts with the wall
+    /// cleared.
+    fn record_result(&mut self) -> Option<String> {
+        let mode = self.mode();
+        let result = match mode {
+            Mode::TimeAttack if self.screens.play.bricks().breakable_count() == 0 => {
+                self.screens.play.clock()
+            }
+            Mode::ScoreAttack => self.score,
+            _ => return None,
+        };
+        self.scores = HighScores::load(self.scores.path().to_path_buf());
+        let board = self.board();
+        let new_best = self.scores.record(&board, result);
+        if let Err(err) = self.scores.save() {
+            println!("[ERROR] could not save high scores: {err}");
+        }
+        let mut text = match mode {
+            Mode::TimeAttack => match Medal::for_time(result) {
+                Some(medal) => format!("Time {} - {}", clock_text(result), medal.name()),
+                None => format!("Time {}", clock_text(result)),
+            },
+            _ => format!("Score {result}"),
+        };
+        let best = self.scores.best(&board).unwrap_or(result);
+        if new_best {
+            text.push_str(" - New Best");
+        } else if mode == Mode::TimeAttack {
+            text.push_str(&format!(" - Best {}", clock_text(best)));
+        } else {
+            text.push_str(&format!(" - Best {best}"));
+        }
+        println!("[INFO] {}: {text}", mode.name());
+        Some(text)
+    }
+
+    /// Saves the score of a pack run that ended.
+    fn end_pack_run(&mut self) {
+        if let Some(run) = self.pack_run.take() {
+            let progress = self.screens.packs.progress_mut();
+            progress.record_score(&run.id, self.score);
+            if let Err(err) = progress.save() {
+                println!("[ERROR] could not save progress: {err}");
+            }
+        }
+    }
+
+    fn next_pack_stage(&mut self) {
+        let Some(run) = self.pack_run.as_mut() else {
+            return;
+        };
+        let (stage, _) = run.stages[run.at];
+        self.screens
+            .packs
+            .progress_mut()
+            .record_clear(&run.id, stage);
+        run.at += 1;
+        match run.stages.get(run.at) {
+            Some((stage, level)) => {
+                println!(
+                    "[INFO] pack {}, stage {}: {}",
+                    run.id,
+                    stage + 1,
+                    level.name
+                );
+                self.screens.play.next_wall(level);
             }
-            if let Some(round) = self.screens[&self.screen].get_round() {
-                self.heading.set_ball(round);
+            None => {
+                println!("[INFO] pack {} cleared", run.id);
+                self.handle(ScreenMsg::Goto(Screen::Menu));
             }
+        }
+    }
 
-            let mut check_speed = false;
-            if let Some(points) = self.screens[&self.screen].get_points() {
+    /// Starts a game on the play screen, in the duel modes too for the level
+    /// codes and packs that bring their own wall.
+    fn start_play(&mut self) {
+        println!("[INFO] reset initialized");
+        // Progressive starts over from the classic wall too
+        if self.custom_wall || self.mode() == Mode::Progressive {
+            self.screens.play.bricks_mut().set_level(&Level::classic());
+            self.custom_wall = false;
+        }
+        self.stage = 0;
+        self.single_wall = false;
+        self.reset();
+        self.screens.play.reset();
+        let wall = match self.mode() {
+            Mode::Cavity | Mode::Endless => Some(self.stage_wall()),
+            Mode::TimeAttack => Some(generate(TIME_ATTACK_SEED, TIME_ATTACK_DIFFICULTY)),
+            _ => None,
+        };
+        if let Some(level) = wall {
+            self.set_wall(&level);
+        }
+        self.apply_speed(true);
+        self.started = true;
+    }
+
+    pub fn handle(&mut self, msg: ScreenMsg) {
+        match msg {
+            ScreenMsg::Goto(screen) => {
+                self.screen = match screen {
+                    // a test run always ends back in the editor
+                    Screen::GameOver | Screen::Menu if self.testing => {
+                        self.testing = false;
+                        Screen::Editor
+                    }
+                    Screen::Play if self.mode() == Mode::Versus => Screen::Versus,
+                    Screen::Play if self.mode() == Mode::Online => Screen::Online,
+                    screen => screen,
+                };
+                match self.screen {
+                    Screen::Play => self.start_play(),
+                    Screen::Menu if self.started => {
+                        self.end_pack_run();
+                        // leaves a finished online game
+                        self.screens.online.reset();
+                        self.screens.menu.prepare_render_score(self.score);
+                    }
+                    Screen::GameOver => {
+                        let ranked = self.pack_run.is_none() && self.mode().is_ranked();
+                        let endless = self.pack_run.is_none() && self.mode() == Mode::Endless;
+                        // a level code or pack played in a duel mode is no duel
+                        let duel = self.pack_run.is_none() && !self.single_wall;
+                        self.end_pack_run();
+                        self.screens.game_over.set_score(self.score);
+                        let result = if ranked {
+                            self.record_result()
+                        } else if endless {
+                            Some(format!("Stages Survived: {}", self.stage))
+                        } else if self.mode() == Mode::HotSeat {
+                            Some(self.duel_result(self.winner(), self.player_scores))
+                        } else if duel && self.mode() == Mode::Versus {
+                            let versus = &self.screens.versus;
+                            Some(self.duel_result(versus.winner(), versus.scores()))
+                        } else if duel && let Some(session) = self.screens.online.session() {
+                            let versus = session.versus();
+                            Some(self.duel_result(versus.winner(), versus.scores()))
+                        } else {
+                            None
+                        };
+                        self.screens.game_over.set_result(result);
+                    }
+                    Screen::Versus => {
+                        println!("[INFO] versus game started");
+                        self.reset();
+                        self.screens.versus.reset();
+                        self.started = true;
+                    }
+                    Screen::Online => {
+                        self.reset();
+                        self.screens.online.reset();
+                        self.started = true;
+                    }
+                    Screen::Packs => self.screens.packs.refresh(),
+                    _ => (),
+                }
+            }
+            // a test run always ends back in the editor
+            ScreenMsg::Quit if self.testing => self.handle(ScreenMsg::Goto(Screen::Menu)),
+            ScreenMsg::Quit => {
+                self.end_pack_run();
+                self.quit = true;
+            }
+            ScreenMsg::BallLost(round) => self.heading.set_ball(round),
+            ScreenMsg::WallCleared if self.testing => self.handle(ScreenMsg::Goto(Screen::Menu)),
+            ScreenMsg::WallCleared if self.pack_run.is_some() => self.next_pack_stage(),
+            ScreenMsg::WallCleared if self.single_wall => {
+                self.single_wall = false;
+                self.handle(ScreenMsg::Goto(Screen::Menu));
+            }
+            ScreenMsg::WallCleared if self.mode() == Mode::TimeAttack => {
+                println!(
+                    "[INFO] wall cleared in {}",
+                    clock_text(self.screens.play.clock())
+                );
+                self.handle(ScreenMsg::Goto(Screen::GameOver));
+            }
+            ScreenMsg::WallCleared if self.mode() == Mode::Endless => {
+                self.stage += 1;
+                let level = self.stage_wall();
+                println!("[INFO] endless stage {}: {}", self.stage, level.name);
+                self.screens.play.next_wall(&level);
+                self.custom_wall = true;
+                self.apply_speed(true);
+                let play = &mut self.screens.play;
+                if EndlessStage::grants_ball(self.stage) && play.balls_left() < MAX_BALLS {
+                    println!("[INFO] extra ball");
+                    play.add_ball();
+                    self.heading.add_ball();
+                }
+            }
+            ScreenMsg::WallCleared if self.ruleset().walls().is_some() => {
+                self.stage += 1;
+                if Some(self.stage) == self.ruleset().walls() {
+                    println!("[INFO] all walls cleared");
+                    self.handle(ScreenMsg::Goto(Screen::GameOver));
+                } else {
+                    self.screens.play.next_wall(&Level::classic());
+                }
+            }
+            ScreenMsg::WallCleared => {
+                self.stage += 1;
+                let level = self.stage_wall();
+                println!(
+                    "[INFO] stage {}: {} ({})",
+                    self.stage,
+                    level.name,
+                    encode(&level)
+                );
+                self.screens.play.next_wall(&level);
+                self.custom_wall = true;
+            }
+            ScreenMsg::PlayLevel(level) => {
+                self.testing = true;
+                self.screen = Screen::Play;
+                self.reset();
+                self.screens.play.reset();
+                self.set_wall(&level);
+                self.apply_speed(true);
+            }
+            ScreenMsg::PlayCode(level) => {
+                self.screen = Screen::Play;
+                self.start_play();
+                self.set_wall(&level);
+                self.single_wall = true;
+            }
+            ScreenMsg::PlayPack { pack, stage } => {
+                let Some(pack) = self.screens.packs.pack(pack) else {
+                    return;
+                };
+                let id = pack.id.clone();
+                let stages: Vec<(usize, Level)> = pack
+                    .stages
+                    .iter()
+                    .enumerate()
+                    .skip(stage)
+                    .filter_map(|(i, s)| s.level.as_ref().ok().map(|l| (i, l.clone())))
+                    .collect();
+                if stages.is_empty() {
+                    return;
+                }
+                self.screen = Screen::Play;
+                self.start_play();
+                println!(
+                    "[INFO] pack {id}, stage {}: {}",
+                    stages[0].0 + 1,
+                    stages[0].1.name
+                );
+                self.set_wall(&stages[0].1);
+                self.pack_run = Some(PackRun { id, stages, at: 0 });
+            }
+            ScreenMsg::Points(points) => {
                 self.score += points;
                 self.heading.set_score(self.score);
-                check_speed = true;
-            }
-            if check_speed {
-                let cur = self.level;
-                let (multi, level) = get_level(self.score);
-                if level > cur {
-                    self.level = level;
-                    let v = vx2!(multi * BALL_BASE_VEL_X, multi * BALL_BASE_VEL_Y);
-                    self.screens[&self.screen].set_velocity(v, multi);
-                    self.heading.set_speed(self.level);
+                if let Some(hot_seat) = self.screens.play.hot_seat() {
+                    self.player_scores[hot_seat.active()] = self.score;
+                    self.heading
+                        .set_players(self.player_scores, hot_seat.active());
                 }
+                self.apply_speed(false);
             }
+            ScreenMsg::Player(player) => {
+                self.score = self.player_scores[player];
+                let balls = self.ruleset().balls();
+                self.heading
+                    .set_ball(balls - self.screens.play.balls_left());
+                self.heading.set_score(self.score);
+                self.heading.set_players(self.player_scores, player);
+                self.apply_speed(true);
+            }
+            ScreenMsg::Difficulty(difficulty) => self.difficulty = difficulty,
+            ScreenMsg::Ruleset(ruleset) => self.screens.play.set_ruleset(ruleset),
+            ScreenMsg::Variant(variant) => self.screens.play.set_variant(variant),
+            ScreenMsg::Mode(mode) => self.screens.play.set_mode(mode),
+            ScreenMsg::Speed(level) => {
+                self.level = level;
+                self.heading.set_speed(level);
+            }
+            // Versus sends the rows over itself
+            ScreenMsg::RowCleared => (),
         }
+    }
+
+    pub fn update(&mut self, input: &dyn Input) -> bool {
+        let mut msgs = Vec::new();
+        self.screens[&self.screen].update(input, &mut msgs);
 
-        if self.screen == Screen::Menu && self.started {
-            self.screens[&self.screen].prepare_render_score(self.score, &self.heading.font);
+        for msg in msgs {
+            self.handle(msg);
+        }
+        if self.screen == Screen::Play {
+            let play = &self.screens.play;
+            let clock = match self.mode() {
+                Mode::TimeAttack => Some(play.clock()),
+                Mode::ScoreAttack => play.time_left(),
+                _ => None,
+            };
+            self.heading.set_clock(clock);
         }
 
-        return false;
+        self.quit
+    }
+}
+
+impl Default for Breakout {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
-impl State for Breakout {
+/// Renders the first frame off-screen and writes it to `path`.
+pub fn screenshot(path: &Path) -> Result<(), CaptureError> {
+    let assets = Assets::from_env();
+    let mut canvas = SoftCanvas::new(
+        WIDTH as u32,
+        HEIGHT as u32,
+        &assets.bytes(AssetId::MonoFont),
+    )?;
+    Breakout::new().draw(&mut canvas);
+    canvas.save_png(path)
+}
+
+/// Plays `replay` off-screen and writes every frame into `dir`, named by
+/// `frame_path`. Returns how many frames were written, fewer than the replay
+/// holds if the game quits.
+pub fn capture(replay: &Replay, dir: &Path) -> Result<usize, CaptureError> {
+    let assets = Assets::from_env();
+    let mut canvas = SoftCanvas::new(
+        WIDTH as u32,
+        HEIGHT as u32,
+        &assets.bytes(AssetId::MonoFont),
+    )?;
+    fs::create_dir_all(dir)?;
+    let mut game = Breakout::new();
+    game.seed(replay.seed);
+    let mut input = FakeInput::new();
+    let mut frames = 0;
+    for held in replay.frames() {
+        for key in Key::ALL {
+            if held.contains(&key) {
+                input.press(key);
+            } else {
+                input.release(key);
+            }
+        }
+        let quit = game.update(&input);
+        input.next_frame();
+        if quit {
+            break;
+        }
+        game.draw(&mut canvas);
+        canvas.save_frame(dir, frames)?;
+        frames += 1;
+    }
+    Ok(frames)
+}
+
+/// `Breakout` running in a grapes window.
+pub struct Windowed {
+    game: Breakout,
+    text: TextCache,
+}
+
+impl State for Windowed {
     fn user_init(
         _renderer: &mut grapes::renderer::two_d::Renderer,
         _camera: grapes::engine::camera_2d::Camera2dRef,
     ) -> Self {
-        let top_left = vx2!(FRAME_OFFSET);
-        let top_right = grapes::linal::vertx2::VX2::new(WIDTH - FRAME_OFFSET, FRAME_OFFSET);
-        let bottom_right = vx2!(WIDTH - FRAME_OFFSET, HEIGHT - FRAME_OFFSET);
-        let bottom_left = vx2!(FRAME_OFFSET, HEIGHT - FRAME_OFFSET);
-        let game_top_left = vx2!(FRAME_OFFSET, HEADING_SIZE);
-        let game_top_right = vx2!(WIDTH - FRAME_OFFSET, HEADING_SIZE);
-        let frame = [
-            Line2d::new(top_left, top_right),
-            Line2d::new(top_right, bottom_right),
-            Line2d::new(bottom_left, top_left),
-            Line2d::new(bottom_right, bottom_left),
-            Line2d::new(game_top_left, game_top_right),
-        ];
-        Breakout {
-            frame: frame.clone(),
-            screen: Screen::Menu,
-            screens: Screens::init(frame),
-            heading: Heading::init(),
-            score: 0,
-            level: 1,
-            started: false,
+        let mut assets = Assets::from_env();
+        let font = match assets.font(AssetId::MonoFont) {
+            Ok(font) => font,
+            Err(err) => {
+                println!("[ERROR] {err}");
+                process::exit(1);
+            }
+        };
+        Windowed {
+            game: Breakout::new(),
+            text: TextCache::new(font),
         }
     }
 
     fn user_update(
         &mut self,
-        renderer: &mut grapes::renderer::two_d::Renderer,
+        renderer: &mut Renderer,
         events: grapes::events::input::Events,
         _camera: grapes::engine::camera_2d::Camera2dRef,
         _time_info: &grapes::context::time::TimeInfo,
     ) -> bool {
-        if self.update(renderer, events) {
+        if self.game.update(&events) {
             return true;
         }
-        renderer.clear_background(GrapesColors::GrapesBlack);
-        self.draw(renderer);
+        self.game
+            .draw(&mut WindowCanvas::new(renderer, &mut self.text));
+        self.text.end_frame();
 
         //        let buf = format!("\r{}", time_info.get_average_fps());
         //        let mut stdout = std::io::stdout().lock();
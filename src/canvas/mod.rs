@@ -0,0 +1,71 @@
+use grapes::{linal::vertx2::VX2, objects::rectangle::Rectangle, vx2};
+
+pub mod soft;
+pub mod window;
+
+/// Padding between a label's box and its text.
+pub const LABEL_PAD: f32 = 4.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Rgba {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Rgba {
+    pub const BLACK: Rgba = Rgba::new(0x00, 0x00, 0x00, 0xFF);
+    pub const TEAL: Rgba = Rgba::new(0x00, 0x80, 0x80, 0xFF);
+    pub const MAROON: Rgba = Rgba::new(0x80, 0x00, 0x00, 0xFF);
+
+    pub const fn new(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self { r, g, b, a }
+    }
+
+    pub fn lerp(from: Rgba, to: Rgba, t: f32) -> Rgba {
+        let t = t.clamp(0.0, 1.0);
+        let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+        Rgba::new(
+            mix(from.r, to.r),
+            mix(from.g, to.g),
+            mix(from.b, to.b),
+            mix(from.a, to.a),
+        )
+    }
+
+    /// Scales the distance to grey by `s`, 0 is fully grey and 1 unchanged.
+    pub fn saturation(self, s: f32) -> Rgba {
+        let grey = (0.299 * self.r as f32 + 0.587 * self.g as f32 + 0.114 * self.b as f32) as u8;
+        let grey = Rgba::new(grey, grey, grey, self.a);
+        Rgba::lerp(grey, self, s)
+    }
+}
+
+/// Everything the game draws goes through a `Canvas`, so the same frame can
+/// end up in a window or in a pixel buffer.
+pub trait Canvas {
+    fn clear(&mut self, color: Rgba);
+    fn fill_rect(&mut self, rect: &Rectangle, color: Rgba);
+    fn stroke_rect(&mut self, rect: &Rectangle, color: Rgba);
+    fn fill_circle(&mut self, center: VX2, radius: f32, color: Rgba);
+    fn stroke_circle(&mut self, center: VX2, radius: f32, color: Rgba);
+    fn line(&mut self, from: VX2, to: VX2, color: Rgba);
+    /// Draws `text` on a black box with its top left corner at `pos`. The box
+    /// is `text_width + 2 * LABEL_PAD` wide and `fs + 2 * LABEL_PAD` high.
+    fn label(&mut self, text: &str, pos: VX2, fs: f32, color: Rgba);
+    fn text_width(&self, text: &str, fs: f32) -> f32;
+
+    fn label_size(&self, text: &str, fs: f32) -> VX2 {
+        vx2!(
+            self.text_width(text, fs) + 2.0 * LABEL_PAD,
+            fs + 2.0 * LABEL_PAD
+        )
+    }
+
+    fn label_centered(&mut self, text: &str, center: VX2, fs: f32, color: Rgba) {
+        let size = self.label_size(text, fs);
+        let pos = vx2!(center.x - size.x * 0.5, center.y - size.y * 0.5);
+        self.label(text, pos, fs, color);
+    }
+}
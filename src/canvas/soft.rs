@@ -0,0 +1,267 @@
+use std::{
+    fmt, fs,
+    io::{self, BufWriter},
+    path::{Path, PathBuf},
+};
+
+use ab_glyph::{Font, FontArc, PxScale, ScaleFont, point};
+use grapes::{linal::vertx2::VX2, objects::rectangle::Rectangle};
+
+use super::{Canvas, LABEL_PAD, Rgba};
+
+#[derive(Debug)]
+pub enum CaptureError {
+    Font(ab_glyph::InvalidFont),
+    Io(io::Error),
+    Png(png::EncodingError),
+}
+
+impl fmt::Display for CaptureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CaptureError::Font(err) => write!(f, "could not parse font: {err}"),
+            CaptureError::Io(err) => write!(f, "could not write frame: {err}"),
+            CaptureError::Png(err) => write!(f, "could not encode frame: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for CaptureError {}
+
+impl From<io::Error> for CaptureError {
+    fn from(err: io::Error) -> Self {
+        CaptureError::Io(err)
+    }
+}
+
+impl From<png::EncodingError> for CaptureError {
+    fn from(err: png::EncodingError) -> Self {
+        CaptureError::Png(err)
+    }
+}
+
+/// File name of frame `n` in a captured sequence.
+pub fn frame_path(dir: &Path, n: usize) -> PathBuf {
+    dir.join(format!("frame_{n:05}.png"))
+}
+
+/// Rasterises into an RGBA buffer in memory, no window or GPU needed.
+pub struct SoftCanvas {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+    font: FontArc,
+}
+
+impl SoftCanvas {
+    pub fn new(width: u32, height: u32, font: &[u8]) -> Result<Self, CaptureError> {
+        let font = FontArc::try_from_vec(font.to_vec()).map_err(CaptureError::Font)?;
+        Ok(Self {
+            width,
+            height,
+            pixels: vec![0; (width * height * 4) as usize],
+            font,
+        })
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Row major RGBA bytes, 4 per pixel.
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    pub fn pixel(&self, x: u32, y: u32) -> Rgba {
+        let i = ((y * self.width + x) * 4) as usize;
+        Rgba::new(
+            self.pixels[i],
+            self.pixels[i + 1],
+            self.pixels[i + 2],
+            self.pixels[i + 3],
+        )
+    }
+
+    pub fn save_png(&self, path: &Path) -> Result<(), CaptureError> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let file = BufWriter::new(fs::File::create(path)?);
+        let mut encoder = png::Encoder::new(file, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.pixels)?;
+        Ok(())
+    }
+
+    /// Writes the current frame as number `n` of a sequence in `dir`.
+    pub fn save_frame(&self, dir: &Path, n: usize) -> Result<PathBuf, CaptureError> {
+        let path = frame_path(dir, n);
+        self.save_png(&path)?;
+        Ok(path)
+    }
+
+    /// Blends `color` over pixel (x, y) with the given coverage in 0..=1.
+    fn blend(&mut self, x: i64, y: i64, color: Rgba, coverage: f32) {
+        if x < 0 || y < 0 || x >= self.width as i64 || y >= self.height as i64 {
+            return;
+        }
+        let alpha = coverage.clamp(0.0, 1.0) * color.a as f32 / 255.0;
+        if alpha <= 0.0 {
+            return;
+        }
+        let i = ((y as u32 * self.width + x as u32) * 4) as usize;
+        let px = &mut self.pixels[i..i + 4];
+        for (dst, src) in px.iter_mut().zip([color.r, color.g, color.b]) {
+            *dst = (src as f32 * alpha + *dst as f32 * (1.0 - alpha)).round() as u8;
+        }
+        px[3] = (255.0 * alpha + px[3] as f32 * (1.0 - alpha)).round() as u8;
+    }
+
+    /// Fills every pixel whose centre lies inside [x0, x1) x [y0, y1).
+    fn fill_area(&mut self, x0: f32, y0: f32, x1: f32, y1: f32, color: Rgba) {
+        let (x0, x1) = ((x0 - 0.5).ceil() as i64, (x1 - 0.5).ceil() as i64);
+        let (y0, y1) = ((y0 - 0.5).ceil() as i64, (y1 - 0.5).ceil() as i64);
+        for y in y0..y1 {
+            for x in x0..x1 {
+                self.blend(x, y, color, 1.0);
+            }
+        }
+    }
+
+    /// Blends every pixel around `center` for which `coverage` returns > 0,
+    /// given the distance of the pixel centre to `center`.
+    fn circle_with<F>(&mut self, center: VX2, radius: f32, color: Rgba, coverage: F)
+    where
+        F: Fn(f32) -> f32,
+    {
+        let r = radius.ceil() as i64 + 1;
+        let (cx, cy) = (center.x.floor() as i64, center.y.floor() as i64);
+        for y in cy - r..=cy + r {
+            for x in cx - r..=cx + r {
+                let dx = x as f32 + 0.5 - center.x;
+                let dy = y as f32 + 0.5 - center.y;
+                let c = coverage((dx * dx + dy * dy).sqrt());
+                if c > 0.0 {
+                    self.blend(x, y, color, c);
+                }
+            }
+        }
+    }
+}
+
+impl Canvas for SoftCanvas {
+    fn clear(&mut self, color: Rgba) {
+        for px in self.pixels.chunks_exact_mut(4) {
+            px.copy_from_slice(&[color.r, color.g, color.b, color.a]);
+        }
+    }
+
+    fn fill_rect(&mut self, rect: &Rectangle, color: Rgba) {
+        let (hw, hh) = (rect.size.x * 0.5, rect.size.y * 0.5);
+        self.fill_area(
+            rect.pos.x - hw,
+            rect.pos.y - hh,
+            rect.pos.x + hw,
+            rect.pos.y + hh,
+            color,
+        );
+    }
+
+    fn stroke_rect(&mut self, rect: &Rectangle, color: Rgba) {
+        let (x0, y0) = (
+            rect.pos.x - rect.size.x * 0.5,
+            rect.pos.y - rect.size.y * 0.5,
+        );
+        let (x1, y1) = (
+            rect.pos.x + rect.size.x * 0.5,
+            rect.pos.y + rect.size.y * 0.5,
+        );
+        self.fill_area(x0, y0, x1, y0 + 1.0, color);
+        self.fill_area(x0, y1 - 1.0, x1, y1, color);
+        self.fill_area(x0, y0 + 1.0, x0 + 1.0, y1 - 1.0, color);
+        self.fill_area(x1 - 1.0, y0 + 1.0, x1, y1 - 1.0, color);
+    }
+
+    fn fill_circle(&mut self, center: VX2, radius: f32, color: Rgba) {
+        self.circle_with(center, radius, color, |d| {
+            (radius + 0.5 - d).clamp(0.0, 1.0)
+        });
+    }
+
+    fn stroke_circle(&mut self, center: VX2, radius: f32, color: Rgba) {
+        self.circle_with(center, radius, color, |d| {
+            (1.0 - (d - radius + 0.5).abs()).clamp(0.0, 1.0)
+        });
+    }
+
+    fn line(&mut self, from: VX2, to: VX2, color: Rgba) {
+        let (dx, dy) = (to.x - from.x, to.y - from.y);
+        let steps = dx.abs().max(dy.abs()).ceil().max(1.0) as i64;
+        for i in 0..=steps {
+            let t = i as f32 / steps as f32;
+            let x = (from.x + dx * t).floor() as i64;
+            let y = (from.y + dy * t).floor() as i64;
+            self.blend(x, y, color, 1.0);
+        }
+    }
+
+    fn label(&mut self, text: &str, pos: VX2, fs: f32, color: Rgba) {
+        let width = self.text_width(text, fs);
+        self.fill_area(
+            pos.x,
+            pos.y,
+            pos.x + width + 2.0 * LABEL_PAD,
+            pos.y + fs + 2.0 * LABEL_PAD,
+            Rgba::BLACK,
+        );
+
+        let font = self.font.clone();
+        let scale = PxScale::from(fs);
+        let scaled = font.as_scaled(scale);
+        let baseline = pos.y + LABEL_PAD + fs;
+        let mut x = pos.x + LABEL_PAD;
+        let mut prev = None;
+        for c in text.chars() {
+            let id = scaled.glyph_id(c);
+            if let Some(prev) = prev {
+                x += scaled.kern(prev, id);
+            }
+            let glyph = id.with_scale_and_position(scale, point(x, baseline));
+            x += scaled.h_advance(id);
+            prev = Some(id);
+            if let Some(outline) = font.outline_glyph(glyph) {
+                let bounds = outline.px_bounds();
+                outline.draw(|gx, gy, coverage| {
+                    self.blend(
+                        bounds.min.x as i64 + gx as i64,
+                        bounds.min.y as i64 + gy as i64,
+                        color,
+                        coverage,
+                    );
+                });
+            }
+        }
+    }
+
+    fn text_width(&self, text: &str, fs: f32) -> f32 {
+        let scaled = self.font.as_scaled(PxScale::from(fs));
+        let mut width = 0.0;
+        let mut prev = None;
+        for c in text.chars() {
+            let id = scaled.glyph_id(c);
+            if let Some(prev) = prev {
+                width += scaled.kern(prev, id);
+            }
+            width += scaled.h_advance(id);
+            prev = Some(id);
+        }
+        width
+    }
+}
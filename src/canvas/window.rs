@@ -0,0 +1,128 @@
+use std::{collections::HashMap, rc::Rc};
+
+use grapes::{
+    colors::color::Color,
+    fonts::Font,
+    linal::vertx2::VX2,
+    objects::{circle::Circle, line::Line2d, rectangle::Rectangle},
+    renderer::two_d::{Render, Renderer},
+    textures::Texture,
+    vx2,
+};
+
+use super::{Canvas, LABEL_PAD, Rgba};
+
+impl From<Rgba> for Color {
+    fn from(c: Rgba) -> Self {
+        Color::new(c.r, c.g, c.b, c.a)
+    }
+}
+
+#[derive(PartialEq, Eq, Hash)]
+struct LabelKey {
+    text: String,
+    fs: u32,
+    color: Rgba,
+}
+
+struct Label {
+    texture: Texture,
+    size: VX2,
+    used: bool,
+}
+
+/// Rendered label textures, kept for as long as they are drawn every frame.
+pub struct TextCache {
+    font: Rc<Font>,
+    labels: HashMap<LabelKey, Label>,
+}
+
+impl TextCache {
+    pub fn new(font: Rc<Font>) -> Self {
+        Self {
+            font,
+            labels: HashMap::new(),
+        }
+    }
+
+    /// Drops every label that was not drawn since the last call.
+    pub fn end_frame(&mut self) {
+        self.labels.retain(|_, label| label.used);
+        for label in self.labels.values_mut() {
+            label.used = false;
+        }
+    }
+}
+
+/// Draws into the grapes window for one frame.
+pub struct WindowCanvas<'a> {
+    renderer: &'a mut Renderer,
+    text: &'a mut TextCache,
+}
+
+impl<'a> WindowCanvas<'a> {
+    pub fn new(renderer: &'a mut Renderer, text: &'a mut TextCache) -> Self {
+        Self { renderer, text }
+    }
+}
+
+impl Canvas for WindowCanvas<'_> {
+    fn clear(&mut self, color: Rgba) {
+        self.renderer.clear_background(Color::from(color));
+    }
+
+    fn fill_rect(&mut self, rect: &Rectangle, color: Rgba) {
+        rect.fill_clr(self.renderer, color);
+    }
+
+    fn stroke_rect(&mut self, rect: &Rectangle, color: Rgba) {
+        rect.draw_clr(self.renderer, color);
+    }
+
+    fn fill_circle(&mut self, center: VX2, radius: f32, color: Rgba) {
+        Circle::new(center, radius).fill_clr(self.renderer, color);
+    }
+
+    fn stroke_circle(&mut self, center: VX2, radius: f32, color: Rgba) {
+        Circle::new(center, radius).draw_clr(self.renderer, color);
+    }
+
+    fn line(&mut self, from: VX2, to: VX2, color: Rgba) {
+        Line2d::new(from, to).draw_clr(self.renderer, color);
+    }
+
+    fn label(&mut self, text: &str, pos: VX2, fs: f32, color: Rgba) {
+        let key = LabelKey {
+            text: text.to_string(),
+            fs: fs.to_bits(),
+            color,
+        };
+        let font = &self.text.font;
+        let label = self.text.labels.entry(key).or_insert_with(|| {
+            let size = vx2!(
+                font.width(text.as_bytes(), fs) + 2.0 * LABEL_PAD,
+                fs + 2.0 * LABEL_PAD
+            );
+            let mut texture = Texture::init_with_background_color(size, Rgba::BLACK.into());
+            font.render_into_texture(
+                text.as_bytes(),
+                vx2!(LABEL_PAD, fs + LABEL_PAD),
+                fs,
+                color.into(),
+                &mut texture,
+            );
+            Label {
+                texture,
+                size,
+                used: false,
+            }
+        });
+        label.used = true;
+        let center = vx2!(pos.x + label.size.x * 0.5, pos.y + label.size.y * 0.5);
+        Rectangle::new(center, label.size).with_texture(self.renderer, &label.texture);
+    }
+
+    fn text_width(&self, text: &str, fs: f32) -> f32 {
+        self.text.font.width(text.as_bytes(), fs)
+    }
+}
@@ -0,0 +1,115 @@
+use grapes::{objects::rectangle::Rectangle, vx2};
+use rand::Rng;
+
+use crate::{
+    common::{BALL_RADIUS, BRICK_MARGIN, ball_base_speed},
+    sprites::{ball::Ball, bricks::brick_rect},
+};
+
+/// Speed of a trapped ball as a share of the base speed.
+pub const TRAPPED_SPEED: f32 = 0.5;
+
+/// Cells walled in by bricks, see `Level::pockets`.
+pub struct Pocket {
+    cells: Vec<(usize, usize)>,
+    /// the room between the bricks around it
+    bounds: Rectangle,
+}
+
+impl Pocket {
+    pub fn new(cells: Vec<(usize, usize)>) -> Self {
+        let (mut left, mut top) = (f32::MAX, f32::MAX);
+        let (mut right, mut bottom) = (f32::MIN, f32::MIN);
+        for &(row, col) in &cells {
+            let r = brick_rect(row, col);
+            left = left.min(r.pos.x - r.size.x * 0.5 - 2.0 * BRICK_MARGIN);
+            right = right.max(r.pos.x + r.size.x * 0.5 + 2.0 * BRICK_MARGIN);
+            top = top.min(r.pos.y - r.size.y * 0.5 - 2.0 * BRICK_MARGIN);
+            bottom = bottom.max(r.pos.y + r.size.y * 0.5 + 2.0 * BRICK_MARGIN);
+        }
+        Self {
+            cells,
+            bounds: Rectangle::new(
+                vx2!((left + right) * 0.5, (top + bottom) * 0.5),
+                vx2!(right - left, bottom - top),
+            ),
+        }
+    }
+
+    pub fn cells(&self) -> &[(usize, usize)] {
+        &self.cells
+    }
+
+    pub fn bounds(&self) -> &Rectangle {
+        &self.bounds
+    }
+
+    /// Breaking the brick at (row, col) opens the pocket.
+    pub fn opened_by(&self, row: usize, col: usize) -> bool {
+        self.cells
+            .iter()
+            .any(|&(r, c)| r.abs_diff(row) + c.abs_diff(col) == 1)
+    }
+}
+
+/// An extra ball of the Cavity mode. It bounces around its pocket until the
+/// pocket is broken open, then it plays like any other ball.
+pub struct CavityBall {
+    pub ball: Ball,
+    /// where it is held, `None` once free
+    pocket: Option<Pocket>,
+}
+
+impl CavityBall {
+    /// A ball in the middle of `pocket`, off in a random direction.
+    pub fn new<R: Rng>(pocket: Pocket, rng: &mut R) -> Self {
+        let mut ball = Ball::new(pocket.bounds.pos);
+        let speed = TRAPPED_SPEED * ball_base_speed();
+        let angle: f32 = rng.random_range(0.2..1.3) * if rng.random_bool(0.5) { 1.0 } else { -1.0 };
+        let down = if rng.random_bool(0.5) { 1.0 } else { -1.0 };
+        ball.set_ball_vel(vx2!(speed * angle.sin(), down * speed * angle.cos()));
+        Self {
+            ball,
+            pocket: Some(pocket),
+        }
+    }
+
+    pub fn is_free(&self) -> bool {
+        self.pocket.is_none()
+    }
+
+    pub fn pocket(&self) -> Option<&Pocket> {
+        self.pocket.as_ref()
+    }
+
+    /// Lets the ball out of its pocket, it keeps its direction and goes on
+    /// at `speed`.
+    pub fn free(&mut self, speed: f32) {
+        self.pocket = None;
+        let v = self.ball.velocity;
+        let len = v.length();
+        if len > 0.0 {
+            self.ball
+                .set_ball_vel(vx2!(v.x * speed / len, v.y * speed / len));
+        }
+    }
+
+    /// Moves a trapped ball a frame, bouncing off the sides of its pocket.
+    pub fn bounce(&mut self) {
+        let Some(pocket) = &self.pocket else {
+            return;
+        };
+        let b = pocket.bounds;
+        let (hx, hy) = (b.size.x * 0.5 - BALL_RADIUS, b.size.y * 0.5 - BALL_RADIUS);
+        self.ball.move_ball();
+        let (pos, vel) = (&mut self.ball.circle.pos, &mut self.ball.velocity);
+        if pos.x < b.pos.x - hx || pos.x > b.pos.x + hx {
+            pos.x = pos.x.clamp(b.pos.x - hx, b.pos.x + hx);
+            vel.x = -vel.x;
+        }
+        if pos.y < b.pos.y - hy || pos.y > b.pos.y + hy {
+            pos.y = pos.y.clamp(b.pos.y - hy, b.pos.y + hy);
+            vel.y = -vel.y;
+        }
+    }
+}
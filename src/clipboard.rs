@@ -0,0 +1,56 @@
+use std::{
+    io::{self, Write},
+    process::{Command, Stdio},
+};
+
+/// X11 clipboard tools to try, with the arguments to copy and to paste.
+const TOOLS: [(&str, &[&str], &[&str]); 2] = [
+    (
+        "xclip",
+        &["-selection", "clipboard"],
+        &["-selection", "clipboard", "-o"],
+    ),
+    (
+        "xsel",
+        &["--clipboard", "--input"],
+        &["--clipboard", "--output"],
+    ),
+];
+
+/// Puts `text` on the clipboard through `xclip` or `xsel`.
+pub fn copy(text: &str) -> io::Result<()> {
+    let mut last = io::Error::new(io::ErrorKind::NotFound, "no clipboard tool");
+    for (tool, args, _) in TOOLS {
+        let result = Command::new(tool)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .and_then(|mut child| {
+                child.stdin.take().unwrap().write_all(text.as_bytes())?;
+                child.wait()
+            });
+        match result {
+            Ok(status) if status.success() => return Ok(()),
+            Ok(status) => last = io::Error::other(format!("{tool} failed: {status}")),
+            Err(err) => last = err,
+        }
+    }
+    Err(last)
+}
+
+/// What is on the clipboard, read through `xclip` or `xsel`.
+pub fn paste() -> io::Result<String> {
+    let mut last = io::Error::new(io::ErrorKind::NotFound, "no clipboard tool");
+    for (tool, _, args) in TOOLS {
+        match Command::new(tool).args(args).stderr(Stdio::null()).output() {
+            Ok(out) if out.status.success() => {
+                return Ok(String::from_utf8_lossy(&out.stdout).into_owned());
+            }
+            Ok(out) => last = io::Error::other(format!("{tool} failed: {}", out.status)),
+            Err(err) => last = err,
+        }
+    }
+    Err(last)
+}
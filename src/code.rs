@@ -0,0 +1,245 @@
+use std::fmt;
+
+use miniz_oxide::{deflate::compress_to_vec, inflate::decompress_to_vec_with_limit};
+
+use crate::{
+    canvas::Rgba,
+    common::{BRICK_COLS, BRICK_ROWS},
+    level::{BrickKind, BrickSpec, Level, PALETTE, checksum},
+};
+
+/// Bumped whenever the layout of the encoded data changes.
+pub const CODE_VERSION: u8 = 1;
+
+/// Crockford's base 32, no `I`, `L`, `O` or `U` to mix up when typing. Codes
+/// are made of these characters.
+pub const ALPHABET: &str = "0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+/// Longest level name a code carries, in bytes.
+const MAX_NAME: usize = 32;
+/// Set on the kind of a brick whose colour is given as an index into `PALETTE`.
+const FROM_PALETTE: u8 = 0x80;
+/// Nothing a valid code holds inflates to more than this, a different brick
+/// and a run for every cell.
+const MAX_DATA: usize = 2 + MAX_NAME + BRICK_ROWS * BRICK_COLS * 17;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum CodeError {
+    Empty,
+    BadChar(char),
+    /// made by a newer or unknown version of the game
+    Version(u8),
+    Checksum,
+    Corrupt(&'static str),
+}
+
+impl fmt::Display for CodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CodeError::Empty => write!(f, "no code"),
+            CodeError::BadChar(c) => write!(f, "`{c}` is not part of a level code"),
+            CodeError::Version(v) => write!(f, "code version {v} is not supported"),
+            CodeError::Checksum => write!(f, "code is mistyped or incomplete"),
+            CodeError::Corrupt(what) => write!(f, "code is damaged: {what}"),
+        }
+    }
+}
+
+impl std::error::Error for CodeError {}
+
+/// Packs `level` into a short code to paste into chat.
+///
+/// The code is base 32 of a version byte, the deflated level data and a
+/// checksum over both. The level data is the name, the distinct bricks the
+/// wall uses, with palette colours as an index, and runs of cells pointing
+/// into them. The rating is not part of the code.
+pub fn encode(level: &Level) -> String {
+    let mut end = level.name.len().min(MAX_NAME);
+    while !level.name.is_char_boundary(end) {
+        end -= 1;
+    }
+    let name = &level.name[..end];
+
+    let mut specs: Vec<BrickSpec> = Vec::new();
+    let mut cells = Vec::with_capacity(BRICK_ROWS * BRICK_COLS);
+    for row in 0..BRICK_ROWS {
+        for col in 0..BRICK_COLS {
+            let cell = level
+                .get(row, col)
+                .map(|b| match specs.iter().position(|s| *s == b) {
+                    Some(i) => i,
+                    None => {
+                        specs.push(b);
+                        specs.len() - 1
+                    }
+                });
+            cells.push(cell.map_or(0, |i| i as u8 + 1));
+        }
+    }
+
+    let mut data = vec![name.len() as u8];
+    data.extend_from_slice(name.as_bytes());
+    data.push(specs.len() as u8);
+    for spec in &specs {
+        let kind = BrickKind::ALL.iter().position(|k| *k == spec.kind).unwrap();
+        match PALETTE.iter().position(|c| *c == spec.color) {
+            Some(i) => data.extend_from_slice(&[kind as u8 | FROM_PALETTE, i as u8]),
+            None => data.extend_from_slice(&[kind as u8, spec.color.r, spec.color.g, spec.color.b]),
+        }
+        push_varint(&mut data, spec.points);
+    }
+    // runs of the same cell, walls are mostly whole rows
+    for run in cells.chunk_by(|a, b| a == b) {
+        for part in run.chunks(u8::MAX as usize) {
+            data.extend_from_slice(&[part.len() as u8, part[0]]);
+        }
+    }
+
+    let mut bytes = vec![CODE_VERSION];
+    bytes.extend(compress_to_vec(&data, 10));
+    bytes.extend_from_slice(&checksum(&bytes).to_be_bytes());
+    to_base32(&bytes)
+}
+
+/// Reads a code made by `encode`. Case, spaces and dashes do not matter.
+pub fn decode(code: &str) -> Result<Level, CodeError> {
+    let bytes = from_base32(code)?;
+    if bytes.is_empty() {
+        return Err(CodeError::Empty);
+    }
+    if bytes.len() < 5 {
+        return Err(CodeError::Checksum);
+    }
+    let (body, sum) = bytes.split_at(bytes.len() - 4);
+    if checksum(body).to_be_bytes() != sum {
+        return Err(CodeError::Checksum);
+    }
+    if body[0] != CODE_VERSION {
+        return Err(CodeError::Version(body[0]));
+    }
+    let data = decompress_to_vec_with_limit(&body[1..], MAX_DATA)
+        .map_err(|_| CodeError::Corrupt("does not inflate"))?;
+    read_level(&data)
+}
+
+fn read_level(data: &[u8]) -> Result<Level, CodeError> {
+    let mut data = data.iter().copied();
+    let mut next = |what| data.next().ok_or(CodeError::Corrupt(what));
+
+    let len = next("no name")? as usize;
+    let name: Vec<u8> = (0..len)
+        .map(|_| next("short name"))
+        .collect::<Result<_, _>>()?;
+    let mut level = Level::new(&String::from_utf8_lossy(&name));
+
+    let mut specs = Vec::new();
+    for _ in 0..next("no bricks")? {
+        let tag = next("short brick")?;
+        let kind = *BrickKind::ALL
+            .get((tag & !FROM_PALETTE) as usize)
+            .ok_or(CodeError::Corrupt("unknown brick kind"))?;
+        let color = if tag & FROM_PALETTE != 0 {
+            *PALETTE
+                .get(next("short brick")? as usize)
+                .ok_or(CodeError::Corrupt("unknown colour"))?
+        } else {
+            Rgba::new(
+                next("short brick")?,
+                next("short brick")?,
+                next("short brick")?,
+                0xFF,
+            )
+        };
+        let points = read_varint(&mut next)?;
+        specs.push(BrickSpec::new(kind, color, points));
+    }
+
+    let mut cell = 0;
+    while cell < BRICK_ROWS * BRICK_COLS {
+        let run = next("short wall")? as usize;
+        let brick = match next("short wall")? {
+            0 => None,
+            i => Some(
+                *specs
+                    .get(i as usize - 1)
+                    .ok_or(CodeError::Corrupt("unknown brick"))?,
+            ),
+        };
+        if run == 0 || cell + run > BRICK_ROWS * BRICK_COLS {
+            return Err(CodeError::Corrupt("wall does not fit"));
+        }
+        for i in cell..cell + run {
+            level.set(i / BRICK_COLS, i % BRICK_COLS, brick);
+        }
+        cell += run;
+    }
+    Ok(level)
+}
+
+fn push_varint(data: &mut Vec<u8>, mut n: usize) {
+    while n >= 0x80 {
+        data.push(n as u8 | 0x80);
+        n >>= 7;
+    }
+    data.push(n as u8);
+}
+
+fn read_varint<F>(next: &mut F) -> Result<usize, CodeError>
+where
+    F: FnMut(&'static str) -> Result<u8, CodeError>,
+{
+    let mut n = 0usize;
+    for shift in (0..usize::BITS).step_by(7) {
+        let b = next("short points")?;
+        n |= ((b & 0x7F) as usize) << shift;
+        if b & 0x80 == 0 {
+            return Ok(n);
+        }
+    }
+    Err(CodeError::Corrupt("points too large"))
+}
+
+fn to_base32(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len() * 8 / 5 + 1);
+    let (mut acc, mut bits) = (0u32, 0);
+    for &b in bytes {
+        acc = (acc << 8) | b as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(ALPHABET.as_bytes()[(acc >> bits) as usize & 31] as char);
+        }
+    }
+    if bits > 0 {
+        out.push(ALPHABET.as_bytes()[(acc << (5 - bits)) as usize & 31] as char);
+    }
+    out
+}
+
+fn from_base32(code: &str) -> Result<Vec<u8>, CodeError> {
+    let mut out = Vec::with_capacity(code.len() * 5 / 8);
+    let (mut acc, mut bits) = (0u32, 0);
+    for c in code.chars() {
+        if c.is_whitespace() || c == '-' {
+            continue;
+        }
+        let value = match c.to_ascii_uppercase() {
+            'O' => 0,
+            'I' | 'L' => 1,
+            c => ALPHABET
+                .chars()
+                .position(|a| a == c)
+                .ok_or(CodeError::BadChar(c))? as u32,
+        };
+        acc = (acc << 5) | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((acc >> bits) as u8);
+        }
+    }
+    // the padding of the last character is always zero
+    if acc & ((1 << bits) - 1) != 0 {
+        return Err(CodeError::Checksum);
+    }
+    Ok(out)
+}
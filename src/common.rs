@@ -1,6 +1,9 @@
-use grapes::{events::input::Events, fonts::Font, linal::vertx2::VX2, renderer::two_d::Renderer};
+use grapes::{linal::vertx2::VX2, vx2};
 
-use crate::screens::Screen;
+use crate::{
+    canvas::Canvas, difficulty::Difficulty, input::Input, level::Level, mode::Mode, rules::Ruleset,
+    screens::Screen, variant::VariantKind,
+};
 
 pub const WIDTH: f32 = 760.0;
 pub const HEIGHT: f32 = 900.0;
@@ -11,10 +14,19 @@ pub const PADDLE_VEL: f32 = 8.0;
 pub const BALL_RADIUS: f32 = 10.0;
 pub const BALL_BASE_VEL_Y: f32 = 6.0;
 pub const BALL_BASE_VEL_X: f32 = 1.0;
-pub const MENU_TITLE: &[u8; 8] = b"Breakout";
-pub const MENU_ACTION: &[u8; 18] = b"Hit Enter To Start";
-pub const MENU_QUIT: &[u8; 18] = b"Hit Escape To Quit";
-pub const MENU_SCORE: &[u8; 10] = b"Your Score";
+pub const MENU_TITLE: &str = "Breakout";
+pub const MENU_ACTION: &str = "Hit Enter To Start";
+pub const MENU_QUIT: &str = "Hit Escape To Quit";
+pub const MENU_SCORE: &str = "Your Score";
+pub const MENU_EDITOR: &str = "Hit E To Edit Levels";
+pub const MENU_PACKS: &str = "Hit P For Level Packs";
+pub const MENU_CODE: &str = "Hit C To Enter A Level Code";
+pub const MENU_DIFFICULTY: &str = "Hit D For Difficulty";
+pub const MENU_RULES: &str = "Hit R For Rules";
+pub const MENU_VARIANT: &str = "Hit V For Variant";
+pub const MENU_MODE: &str = "Hit M For Mode";
+pub const GAME_OVER_TITLE: &str = "Game Over";
+pub const GAME_OVER_ACTION: &str = "Hit Enter To Continue";
 
 pub const START_IN: &[u8; 8] = b"Start in";
 pub const NEXT_BALL_IN: &[u8; 12] = b"Next Ball in";
@@ -27,27 +39,140 @@ pub const BRICK_HEIGHT: f32 = 28.0;
 pub const HEADING_SIZE: f32 = 50.0;
 pub const PAUSE_TEXT_WIDTH: f32 = 130.0;
 pub const PAUSE_TEXT_HEIGHT: f32 = 44.0;
-pub const PAUSE_MARGIN_LEFT: f32 = 4.0;
 pub const PAUSE_FS: f32 = 32.0;
 
-pub trait GameControl {
-    fn update(&mut self, renderer: &mut Renderer, events: &Events) -> Option<Screen>;
-    fn draw(&self, renderer: &mut Renderer);
-    fn get_points(&mut self) -> Option<usize> {
-        return None;
-    }
-    fn set_velocity(&mut self, vel: VX2, level: f32) {
-        unimplemented!("set_velocity");
+/// Length of the ball's base velocity.
+pub fn ball_base_speed() -> f32 {
+    BALL_BASE_VEL_X.hypot(BALL_BASE_VEL_Y)
+}
+
+/// `frames` as minutes and seconds, a started second counts as a whole one.
+pub fn clock_text(frames: usize) -> String {
+    let secs = frames.div_ceil(60);
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
+/// Where a playfield sits in the window. Games are played on the whole
+/// window, split screen versus puts one on each half.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Field {
+    /// x of the field's left edge in the window
+    pub left: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Field {
+    pub const FULL: Field = Field {
+        left: 0.0,
+        width: WIDTH,
+        height: HEIGHT,
+    };
+
+    /// The left and the right half of the window.
+    pub const HALVES: [Field; 2] = [
+        Field {
+            left: 0.0,
+            width: WIDTH * 0.5,
+            height: HEIGHT,
+        },
+        Field {
+            left: WIDTH * 0.5,
+            width: WIDTH * 0.5,
+            height: HEIGHT,
+        },
+    ];
+
+    pub fn center_x(&self) -> f32 {
+        self.left + self.width * 0.5
     }
 
-    fn get_round(&self) -> Option<usize> {
-        None
+    /// Inner side of the left wall.
+    pub fn min_x(&self) -> f32 {
+        self.left + FRAME_OFFSET
     }
 
-    fn prepare_render_score(&mut self, score: usize, font: &Font) {
-        unimplemented!("get_score")
+    /// Inner side of the right wall.
+    pub fn max_x(&self) -> f32 {
+        self.left + self.width - FRAME_OFFSET
     }
-    fn reset(&mut self) {
-        unimplemented!("reset")
+
+    /// The field's border as (from, to) pairs:
+    /// 0: (top_left, top_right)
+    /// 1: (top_right, bottom_right)
+    /// 2: (bottom_left, top_left)
+    /// 3: (bottom_right, bottom_left)
+    /// 4: (game_top_left, game_top_right)
+    pub fn frame_segments(&self) -> [(VX2, VX2); 5] {
+        let (min_x, max_x) = (self.min_x(), self.max_x());
+        let top_left = vx2!(min_x, FRAME_OFFSET);
+        let top_right = vx2!(max_x, FRAME_OFFSET);
+        let bottom_right = vx2!(max_x, self.height - FRAME_OFFSET);
+        let bottom_left = vx2!(min_x, self.height - FRAME_OFFSET);
+        let game_top_left = vx2!(min_x, HEADING_SIZE);
+        let game_top_right = vx2!(max_x, HEADING_SIZE);
+        [
+            (top_left, top_right),
+            (top_right, bottom_right),
+            (bottom_left, top_left),
+            (bottom_right, bottom_left),
+            (game_top_left, game_top_right),
+        ]
     }
 }
+
+/// The window's border, see `Field::frame_segments`.
+pub fn frame_segments() -> [(VX2, VX2); 5] {
+    Field::FULL.frame_segments()
+}
+
+/// Anything that can put itself on screen.
+pub trait Drawable {
+    fn draw(&self, canvas: &mut dyn Canvas);
+}
+
+/// Objects living inside a screen, advanced once per frame by their owner.
+pub trait Sprite: Drawable {
+    fn update(&mut self, input: &dyn Input);
+}
+
+/// What a screen reports back to `Breakout` after an update.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScreenMsg {
+    /// switch to another screen
+    Goto(Screen),
+    /// leave the game
+    Quit,
+    /// points scored by hitting a brick
+    Points(usize),
+    /// a ball was lost, carries the number of balls lost so far
+    BallLost(usize),
+    /// the last breakable brick is gone
+    WallCleared,
+    /// play stage `stage` of pack number `pack` and the ones after it
+    PlayPack { pack: usize, stage: usize },
+    /// try out a level, returns to the editor afterwards
+    PlayLevel(Box<Level>),
+    /// play a wall from a level code, back to the menu once it is cleared
+    PlayCode(Box<Level>),
+    /// the difficulty picked in the menu, for the next game
+    Difficulty(Difficulty),
+    /// the rules picked in the menu
+    Ruleset(Ruleset),
+    /// the game variant picked in the menu
+    Variant(VariantKind),
+    /// the mode picked in the menu
+    Mode(Mode),
+    /// the ball changed speed by itself, carries the new speed level
+    Speed(usize),
+    /// the turn went over to another player, carries the player now active
+    Player(usize),
+    /// a break left a row without breakable bricks, in Versus
+    RowCleared,
+}
+
+/// A full screen. Screens never reach into the game state, they only post
+/// `ScreenMsg`s; the game talks back through the concrete screen types.
+pub trait GameScreen: Drawable {
+    fn update(&mut self, input: &dyn Input, msgs: &mut Vec<ScreenMsg>);
+}
@@ -0,0 +1,153 @@
+use crate::common::{PADDLE_VEL, ball_base_speed};
+
+/// A speed level, reached once the score gets to `score`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Step {
+    pub score: usize,
+    /// ball speed as a multiple of the base speed
+    pub ball: f32,
+    /// paddle speed as a multiple of `PADDLE_VEL`
+    pub paddle: f32,
+}
+
+/// How the game speeds up with the score. Every step is taken from the base
+/// speeds, a level up never builds on the one before.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Curve {
+    /// ordered by score, the first one starts at 0
+    pub steps: &'static [Step],
+    /// fastest the ball may go, in pixels per frame
+    pub max_speed: f32,
+}
+
+/// Speeds for a score, see `Curve::speeds`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Speeds {
+    /// speed level as shown in the heading, starting at 1
+    pub level: usize,
+    /// ball and paddle speed in pixels per frame
+    pub ball: f32,
+    pub paddle: f32,
+}
+
+const fn step(score: usize, ball: f32, paddle: f32) -> Step {
+    Step {
+        score,
+        ball,
+        paddle,
+    }
+}
+
+const EASY: Curve = Curve {
+    steps: &[
+        step(0, 1.0, 1.0),
+        step(200, 1.25, 1.1),
+        step(500, 1.5, 1.2),
+        step(900, 1.75, 1.3),
+        step(1400, 2.0, 1.4),
+    ],
+    max_speed: 14.0,
+};
+
+/// The curve the game always had, minus the paddle speeding up on its own
+/// speed at every level.
+const NORMAL: Curve = Curve {
+    steps: &[
+        step(0, 1.0, 1.0),
+        step(100, 1.5, 1.25),
+        step(300, 2.0, 1.5),
+        step(600, 3.0, 1.75),
+        step(800, 4.0, 2.0),
+        step(1000, 5.0, 2.25),
+    ],
+    max_speed: 32.0,
+};
+
+const HARD: Curve = Curve {
+    steps: &[
+        step(0, 1.5, 1.25),
+        step(80, 2.0, 1.5),
+        step(200, 2.5, 1.75),
+        step(400, 3.5, 2.0),
+        step(600, 4.5, 2.25),
+        step(800, 5.5, 2.5),
+    ],
+    max_speed: 36.0,
+};
+
+const INSANE: Curve = Curve {
+    steps: &[
+        step(0, 2.0, 1.5),
+        step(50, 3.0, 2.0),
+        step(150, 4.0, 2.5),
+        step(300, 5.0, 3.0),
+        step(500, 6.5, 3.5),
+    ],
+    max_speed: 42.0,
+};
+
+impl Curve {
+    /// Speeds once the score is `score`, the ball no faster than `max_speed`.
+    pub fn speeds(&self, score: usize) -> Speeds {
+        let i = self
+            .steps
+            .iter()
+            .rposition(|s| s.score <= score)
+            .unwrap_or(0);
+        let step = self.steps[i];
+        Speeds {
+            level: i + 1,
+            ball: (ball_base_speed() * step.ball).min(self.max_speed),
+            paddle: PADDLE_VEL * step.paddle,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+    Insane,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 4] = [
+        Difficulty::Easy,
+        Difficulty::Normal,
+        Difficulty::Hard,
+        Difficulty::Insane,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+            Difficulty::Insane => "Insane",
+        }
+    }
+
+    /// Case does not matter.
+    pub fn from_name(name: &str) -> Option<Difficulty> {
+        Difficulty::ALL
+            .into_iter()
+            .find(|d| d.name().eq_ignore_ascii_case(name))
+    }
+
+    pub fn curve(self) -> &'static Curve {
+        match self {
+            Difficulty::Easy => &EASY,
+            Difficulty::Normal => &NORMAL,
+            Difficulty::Hard => &HARD,
+            Difficulty::Insane => &INSANE,
+        }
+    }
+
+    /// The next harder preset, after `Insane` comes `Easy` again.
+    pub fn next(self) -> Difficulty {
+        let i = Difficulty::ALL.iter().position(|d| *d == self).unwrap_or(0);
+        Difficulty::ALL[(i + 1) % Difficulty::ALL.len()]
+    }
+}
@@ -0,0 +1,126 @@
+use crate::{bot::Bot, breakout::Breakout, common::ScreenMsg, level::Level, screens::Screen};
+
+/// Bot skills a wall is played at, from struggling to strong.
+pub const SKILLS: [f32; 3] = [0.3, 0.6, 0.9];
+/// Frames per second the game is meant to run at, for reporting times.
+pub const FPS: f32 = 60.0;
+/// A run that takes longer than this, ten minutes, counts as not cleared.
+pub const MAX_TICKS: usize = 10 * 60 * 60;
+const BALLS: usize = 3;
+
+/// How one headless game on a wall went.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Run {
+    pub cleared: bool,
+    pub ticks: usize,
+    pub balls_lost: usize,
+    pub score: usize,
+}
+
+/// Plays `level` once with a bot of `skill` under the normal `Play` rules,
+/// speed ups included, until the wall is cleared, the game is over or
+/// `max_ticks` have passed.
+pub fn simulate(level: &Level, skill: f32, seed: u64, max_ticks: usize) -> Run {
+    let mut game = Breakout::new();
+    game.seed(seed);
+    game.handle(ScreenMsg::PlayCode(Box::new(level.clone())));
+    let mut bot = Bot::new(skill, seed);
+
+    let mut ticks = 0;
+    while *game.screen() == Screen::Play && ticks < max_ticks {
+        let input = bot.input(&game.screens().play);
+        game.update(input);
+        ticks += 1;
+    }
+    let over = *game.screen() == Screen::GameOver;
+    let play = &game.screens().play;
+    Run {
+        cleared: *game.screen() == Screen::Menu,
+        ticks,
+        balls_lost: if over {
+            BALLS
+        } else {
+            BALLS - play.balls_left()
+        },
+        score: game.score(),
+    }
+}
+
+/// All runs at one skill.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkillReport {
+    pub skill: f32,
+    pub runs: Vec<Run>,
+}
+
+impl SkillReport {
+    pub fn clear_rate(&self) -> f32 {
+        self.runs.iter().filter(|r| r.cleared).count() as f32 / self.runs.len().max(1) as f32
+    }
+
+    /// Median seconds to clear over the runs that cleared the wall.
+    pub fn median_time(&self) -> Option<f32> {
+        let mut ticks: Vec<usize> = self
+            .runs
+            .iter()
+            .filter(|r| r.cleared)
+            .map(|r| r.ticks)
+            .collect();
+        ticks.sort_unstable();
+        ticks.get(ticks.len() / 2).map(|&t| t as f32 / FPS)
+    }
+
+    pub fn mean_balls_lost(&self) -> f32 {
+        self.runs.iter().map(|r| r.balls_lost).sum::<usize>() as f32 / self.runs.len().max(1) as f32
+    }
+
+    /// Lowest, 25th percentile, median, 75th percentile and highest score.
+    pub fn scores(&self) -> [usize; 5] {
+        let mut scores: Vec<usize> = self.runs.iter().map(|r| r.score).collect();
+        scores.sort_unstable();
+        let at = |p: f32| {
+            let idx = ((scores.len().max(1) - 1) as f32 * p).round() as usize;
+            scores.get(idx).copied().unwrap_or(0)
+        };
+        [at(0.0), at(0.25), at(0.5), at(0.75), at(1.0)]
+    }
+
+    /// 0 when every run clears without losing a ball, 1 when none clears.
+    fn hardness(&self) -> f32 {
+        let lost = (self.mean_balls_lost() / BALLS as f32).min(1.0);
+        1.0 - self.clear_rate() * (1.0 - 0.5 * lost)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Estimate {
+    pub skills: Vec<SkillReport>,
+    /// from 0, easy for every bot, to 1, too hard for all of them
+    pub rating: f32,
+}
+
+/// Plays `level` `runs` times at each of `SKILLS`. The same seed gives the
+/// same estimate.
+pub fn estimate(level: &Level, runs: usize, seed: u64) -> Estimate {
+    estimate_with(level, &SKILLS, runs, seed, MAX_TICKS)
+}
+
+pub fn estimate_with(
+    level: &Level,
+    skills: &[f32],
+    runs: usize,
+    seed: u64,
+    max_ticks: usize,
+) -> Estimate {
+    let skills: Vec<SkillReport> = skills
+        .iter()
+        .map(|&skill| SkillReport {
+            skill,
+            runs: (0..runs as u64)
+                .map(|i| simulate(level, skill, seed.wrapping_add(i), max_ticks))
+                .collect(),
+        })
+        .collect();
+    let rating = skills.iter().map(SkillReport::hardness).sum::<f32>() / skills.len().max(1) as f32;
+    Estimate { skills, rating }
+}
@@ -0,0 +1,209 @@
+use rand::{Rng, SeedableRng, rngs::StdRng, seq::IndexedRandom};
+
+use crate::{
+    common::{BRICK_COLS, BRICK_ROWS},
+    level::{BrickKind, BrickSpec, Level, PALETTE},
+};
+
+/// How close `generate` gets to the asked difficulty.
+const TOLERANCE: f32 = 0.03;
+
+/// Shapes the generator builds walls from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pattern {
+    /// random left half, mirrored
+    Symmetric,
+    Pyramid,
+    Checkerboard,
+    /// full rows cut by vertical shafts
+    Tunnels,
+    /// full rows with empty pockets inside
+    Cavities,
+}
+
+impl Pattern {
+    pub const ALL: [Pattern; 5] = [
+        Pattern::Symmetric,
+        Pattern::Pyramid,
+        Pattern::Checkerboard,
+        Pattern::Tunnels,
+        Pattern::Cavities,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Pattern::Symmetric => "Symmetric",
+            Pattern::Pyramid => "Pyramid",
+            Pattern::Checkerboard => "Checkerboard",
+            Pattern::Tunnels => "Tunnels",
+            Pattern::Cavities => "Cavities",
+        }
+    }
+
+    /// Which cells of the top `rows` rows get a brick.
+    fn mask(self, rows: usize, rng: &mut StdRng) -> [[bool; BRICK_COLS]; BRICK_ROWS] {
+        let mut mask = [[false; BRICK_COLS]; BRICK_ROWS];
+        let half = BRICK_COLS.div_ceil(2);
+        match self {
+            Pattern::Symmetric => {
+                for row in mask.iter_mut().take(rows) {
+                    for col in 0..half {
+                        let on = rng.random_bool(0.65);
+                        row[col] = on;
+                        row[BRICK_COLS - 1 - col] = on;
+                    }
+                }
+            }
+            Pattern::Pyramid => {
+                for (r, row) in mask.iter_mut().take(rows).enumerate() {
+                    let width = (r + 1) * BRICK_COLS / rows;
+                    let start = (BRICK_COLS - width) / 2;
+                    row[start..start + width].fill(true);
+                }
+            }
+            Pattern::Checkerboard => {
+                let size = rng.random_range(1..=2);
+                for (r, row) in mask.iter_mut().take(rows).enumerate() {
+                    for (c, cell) in row.iter_mut().enumerate() {
+                        *cell = (r / size + c / size) % 2 == 0;
+                    }
+                }
+            }
+            Pattern::Tunnels => {
+                let shafts: Vec<usize> = (0..rng.random_range(1..=3))
+                    .map(|_| rng.random_range(1..BRICK_COLS - 1))
+                    .collect();
+                for row in mask.iter_mut().take(rows) {
+                    for (c, cell) in row.iter_mut().enumerate() {
+                        *cell = !shafts.contains(&c);
+                    }
+                }
+            }
+            Pattern::Cavities => {
+                for row in mask.iter_mut().take(rows) {
+                    row.fill(true);
+                }
+                for _ in 0..rng.random_range(1..=3) {
+                    if rows < 3 {
+                        break;
+                    }
+                    let (h, w) = (rng.random_range(1..=2), rng.random_range(2..=4));
+                    // pockets stay off the bottom row
+                    let top = rng.random_range(0..=rows - 1 - h);
+                    let left = rng.random_range(0..=BRICK_COLS - w);
+                    for row in mask.iter_mut().skip(top).take(h) {
+                        row[left..left + w].fill(false);
+                    }
+                }
+            }
+        }
+        mask
+    }
+}
+
+/// How hard a wall is, 0 for no bricks and 1 for every cell a tough brick.
+/// The classic wall rates 0.5.
+pub fn rate(level: &Level) -> f32 {
+    let hits: f32 = level
+        .bricks()
+        .map(|(_, _, b)| match b.kind.hits() {
+            Some(hits) => hits as f32,
+            // solid bricks get in the way but never need clearing
+            None => 0.5,
+        })
+        .sum();
+    hits / (2 * BRICK_ROWS * BRICK_COLS) as f32
+}
+
+/// A fresh wall from `seed`, the same seed and difficulty always give the same
+/// wall. `difficulty` runs from 0 (a few soft bricks) to 1 (a full, tough wall
+/// with solid obstacles), every breakable brick can be reached.
+pub fn generate(seed: u64, difficulty: f32) -> Level {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let pattern = *Pattern::ALL.choose(&mut rng).unwrap();
+    generate_pattern(pattern, &mut rng, difficulty)
+}
+
+/// Like `generate` with the pattern picked by hand.
+pub fn generate_with(pattern: Pattern, seed: u64, difficulty: f32) -> Level {
+    generate_pattern(pattern, &mut StdRng::seed_from_u64(seed), difficulty)
+}
+
+fn generate_pattern(pattern: Pattern, rng: &mut StdRng, difficulty: f32) -> Level {
+    let d = difficulty.clamp(0.0, 1.0);
+    let rows = (BRICK_ROWS as f32 * (0.4 + 0.6 * d)).round() as usize;
+    let mask = pattern.mask(rows.clamp(2, BRICK_ROWS), rng);
+
+    let mut level = Level::new(pattern.name());
+    let color_shift = rng.random_range(0..PALETTE.len());
+    for (row, cells) in mask.iter().enumerate() {
+        let color = PALETTE[(row / 2 + color_shift) % PALETTE.len()];
+        for (col, on) in cells.iter().enumerate() {
+            if !on {
+                continue;
+            }
+            let kind = if row + 1 < rows && rng.random_bool((0.15 * d) as f64) {
+                BrickKind::Solid
+            } else if rng.random_bool((0.5 * d) as f64) {
+                BrickKind::Tough
+            } else {
+                BrickKind::Normal
+            };
+            level.set(row, col, Some(BrickSpec::row_points(kind, color, row)));
+        }
+    }
+    open_up(&mut level);
+    tune(&mut level, rng, d);
+    level
+}
+
+/// Turns solid bricks into normal ones until every breakable brick can be
+/// reached from below.
+fn open_up(level: &mut Level) {
+    while let Some(&(row, col)) = level.unreachable().first() {
+        for r in row + 1..BRICK_ROWS {
+            if let Some(b) = level.get(r, col).filter(|b| b.kind == BrickKind::Solid) {
+                level.set(
+                    r,
+                    col,
+                    Some(BrickSpec::row_points(BrickKind::Normal, b.color, r)),
+                );
+            }
+        }
+    }
+}
+
+/// Moves the rating towards the one wanted for `difficulty` by changing brick
+/// kinds, then by taking bricks away.
+fn tune(level: &mut Level, rng: &mut StdRng, difficulty: f32) {
+    let target = 0.1 + 0.6 * difficulty;
+    let cells = |level: &Level, kind: BrickKind| -> Vec<(usize, usize)> {
+        level
+            .bricks()
+            .filter(|(_, _, b)| b.kind == kind)
+            .map(|(r, c, _)| (r, c))
+            .collect()
+    };
+    loop {
+        let r = rate(level);
+        if (r - target).abs() <= TOLERANCE {
+            return;
+        }
+        let (from, to) = if r < target {
+            (BrickKind::Normal, Some(BrickKind::Tough))
+        } else if !cells(level, BrickKind::Tough).is_empty() {
+            (BrickKind::Tough, Some(BrickKind::Normal))
+        } else {
+            (BrickKind::Normal, None)
+        };
+        let Some(&(row, col)) = cells(level, from).choose(rng) else {
+            return;
+        };
+        let color = level.get(row, col).unwrap().color;
+        level.set(
+            row,
+            col,
+            to.map(|kind| BrickSpec::row_points(kind, color, row)),
+        );
+    }
+}
@@ -1,140 +1,207 @@
-use grapes::{
-    colors::{color::Color, presets::GrapesColors},
-    fonts::Font,
-    linal::vertx2::VX2,
-    objects::{circle::Circle, rectangle::Rectangle},
-    renderer::two_d::{Render, Renderer},
-    textures::Texture,
-    vx2,
-};
+use grapes::{linal::vertx2::VX2, objects::rectangle::Rectangle, vx2};
 
-use crate::common::{FRAME_OFFSET, GameControl, HEADING_SIZE, WIDTH};
+use crate::{
+    canvas::{Canvas, Rgba},
+    common::{Drawable, FRAME_OFFSET, HEADING_SIZE, WIDTH, clock_text},
+};
 
 const FS: f32 = 32.0;
 
 const BALL_DELTA: f32 = 30.0;
 const BALL_RADIUS: f32 = BALL_DELTA * 0.4;
+/// Room for the clock, it ends just left of the score.
+const CLOCK_WIDTH: f32 = 90.0;
+/// Font size of the two scores in a hot seat game.
+const PLAYER_FS: f32 = 24.0;
+/// Score color of the player waiting for their turn.
+const WAITING: Rgba = Rgba::new(0x30, 0x50, 0x50, 0xFF);
 
 pub struct Disp {
-    texture: Texture,
+    value: String,
+    color: Rgba,
     rectangle: Rectangle,
     fs: f32,
-    background: Color,
+    background: Rgba,
 }
 
 impl Disp {
-    pub fn new(size: VX2, pos: VX2, fs: f32, background: Color) -> Self {
+    pub fn new(size: VX2, pos: VX2, fs: f32, background: Rgba) -> Self {
         Self {
+            value: String::new(),
+            color: background,
             rectangle: Rectangle::new(pos, size),
-            texture: Texture::init_with_background_color(size, background),
             fs,
             background,
         }
     }
 
-    pub fn set_display(&mut self, value: &[u8], font: &Font, color: Color) {
-        self.texture.clear(self.background);
-        font.render_into_texture(
-            value,
-            vx2!(4.0, self.fs + 4.0),
-            self.fs,
-            color,
-            &mut self.texture,
-        );
+    pub fn set_display(&mut self, value: &str, color: Rgba) {
+        self.value = value.to_string();
+        self.color = color;
     }
 
     pub fn reset(&mut self) {
-        self.texture.clear(self.background);
+        self.value.clear();
     }
 
-    pub fn render(&self, renderer: &mut Renderer) {
-        self.rectangle.with_texture(renderer, &self.texture);
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub fn render(&self, canvas: &mut dyn Canvas) {
+        canvas.fill_rect(&self.rectangle, self.background);
+        if !self.value.is_empty() {
+            let top_left = vx2!(
+                self.rectangle.pos.x - self.rectangle.size.x * 0.5,
+                self.rectangle.pos.y - self.rectangle.size.y * 0.5
+            );
+            canvas.label(&self.value, top_left, self.fs, self.color);
+        }
     }
 }
 
 pub struct Heading {
-    circle: Circle,
-    color: Color,
+    center: VX2,
+    color: Rgba,
     ball: usize,
-    pub font: Font,
-    displays: [Disp; 2],
+    /// balls per game
+    balls: usize,
+    /// speed, score and the clock of the timed modes
+    displays: [Disp; 3],
+    /// the scores of a hot seat game, in place of the score
+    players: [Disp; 2],
+    two_players: bool,
 }
 
 impl Heading {
     pub fn reset(&mut self) {
-        self.ball = 3;
-        self.displays[0].set_display(b"Speed: 1", &self.font, GrapesColors::Teal.into());
-        self.displays[1].set_display(b"Score: 0", &self.font, GrapesColors::Teal.into());
+        self.ball = self.balls;
+        self.displays[0].set_display("Speed: 1", Rgba::TEAL);
+        self.displays[1].set_display("Score: 0", Rgba::TEAL);
+        self.displays[2].reset();
+        self.two_players = false;
     }
     pub fn init() -> Self {
-        let font = Font::load("./assets/NotoSansMono.ttf");
-        let circle = Circle::new(
-            vx2!(
-                FRAME_OFFSET * 3.0 + BALL_DELTA,
-                HEADING_SIZE * 0.5 + FRAME_OFFSET
-            ),
-            BALL_RADIUS,
+        let center = vx2!(
+            FRAME_OFFSET * 3.0 + BALL_DELTA,
+            HEADING_SIZE * 0.5 + FRAME_OFFSET
         );
         let half_rect = (WIDTH - 2.0 * FRAME_OFFSET) * 0.15;
         let third = (WIDTH - 2.0 * FRAME_OFFSET) * 0.33;
+        let score_left = 2.0 * third + half_rect - third * 0.5;
         let mut displays = [
             Disp::new(
                 vx2!(third, FS + 8.0),
                 vx2!(third + half_rect, HEADING_SIZE * 0.5),
                 FS,
-                GrapesColors::GrapesBlack.into(),
+                Rgba::BLACK,
             ),
             Disp::new(
                 vx2!(third, FS + 8.0),
                 vx2!(2.0 * third + half_rect, HEADING_SIZE * 0.5),
                 FS,
-                GrapesColors::GrapesBlack.into(),
+                Rgba::BLACK,
+            ),
+            Disp::new(
+                vx2!(CLOCK_WIDTH, FS + 8.0),
+                vx2!(score_left - CLOCK_WIDTH * 0.5 - 4.0, HEADING_SIZE * 0.5),
+                FS,
+                Rgba::BLACK,
             ),
         ];
-        displays[0].set_display(b"Speed: 1", &font, GrapesColors::Teal.into());
-        displays[1].set_display(b"Score: 0", &font, GrapesColors::Teal.into());
+        displays[0].set_display("Speed: 1", Rgba::TEAL);
+        displays[1].set_display("Score: 0", Rgba::TEAL);
+        let players = [0.25, 0.75].map(|at| {
+            Disp::new(
+                vx2!(third * 0.5, FS + 8.0),
+                vx2!(score_left + third * at, HEADING_SIZE * 0.5),
+                PLAYER_FS,
+                Rgba::BLACK,
+            )
+        });
         Self {
-            circle,
-            color: GrapesColors::Teal.into(),
+            center,
+            color: Rgba::TEAL,
             ball: 3,
-            font,
+            balls: 3,
             displays,
+            players,
+            two_players: false,
         }
     }
     pub fn set_ball(&mut self, round: usize) {
-        self.ball = 3 - round;
+        self.ball = self.balls - round;
+    }
+
+    /// One more ball, still to play.
+    pub fn add_ball(&mut self) {
+        self.balls += 1;
+        self.ball += 1;
+    }
+
+    /// Shows `balls` balls, all of them still to play.
+    pub fn set_balls(&mut self, balls: usize) {
+        self.balls = balls;
+        self.ball = balls;
     }
 
     pub fn set_score(&mut self, p: usize) {
-        self.displays[1].set_display(format!("Score: {p}").as_bytes(), &self.font, self.color);
+        self.displays[1].set_display(&format!("Score: {p}"), self.color);
     }
     pub fn set_speed(&mut self, s: usize) {
-        self.displays[0].set_display(format!("Speed: {s}").as_bytes(), &self.font, self.color);
+        self.displays[0].set_display(&format!("Speed: {s}"), self.color);
+    }
+
+    /// Shows the scores of both players in place of the score, the one of
+    /// the `active` player stands out.
+    pub fn set_players(&mut self, scores: [usize; 2], active: usize) {
+        self.two_players = true;
+        for (i, display) in self.players.iter_mut().enumerate() {
+            let color = if i == active { self.color } else { WAITING };
+            display.set_display(&format!("P{}: {}", i + 1, scores[i]), color);
+        }
+    }
+
+    /// The score shown for `player` in a hot seat game and whether it is
+    /// the active one.
+    pub fn player(&self, player: usize) -> Option<(&str, bool)> {
+        let display = self.players.get(player).filter(|_| self.two_players)?;
+        Some((display.value(), display.color == self.color))
     }
-}
 
-impl GameControl for Heading {
-    fn update(
-        &mut self,
-        renderer: &mut grapes::renderer::two_d::Renderer,
-        events: &grapes::events::input::Events,
-    ) -> Option<crate::screens::Screen> {
-        todo!()
+    /// What the clock shows, `None` while it is hidden.
+    pub fn clock(&self) -> Option<&str> {
+        Some(self.displays[2].value()).filter(|v| !v.is_empty())
     }
 
-    fn draw(&self, renderer: &mut grapes::renderer::two_d::Renderer) {
+    /// Shows `frames` on the clock, `None` hides it.
+    pub fn set_clock(&mut self, frames: Option<usize>) {
+        match frames {
+            Some(frames) => self.displays[2].set_display(&clock_text(frames), self.color),
+            None => self.displays[2].reset(),
+        }
+    }
+}
+
+impl Drawable for Heading {
+    fn draw(&self, canvas: &mut dyn Canvas) {
         for display in self.displays.iter() {
-            display.render(renderer);
+            display.render(canvas);
+        }
+        if self.two_players {
+            for display in self.players.iter() {
+                display.render(canvas);
+            }
         }
-        for i in 0..3 {
-            let mut b = self.circle.clone();
-            b.pos.x = (i + 1) as f32 * BALL_DELTA;
-            b.fill_clr(renderer, Color::from(GrapesColors::GrapesBlack));
-            b.draw_clr(renderer, self.color);
+        for i in 0..self.balls {
+            let mut pos = self.center;
+            pos.x = (i + 1) as f32 * BALL_DELTA;
+            canvas.fill_circle(pos, BALL_RADIUS, Rgba::BLACK);
+            canvas.stroke_circle(pos, BALL_RADIUS, self.color);
 
             if i < self.ball {
                 //fill
-                b.fill_clr(renderer, self.color);
+                canvas.fill_circle(pos, BALL_RADIUS, self.color);
             }
         }
     }
@@ -0,0 +1,61 @@
+use crate::sprites::bricks::Bricks;
+
+/// Players in a hot seat game.
+pub const PLAYERS: usize = 2;
+
+/// Two players taking turns at the same game, like the arcade original. The
+/// turn goes over with every lost ball, each player keeps their own wall and
+/// balls. `Play` holds the active player's, the waiting one's are kept here.
+pub struct HotSeat {
+    active: usize,
+    /// the waiting player's wall as they left it
+    bricks: Bricks,
+    /// balls the waiting player lost so far
+    round: usize,
+    /// players without balls left
+    out: [bool; PLAYERS],
+}
+
+impl HotSeat {
+    /// Both players start on `bricks`, player 0 goes first.
+    pub fn new(bricks: &Bricks) -> Self {
+        Self {
+            active: 0,
+            bricks: bricks.clone(),
+            round: 0,
+            out: [false; PLAYERS],
+        }
+    }
+
+    pub fn active(&self) -> usize {
+        self.active
+    }
+
+    pub fn waiting(&self) -> usize {
+        (self.active + 1) % PLAYERS
+    }
+
+    pub fn is_out(&self, player: usize) -> bool {
+        self.out[player]
+    }
+
+    /// The waiting player gets `bricks` too, for a wall put up before the
+    /// first turn.
+    pub fn set_wall(&mut self, bricks: &Bricks) {
+        self.bricks = bricks.clone();
+    }
+
+    /// The active player lost their last ball.
+    pub fn retire(&mut self) {
+        self.out[self.active] = true;
+    }
+
+    /// Hands the turn over, the active player's wall and lost balls are
+    /// swapped with the waiting one's. Returns the player now active.
+    pub fn switch(&mut self, bricks: &mut Bricks, round: &mut usize) -> usize {
+        std::mem::swap(&mut self.bricks, bricks);
+        std::mem::swap(&mut self.round, round);
+        self.active = self.waiting();
+        self.active
+    }
+}
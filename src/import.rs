@@ -0,0 +1,191 @@
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    canvas::Rgba,
+    common::{BRICK_COLS, BRICK_ROWS},
+    level::{BrickKind, BrickSpec, LEVEL_EXT, Level, LevelError, levels_dir, parse_color},
+};
+
+/// Pixels less opaque than this are gaps.
+const MIN_ALPHA: u8 = 0x80;
+
+#[derive(Debug)]
+pub enum ImportError {
+    Io(io::Error),
+    Png(png::DecodingError),
+    /// the image has more pixels than the wall has cells
+    TooLarge {
+        width: u32,
+        height: u32,
+    },
+    Kinds {
+        line: usize,
+        msg: String,
+    },
+    Save(LevelError),
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportError::Io(err) => write!(f, "could not read: {err}"),
+            ImportError::Png(err) => write!(f, "could not decode image: {err}"),
+            ImportError::TooLarge { width, height } => write!(
+                f,
+                "image is {width}x{height}, at most {BRICK_COLS}x{BRICK_ROWS} fits the wall"
+            ),
+            ImportError::Kinds { line, msg } => write!(f, "kinds line {line}: {msg}"),
+            ImportError::Save(err) => write!(f, "could not save level: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for ImportError {}
+
+impl From<io::Error> for ImportError {
+    fn from(err: io::Error) -> Self {
+        ImportError::Io(err)
+    }
+}
+
+impl From<png::DecodingError> for ImportError {
+    fn from(err: png::DecodingError) -> Self {
+        ImportError::Png(err)
+    }
+}
+
+/// Which pixel colours become which kind of brick, any other colour is a
+/// normal brick. Files have one `#rrggbb = <normal|tough|solid>` per line:
+///
+/// ```text
+/// # grey bricks take two hits, white ones never break
+/// #808080 = tough
+/// #ffffff = solid
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct KindMap {
+    kinds: Vec<(Rgba, BrickKind)>,
+}
+
+impl Default for KindMap {
+    /// Grey is tough and white is solid.
+    fn default() -> Self {
+        Self {
+            kinds: vec![
+                (Rgba::new(0x80, 0x80, 0x80, 0xFF), BrickKind::Tough),
+                (Rgba::new(0xFF, 0xFF, 0xFF, 0xFF), BrickKind::Solid),
+            ],
+        }
+    }
+}
+
+impl KindMap {
+    /// Every colour a normal brick.
+    pub fn empty() -> Self {
+        Self { kinds: Vec::new() }
+    }
+
+    pub fn set(&mut self, color: Rgba, kind: BrickKind) {
+        self.kinds.retain(|(c, _)| *c != color);
+        self.kinds.push((color, kind));
+    }
+
+    pub fn kind(&self, color: Rgba) -> BrickKind {
+        self.kinds
+            .iter()
+            .find(|(c, _)| *c == color)
+            .map_or(BrickKind::Normal, |(_, k)| *k)
+    }
+
+    pub fn parse(text: &str) -> Result<KindMap, ImportError> {
+        let mut map = KindMap::empty();
+        for (i, line) in text.lines().enumerate() {
+            let err = |msg: String| ImportError::Kinds { line: i + 1, msg };
+            let line = line.trim();
+            // colours start with `#` too, comments have a space after it
+            if line.is_empty() || line.starts_with("# ") || line == "#" {
+                continue;
+            }
+            let Some((color, kind)) = line.split_once('=') else {
+                return Err(err(format!("expected `#rrggbb = kind`, got `{line}`")));
+            };
+            let (color, kind) = (color.trim(), kind.trim());
+            let color = parse_color(color).ok_or(err(format!("bad colour `{color}`")))?;
+            let kind =
+                BrickKind::from_name(kind).ok_or(err(format!("unknown brick kind `{kind}`")))?;
+            map.set(color, kind);
+        }
+        Ok(map)
+    }
+
+    pub fn load(path: &Path) -> Result<KindMap, ImportError> {
+        KindMap::parse(&fs::read_to_string(path)?)
+    }
+}
+
+/// Turns RGBA pixels, row by row, into a wall. Pixel `(x, y)` is the brick
+/// in column `x` of row `y`, see `brick_rect`, its colour the brick colour.
+/// Points follow the row as in the classic wall.
+pub fn from_pixels(
+    name: &str,
+    width: u32,
+    height: u32,
+    pixels: &[u8],
+    kinds: &KindMap,
+) -> Result<Level, ImportError> {
+    if width as usize > BRICK_COLS || height as usize > BRICK_ROWS {
+        return Err(ImportError::TooLarge { width, height });
+    }
+    let mut level = Level::new(name);
+    for (i, px) in pixels.chunks_exact(4).enumerate() {
+        let (row, col) = (i / width as usize, i % width as usize);
+        if row >= height as usize || px[3] < MIN_ALPHA {
+            continue;
+        }
+        let color = Rgba::new(px[0], px[1], px[2], 0xFF);
+        let brick = BrickSpec::row_points(kinds.kind(color), color, row);
+        level.set(row, col, Some(brick));
+    }
+    Ok(level)
+}
+
+/// Reads a PNG of at most `BRICK_COLS` x `BRICK_ROWS` pixels into a wall
+/// named after the file.
+pub fn import_png(path: &Path, kinds: &KindMap) -> Result<Level, ImportError> {
+    let mut decoder = png::Decoder::new(fs::File::open(path)?);
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info()?;
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buf)?;
+    buf.truncate(info.buffer_size());
+
+    let rgba: Vec<u8> = match info.color_type {
+        png::ColorType::Rgba => buf,
+        png::ColorType::Rgb => buf
+            .chunks_exact(3)
+            .flat_map(|p| [p[0], p[1], p[2], 0xFF])
+            .collect(),
+        png::ColorType::GrayscaleAlpha => buf
+            .chunks_exact(2)
+            .flat_map(|p| [p[0], p[0], p[0], p[1]])
+            .collect(),
+        // indexed images are expanded by `normalize_to_color8`
+        png::ColorType::Grayscale | png::ColorType::Indexed => {
+            buf.iter().flat_map(|&g| [g, g, g, 0xFF]).collect()
+        }
+    };
+    let name = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "Imported".to_string());
+    from_pixels(&name, info.width, info.height, &rgba, kinds)
+}
+
+/// Where an imported image is saved without `--out`.
+pub fn default_output(image: &Path) -> PathBuf {
+    let stem = image.file_stem().unwrap_or_default().to_string_lossy();
+    levels_dir().join(format!("{stem}.{LEVEL_EXT}"))
+}
@@ -0,0 +1,290 @@
+use std::collections::HashSet;
+
+use grapes::events::{input::Events, keyboard::K};
+
+/// The keys the game reacts to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Key {
+    Enter,
+    Escape,
+    Space,
+    Left,
+    Right,
+    Up,
+    Down,
+    Backspace,
+    Tab,
+    A,
+    C,
+    D,
+    E,
+    F,
+    G,
+    H,
+    J,
+    K,
+    L,
+    M,
+    P,
+    R,
+    S,
+    V,
+    X,
+    Y,
+    Z,
+}
+
+impl Key {
+    pub const ALL: [Key; 27] = [
+        Key::Enter,
+        Key::Escape,
+        Key::Space,
+        Key::Left,
+        Key::Right,
+        Key::Up,
+        Key::Down,
+        Key::Backspace,
+        Key::Tab,
+        Key::A,
+        Key::C,
+        Key::D,
+        Key::E,
+        Key::F,
+        Key::G,
+        Key::H,
+        Key::J,
+        Key::K,
+        Key::L,
+        Key::M,
+        Key::P,
+        Key::R,
+        Key::S,
+        Key::V,
+        Key::X,
+        Key::Y,
+        Key::Z,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Key::Enter => "Enter",
+            Key::Escape => "Escape",
+            Key::Space => "Space",
+            Key::Left => "Left",
+            Key::Right => "Right",
+            Key::Up => "Up",
+            Key::Down => "Down",
+            Key::Backspace => "Backspace",
+            Key::Tab => "Tab",
+            Key::A => "A",
+            Key::C => "C",
+            Key::D => "D",
+            Key::E => "E",
+            Key::F => "F",
+            Key::G => "G",
+            Key::H => "H",
+            Key::J => "J",
+            Key::K => "K",
+            Key::L => "L",
+            Key::M => "M",
+            Key::P => "P",
+            Key::R => "R",
+            Key::S => "S",
+            Key::V => "V",
+            Key::X => "X",
+            Key::Y => "Y",
+            Key::Z => "Z",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Key> {
+        Key::ALL.into_iter().find(|k| k.name() == name)
+    }
+
+    fn k(self) -> K {
+        match self {
+            Key::Enter => K::Enter,
+            Key::Escape => K::Escape,
+            Key::Space => K::Space,
+            Key::Left => K::ArrowLeft,
+            Key::Right => K::ArrowRight,
+            Key::Up => K::ArrowUp,
+            Key::Down => K::ArrowDown,
+            Key::Backspace => K::Backspace,
+            Key::Tab => K::Tab,
+            Key::A => K::A,
+            Key::C => K::C,
+            Key::D => K::D,
+            Key::E => K::E,
+            Key::F => K::F,
+            Key::G => K::G,
+            Key::H => K::H,
+            Key::J => K::J,
+            Key::K => K::K,
+            Key::L => K::L,
+            Key::M => K::M,
+            Key::P => K::P,
+            Key::R => K::R,
+            Key::S => K::S,
+            Key::V => K::V,
+            Key::X => K::X,
+            Key::Y => K::Y,
+            Key::Z => K::Z,
+        }
+    }
+}
+
+/// Keyboard state for one frame. Screens and sprites only see this, so they
+/// can be driven by the window or by a script.
+pub trait Input {
+    /// the key is held during this frame
+    fn key_down(&self, key: Key) -> bool;
+    /// the key went down in this frame
+    fn key_pressed(&self, key: Key) -> bool;
+    /// characters typed in this frame, see `spin_text` for getting text
+    /// without them
+    fn text(&self) -> &str {
+        ""
+    }
+}
+
+/// The window reports keys only, typed text keeps its default.
+impl Input for Events {
+    fn key_down(&self, key: Key) -> bool {
+        Events::key_down(self, key.k())
+    }
+
+    fn key_pressed(&self, key: Key) -> bool {
+        Events::key_pressed(self, key.k())
+    }
+}
+
+/// Puts text together with the arrows, for where nothing is typed: `Up` and
+/// `Down` turn the last character of `text` through `alphabet`, `Right` adds
+/// the first character of `alphabet` to turn next. Returns whether `text`
+/// changed.
+pub fn spin_text(text: &mut String, input: &dyn Input, alphabet: &str) -> bool {
+    let chars: Vec<char> = alphabet.chars().collect();
+    let added = input.key_pressed(Key::Right);
+    if added {
+        text.push(chars[0]);
+    }
+    let step = match (input.key_pressed(Key::Up), input.key_pressed(Key::Down)) {
+        (true, false) => 1,
+        (false, true) => chars.len() - 1,
+        _ => return added,
+    };
+    let at = match text.pop() {
+        Some(c) => chars.iter().position(|a| *a == c).map_or(0, |i| i + step),
+        // turning nothing starts at either end
+        None if step == 1 => 0,
+        None => chars.len() - 1,
+    };
+    text.push(chars[at % chars.len()]);
+    true
+}
+
+/// What one player of a versus game does in a frame: move left, move right
+/// and serve. It plays their side as the arrows and `Space` would.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Pad {
+    pub left: bool,
+    pub right: bool,
+    pub serve: bool,
+}
+
+impl Pad {
+    /// Reads the pad from `input`, `keys` are left, right and serve.
+    pub fn read(input: &dyn Input, keys: [Key; 3]) -> Self {
+        let [left, right, serve] = keys;
+        let held = |key| input.key_down(key) || input.key_pressed(key);
+        Self {
+            left: held(left),
+            right: held(right),
+            serve: input.key_pressed(serve),
+        }
+    }
+
+    /// The pad packed into a byte, for sending it over the network.
+    pub fn bits(self) -> u8 {
+        self.left as u8 | (self.right as u8) << 1 | (self.serve as u8) << 2
+    }
+
+    pub fn from_bits(bits: u8) -> Self {
+        Self {
+            left: bits & 1 != 0,
+            right: bits & 2 != 0,
+            serve: bits & 4 != 0,
+        }
+    }
+}
+
+impl Input for Pad {
+    fn key_down(&self, key: Key) -> bool {
+        match key {
+            Key::Left => self.left,
+            Key::Right => self.right,
+            _ => false,
+        }
+    }
+
+    fn key_pressed(&self, key: Key) -> bool {
+        key == Key::Space && self.serve
+    }
+}
+
+/// Input set up by hand, for tests, replays and bots.
+#[derive(Debug, Clone, Default)]
+pub struct FakeInput {
+    down: HashSet<Key>,
+    pressed: HashSet<Key>,
+    text: String,
+}
+
+impl FakeInput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Holds `key` and marks it as freshly pressed if it was not held before.
+    pub fn press(&mut self, key: Key) {
+        if self.down.insert(key) {
+            self.pressed.insert(key);
+        }
+    }
+
+    pub fn release(&mut self, key: Key) {
+        self.down.remove(&key);
+        self.pressed.remove(&key);
+    }
+
+    pub fn release_all(&mut self) {
+        self.down.clear();
+        self.pressed.clear();
+    }
+
+    /// Types `text` in this frame.
+    pub fn type_text(&mut self, text: &str) {
+        self.text.push_str(text);
+    }
+
+    /// Call after each frame, keys that are still held are no longer new.
+    pub fn next_frame(&mut self) {
+        self.pressed.clear();
+        self.text.clear();
+    }
+}
+
+impl Input for FakeInput {
+    fn key_down(&self, key: Key) -> bool {
+        self.down.contains(&key)
+    }
+
+    fn key_pressed(&self, key: Key) -> bool {
+        self.pressed.contains(&key)
+    }
+
+    fn text(&self) -> &str {
+        &self.text
+    }
+}
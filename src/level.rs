@@ -0,0 +1,393 @@
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    assets::data_home,
+    canvas::Rgba,
+    common::{BRICK_COLS, BRICK_ROWS},
+};
+
+/// File extension of level files.
+pub const LEVEL_EXT: &str = "level";
+
+/// Points of the classic wall, top row first.
+pub const POINTS: [usize; BRICK_ROWS] = [100, 100, 50, 50, 25, 25, 10, 10];
+
+/// Brick colours to pick from, the first four are the classic rows.
+pub const PALETTE: [Rgba; 6] = [
+    Rgba::new(0xDA, 0x2A, 0x47, 0xFF),
+    Rgba::new(0xB4, 0x4A, 0x5F, 0xFF),
+    Rgba::new(0x8E, 0x6F, 0x77, 0xFF),
+    Rgba::new(0x04, 0x8C, 0x7F, 0xFF),
+    Rgba::new(0xD9, 0x8E, 0x04, 0xFF),
+    Rgba::new(0x3B, 0x5B, 0xA5, 0xFF),
+];
+
+/// Point values to pick from.
+pub const POINT_STEPS: [usize; 6] = [10, 25, 50, 100, 200, 500];
+
+/// Where the editor saves levels.
+pub fn levels_dir() -> PathBuf {
+    data_home().join("levels")
+}
+
+/// 32 bit FNV-1a hash, catches damaged or edited level data.
+pub fn checksum(data: &[u8]) -> u32 {
+    data.iter().fold(0x811c_9dc5, |hash, &b| {
+        (hash ^ b as u32).wrapping_mul(0x0100_0193)
+    })
+}
+
+fn classic_color(row: usize) -> Rgba {
+    PALETTE[(row / 2).min(3)]
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BrickKind {
+    #[default]
+    Normal,
+    /// takes two hits
+    Tough,
+    /// never breaks
+    Solid,
+}
+
+impl BrickKind {
+    pub const ALL: [BrickKind; 3] = [BrickKind::Normal, BrickKind::Tough, BrickKind::Solid];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            BrickKind::Normal => "normal",
+            BrickKind::Tough => "tough",
+            BrickKind::Solid => "solid",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<BrickKind> {
+        BrickKind::ALL.into_iter().find(|k| k.name() == name)
+    }
+
+    /// Hits needed to break the brick, `None` if it can not break.
+    pub fn hits(self) -> Option<usize> {
+        match self {
+            BrickKind::Normal => Some(1),
+            BrickKind::Tough => Some(2),
+            BrickKind::Solid => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BrickSpec {
+    pub kind: BrickKind,
+    pub color: Rgba,
+    pub points: usize,
+}
+
+impl BrickSpec {
+    pub fn new(kind: BrickKind, color: Rgba, points: usize) -> Self {
+        Self {
+            kind,
+            color,
+            points,
+        }
+    }
+
+    /// Worth what the classic wall pays for `row`, twice that for tough
+    /// bricks and nothing for solid ones.
+    pub fn row_points(kind: BrickKind, color: Rgba, row: usize) -> Self {
+        let points = match kind {
+            BrickKind::Normal => POINTS[row],
+            BrickKind::Tough => 2 * POINTS[row],
+            BrickKind::Solid => 0,
+        };
+        Self::new(kind, color, points)
+    }
+}
+
+#[derive(Debug)]
+pub enum LevelError {
+    Io(io::Error),
+    Parse { line: usize, msg: String },
+}
+
+impl fmt::Display for LevelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LevelError::Io(err) => write!(f, "could not access level: {err}"),
+            LevelError::Parse { line, msg } => write!(f, "line {line}: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for LevelError {}
+
+impl From<io::Error> for LevelError {
+    fn from(err: io::Error) -> Self {
+        LevelError::Io(err)
+    }
+}
+
+/// A wall of bricks on the `BRICK_ROWS` x `BRICK_COLS` grid.
+///
+/// Files are plain text, one `key = value` per line, lines starting with `#`
+/// are comments:
+///
+/// ```text
+/// name = Checkers
+/// rating = 0.42
+/// brick = <row> <col> <normal|tough|solid> <#rrggbb> <points>
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Level {
+    pub name: String,
+    /// difficulty from 0 to 1 found by `estimate`, if it was run
+    pub rating: Option<f32>,
+    cells: [Option<BrickSpec>; BRICK_ROWS * BRICK_COLS],
+}
+
+impl Level {
+    /// A level without bricks.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            rating: None,
+            cells: [None; BRICK_ROWS * BRICK_COLS],
+        }
+    }
+
+    /// The full wall the game always started with.
+    pub fn classic() -> Self {
+        let mut level = Level::new("Classic");
+        for (row, points) in POINTS.into_iter().enumerate() {
+            let spec = BrickSpec::new(BrickKind::Normal, classic_color(row), points);
+            level.fill_row(row, Some(spec));
+        }
+        level
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<BrickSpec> {
+        self.cells[row * BRICK_COLS + col]
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, brick: Option<BrickSpec>) {
+        self.cells[row * BRICK_COLS + col] = brick;
+    }
+
+    pub fn fill_row(&mut self, row: usize, brick: Option<BrickSpec>) {
+        for col in 0..BRICK_COLS {
+            self.set(row, col, brick);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.cells = [None; BRICK_ROWS * BRICK_COLS];
+    }
+
+    /// Every brick with its row and column, row by row.
+    pub fn bricks(&self) -> impl Iterator<Item = (usize, usize, BrickSpec)> + '_ {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter_map(|(i, b)| b.map(|b| (i / BRICK_COLS, i % BRICK_COLS, b)))
+    }
+
+    pub fn brick_count(&self) -> usize {
+        self.cells.iter().flatten().count()
+    }
+
+    /// Bricks that have to go to clear the wall.
+    pub fn breakable_count(&self) -> usize {
+        self.bricks()
+            .filter(|(_, _, b)| b.kind != BrickKind::Solid)
+            .count()
+    }
+
+    /// Breakable bricks the ball can not get to because solid bricks wall
+    /// them in. The ball comes from below and breaks its way up.
+    pub fn unreachable(&self) -> Vec<(usize, usize)> {
+        self.unreachable_by(true)
+    }
+
+    /// Like `unreachable`, for a ball that only gets into the holes broken
+    /// bricks leave if it `fits` through them. Empty cells are open
+    /// playfield either way.
+    pub fn unreachable_by(&self, fits: bool) -> Vec<(usize, usize)> {
+        let solid = |row: usize, col: usize| {
+            self.get(row, col)
+                .is_some_and(|b| b.kind == BrickKind::Solid)
+        };
+        let mut seen = [[false; BRICK_COLS]; BRICK_ROWS];
+        let mut todo: Vec<(usize, usize)> = (0..BRICK_COLS)
+            .map(|col| (BRICK_ROWS - 1, col))
+            .filter(|&(row, col)| !solid(row, col))
+            .collect();
+        while let Some((row, col)) = todo.pop() {
+            if seen[row][col] {
+                continue;
+            }
+            seen[row][col] = true;
+            if !fits && self.get(row, col).is_some() {
+                continue;
+            }
+            let next = [
+                (row.wrapping_sub(1), col),
+                (row + 1, col),
+                (row, col.wrapping_sub(1)),
+                (row, col + 1),
+            ];
+            for (r, c) in next {
+                if r < BRICK_ROWS && c < BRICK_COLS && !seen[r][c] && !solid(r, c) {
+                    todo.push((r, c));
+                }
+            }
+        }
+        self.bricks()
+            .filter(|&(row, col, b)| b.kind != BrickKind::Solid && !seen[row][col])
+            .map(|(row, col, _)| (row, col))
+            .collect()
+    }
+
+    /// Empty cells walled in by bricks, grouped into pockets of cells that
+    /// touch. The ball only gets into them by breaking a brick. The empty
+    /// cells of the bottom row are open to the playfield.
+    pub fn pockets(&self) -> Vec<Vec<(usize, usize)>> {
+        let mut seen = [[false; BRICK_COLS]; BRICK_ROWS];
+        for col in 0..BRICK_COLS {
+            self.empty_around((BRICK_ROWS - 1, col), &mut seen);
+        }
+        let mut pockets = Vec::new();
+        for row in 0..BRICK_ROWS {
+            for col in 0..BRICK_COLS {
+                let pocket = self.empty_around((row, col), &mut seen);
+                if !pocket.is_empty() {
+                    pockets.push(pocket);
+                }
+            }
+        }
+        pockets
+    }
+
+    /// The empty cells connected to `start` that are not `seen` yet, they
+    /// are marked as seen.
+    fn empty_around(
+        &self,
+        start: (usize, usize),
+        seen: &mut [[bool; BRICK_COLS]; BRICK_ROWS],
+    ) -> Vec<(usize, usize)> {
+        let mut cells = Vec::new();
+        let mut todo = vec![start];
+        while let Some((row, col)) = todo.pop() {
+            if row >= BRICK_ROWS || col >= BRICK_COLS || seen[row][col] {
+                continue;
+            }
+            if self.get(row, col).is_some() {
+                continue;
+            }
+            seen[row][col] = true;
+            cells.push((row, col));
+            todo.extend([
+                (row.wrapping_sub(1), col),
+                (row + 1, col),
+                (row, col.wrapping_sub(1)),
+                (row, col + 1),
+            ]);
+        }
+        cells.sort_unstable();
+        cells
+    }
+
+    pub fn parse(text: &str) -> Result<Level, LevelError> {
+        let mut level = Level::new("Untitled");
+        for (i, line) in text.lines().enumerate() {
+            let err = |msg: String| LevelError::Parse { line: i + 1, msg };
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                return Err(err(format!("expected `key = value`, got `{line}`")));
+            };
+            match key.trim() {
+                "name" => level.name = value.trim().to_string(),
+                "rating" => match value.trim().parse::<f32>() {
+                    Ok(r) if (0.0..=1.0).contains(&r) => level.rating = Some(r),
+                    _ => return Err(err(format!("rating `{}` is not in 0..1", value.trim()))),
+                },
+                "brick" => {
+                    let (row, col, brick) = parse_brick(value).map_err(err)?;
+                    level.set(row, col, Some(brick));
+                }
+                key => return Err(err(format!("unknown key `{key}`"))),
+            }
+        }
+        Ok(level)
+    }
+
+    pub fn load(path: &Path) -> Result<Level, LevelError> {
+        Level::parse(&fs::read_to_string(path)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), LevelError> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_string())?;
+        Ok(())
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "name = {}", self.name)?;
+        if let Some(rating) = self.rating {
+            writeln!(f, "rating = {rating:.2}")?;
+        }
+        for (row, col, b) in self.bricks() {
+            writeln!(
+                f,
+                "brick = {row} {col} {} #{:02x}{:02x}{:02x} {}",
+                b.kind.name(),
+                b.color.r,
+                b.color.g,
+                b.color.b,
+                b.points
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_brick(value: &str) -> Result<(usize, usize, BrickSpec), String> {
+    let parts: Vec<&str> = value.split_whitespace().collect();
+    let [row, col, kind, color, points] = parts[..] else {
+        return Err(format!(
+            "expected `<row> <col> <kind> <#rrggbb> <points>`, got `{}`",
+            value.trim()
+        ));
+    };
+    let index = |v: &str, max: usize, what: &str| match v.parse::<usize>() {
+        Ok(n) if n < max => Ok(n),
+        _ => Err(format!("{what} `{v}` is not in 0..{max}")),
+    };
+    let row = index(row, BRICK_ROWS, "row")?;
+    let col = index(col, BRICK_COLS, "column")?;
+    let kind = BrickKind::from_name(kind).ok_or(format!("unknown brick kind `{kind}`"))?;
+    let color = parse_color(color).ok_or(format!("bad colour `{color}`"))?;
+    let points = points
+        .parse()
+        .map_err(|_| format!("bad points `{points}`"))?;
+    Ok((row, col, BrickSpec::new(kind, color, points)))
+}
+
+/// `#rrggbb` as an opaque colour.
+pub fn parse_color(hex: &str) -> Option<Rgba> {
+    let hex = hex.strip_prefix('#')?;
+    if hex.len() != 6 {
+        return None;
+    }
+    let v = u32::from_str_radix(hex, 16).ok()?;
+    Some(Rgba::new((v >> 16) as u8, (v >> 8) as u8, v as u8, 0xFF))
+}
@@ -0,0 +1,27 @@
+pub mod assets;
+pub mod bot;
+pub mod breakout;
+pub mod canvas;
+pub mod cavity;
+pub mod clipboard;
+pub mod code;
+pub mod common;
+pub mod difficulty;
+pub mod estimate;
+pub mod generator;
+pub mod heading;
+pub mod hot_seat;
+pub mod import;
+pub mod input;
+pub mod level;
+pub mod lobby;
+pub mod mode;
+pub mod net;
+pub mod pack;
+pub mod replay;
+pub mod rules;
+pub mod scores;
+pub mod screens;
+pub mod sprites;
+pub mod validate;
+pub mod variant;
@@ -0,0 +1,205 @@
+use std::{
+    env, io,
+    net::{Ipv4Addr, SocketAddr, ToSocketAddrs, UdpSocket},
+    time::{Duration, Instant},
+};
+
+use socket2::{Domain, Protocol, Socket, Type};
+
+use crate::net::{NetError, PROTOCOL_VERSION};
+
+/// Port lobbies are announced on.
+pub const DISCOVERY_PORT: u16 = 7812;
+/// Frames between two announcements of a hosted game.
+pub const ANNOUNCE_EVERY: usize = 60;
+/// A lobby not heard of for this long is taken off the list.
+pub const LOBBY_TTL: Duration = Duration::from_secs(3);
+/// First line of every announcement, anything else on the port is ignored.
+const MAGIC: &str = "breakout lobby";
+
+/// Where hosts announce their lobbies, every game on the LAN.
+pub fn broadcast_addr() -> SocketAddr {
+    SocketAddr::from((Ipv4Addr::BROADCAST, DISCOVERY_PORT))
+}
+
+/// Name of the lobbies hosted here, after the user.
+pub fn lobby_name() -> String {
+    env::var("USER")
+        .or_else(|_| env::var("USERNAME"))
+        .map(|user| format!("{user}'s Game"))
+        .unwrap_or_else(|_| "Breakout Game".to_string())
+}
+
+/// A hosted game waiting for a player, as announced on the network. Sent as
+/// `MAGIC` and one `<key> = <value>` line per field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lobby {
+    pub name: String,
+    /// protocol version of the hosting game, see `PROTOCOL_VERSION`
+    pub version: u16,
+    /// the game's TCP port
+    pub port: u16,
+}
+
+impl Lobby {
+    /// The lobby of a game this version hosts on `port`.
+    pub fn new(name: &str, port: u16) -> Self {
+        Self {
+            name: name.to_string(),
+            version: PROTOCOL_VERSION,
+            port,
+        }
+    }
+
+    pub fn encode(&self) -> String {
+        format!(
+            "{MAGIC}\nname = {}\nversion = {}\nport = {}\n",
+            self.name, self.version, self.port
+        )
+    }
+
+    /// Reads an announcement, `None` for anything that is not one.
+    pub fn parse(text: &str) -> Option<Lobby> {
+        let mut lines = text.lines();
+        if lines.next()? != MAGIC {
+            return None;
+        }
+        let fields: Vec<(&str, &str)> = lines
+            .filter_map(|line| line.split_once('='))
+            .map(|(key, value)| (key.trim(), value.trim()))
+            .collect();
+        let field = |key: &str| fields.iter().find(|(k, _)| *k == key).map(|(_, v)| *v);
+        Some(Lobby {
+            name: field("name")?.to_string(),
+            version: field("version")?.parse().ok()?,
+            port: field("port")?.parse().ok()?,
+        })
+    }
+
+    /// Games of another version refuse to play this one.
+    pub fn is_compatible(&self) -> bool {
+        self.version == PROTOCOL_VERSION
+    }
+}
+
+/// Tells the network about a hosted game every `ANNOUNCE_EVERY` frames.
+pub struct Announcer {
+    socket: UdpSocket,
+    target: SocketAddr,
+    text: String,
+    ticks: usize,
+}
+
+impl Announcer {
+    /// Announces `lobby` to `target`, `broadcast_addr` on a LAN.
+    pub fn new(lobby: &Lobby, target: SocketAddr) -> Result<Self, NetError> {
+        let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))?;
+        socket.set_broadcast(true)?;
+        socket.set_nonblocking(true)?;
+        Ok(Self {
+            socket,
+            target,
+            text: lobby.encode(),
+            ticks: 0,
+        })
+    }
+
+    /// Counts a frame, announces on the first one and every `ANNOUNCE_EVERY`
+    /// after it.
+    pub fn tick(&mut self) {
+        if self.ticks.is_multiple_of(ANNOUNCE_EVERY)
+            && let Err(err) = self.socket.send_to(self.text.as_bytes(), self.target)
+        {
+            println!("[ERROR] could not announce the lobby: {err}");
+        }
+        self.ticks += 1;
+    }
+}
+
+/// A lobby heard of and where to join it.
+#[derive(Debug, Clone)]
+pub struct Found {
+    pub lobby: Lobby,
+    /// the announcing computer with the game's port
+    pub addr: SocketAddr,
+    seen: Instant,
+}
+
+/// Listens for the lobbies hosts announce.
+pub struct Finder {
+    socket: UdpSocket,
+    addr: SocketAddr,
+    found: Vec<Found>,
+}
+
+impl Finder {
+    /// Listens on `addr`, port 0 picks a free one. The port is shared, so
+    /// every game on a computer can look for lobbies at once.
+    pub fn bind(addr: &str) -> Result<Self, NetError> {
+        let resolved = addr
+            .to_socket_addrs()
+            .ok()
+            .and_then(|mut addrs| addrs.next())
+            .ok_or_else(|| NetError::Address(addr.to_string()))?;
+        let socket = Socket::new(
+            Domain::for_address(resolved),
+            Type::DGRAM,
+            Some(Protocol::UDP),
+        )?;
+        socket.set_reuse_address(true)?;
+        #[cfg(unix)]
+        socket.set_reuse_port(true)?;
+        socket.bind(&resolved.into())?;
+        let socket = UdpSocket::from(socket);
+        socket.set_nonblocking(true)?;
+        let addr = socket.local_addr()?;
+        Ok(Self {
+            socket,
+            addr,
+            found: Vec::new(),
+        })
+    }
+
+    /// Where the finder listens.
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Takes in the announcements that came and forgets lobbies gone quiet.
+    pub fn poll(&mut self) {
+        let mut buf = [0; 512];
+        loop {
+            let (n, from) = match self.socket.recv_from(&mut buf) {
+                Ok(received) => received,
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                Err(err) => {
+                    println!("[ERROR] could not look for lobbies: {err}");
+                    break;
+                }
+            };
+            let Some(lobby) = std::str::from_utf8(&buf[..n]).ok().and_then(Lobby::parse) else {
+                continue;
+            };
+            let addr = SocketAddr::new(from.ip(), lobby.port);
+            match self.found.iter_mut().find(|f| f.addr == addr) {
+                Some(found) => {
+                    found.lobby = lobby;
+                    found.seen = Instant::now();
+                }
+                None => {
+                    println!("[INFO] found {} at {addr}", lobby.name);
+                    self.found.push(Found {
+                        lobby,
+                        addr,
+                        seen: Instant::now(),
+                    });
+                }
+            }
+        }
+        self.found.retain(|f| f.seen.elapsed() < LOBBY_TTL);
+    }
+
+    pub fn lobbies(&self) -> &[Found] {
+        &self.found
+    }
+}
@@ -1,20 +1,102 @@
-mod breakout;
-mod common;
-mod screens;
-mod sprites;
-mod heading;
+use std::{
+    cell::RefCell,
+    env,
+    path::{Path, PathBuf},
+    process,
+    rc::Rc,
+};
 
-use std::{cell::RefCell, process, rc::Rc};
-
-use breakout::Breakout;
-use common::{HEIGHT, WIDTH};
-use grapes::
-    engine::{camera_2d::Camera2d, two_d::Engine}
-;
+use breakout::{
+    assets::flag_value,
+    breakout::{Windowed, capture, screenshot},
+    common::{HEIGHT, WIDTH},
+    estimate::estimate,
+    import::{ImportError, KindMap, default_output, import_png},
+    level::{Level, LevelError},
+    replay::Replay,
+    validate::validate,
+};
+use grapes::engine::{camera_2d::Camera2d, two_d::Engine};
 
 fn main() {
+    let args: Vec<String> = env::args().collect();
+    if let Some(path) = flag_value(&args, "--screenshot") {
+        match screenshot(Path::new(path)) {
+            Ok(_) => println!("[INFO] wrote {path}"),
+            Err(err) => {
+                println!("[ERROR] {err}");
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    if let Some(dir) = flag_value(&args, "--capture") {
+        let Some(path) = flag_value(&args, "--replay") else {
+            println!("[ERROR] --capture needs a --replay <file> to play");
+            process::exit(1);
+        };
+        let replay = match Replay::load(Path::new(path)) {
+            Ok(replay) => replay,
+            Err(err) => {
+                println!("[ERROR] {path}: {err}");
+                process::exit(1);
+            }
+        };
+        match capture(&replay, Path::new(dir)) {
+            Ok(frames) => println!("[INFO] wrote {frames} frames to {dir}"),
+            Err(err) => {
+                println!("[ERROR] {err}");
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    if let Some(image) = flag_value(&args, "--import") {
+        match import(&args, Path::new(image)) {
+            Ok(out) => println!("[INFO] wrote {}", out.display()),
+            Err(err) => {
+                println!("[ERROR] {image}: {err}");
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    if let Some(path) = flag_value(&args, "--validate") {
+        let problems = match Level::load(Path::new(path)) {
+            Ok(level) => validate(&level),
+            Err(err) => {
+                println!("[ERROR] {path}: {err}");
+                process::exit(1);
+            }
+        };
+        for problem in &problems {
+            println!("[ERROR] {path}: {problem}");
+        }
+        if !problems.is_empty() {
+            process::exit(1);
+        }
+        println!("[INFO] {path} is fine");
+        return;
+    }
+
+    if let Some(path) = flag_value(&args, "--estimate") {
+        let runs = flag_value(&args, "--runs").map_or(Some(10), |r| r.parse().ok());
+        let Some(runs) = runs.filter(|&r| r > 0) else {
+            println!("[ERROR] --runs takes a number of games");
+            process::exit(1);
+        };
+        if let Err(err) = rate(Path::new(path), runs) {
+            println!("[ERROR] {path}: {err}");
+            process::exit(1);
+        }
+        return;
+    }
+
     let camera = Rc::new(RefCell::new(Camera2d::default()));
-    let mut engine = match Engine::<Breakout>::create_window(
+    let mut engine = match Engine::<Windowed>::create_window(
         "Breakout",
         WIDTH as usize,
         HEIGHT as usize,
@@ -34,3 +116,37 @@ fn main() {
         }
     };
 }
+
+/// `--estimate <level> [--runs <n>]`, prints how bots did and saves the rating.
+fn rate(path: &Path, runs: usize) -> Result<(), LevelError> {
+    let mut level = Level::load(path)?;
+    let estimate = estimate(&level, runs, 0);
+    println!("skill  cleared  median time  balls lost  scores (min 25% 50% 75% max)");
+    for report in &estimate.skills {
+        let time = report
+            .median_time()
+            .map_or("-".to_string(), |t| format!("{t:.1}s"));
+        let [min, q1, median, q3, max] = report.scores();
+        println!(
+            "{:5.1}  {:6.0}%  {time:>11}  {:10.1}  {min} {q1} {median} {q3} {max}",
+            report.skill,
+            report.clear_rate() * 100.0,
+            report.mean_balls_lost(),
+        );
+    }
+    println!("[INFO] rating {:.2}", estimate.rating);
+    level.rating = Some(estimate.rating);
+    level.save(path)
+}
+
+/// `--import <png> [--kinds <file>] [--out <level>]`
+fn import(args: &[String], image: &Path) -> Result<PathBuf, ImportError> {
+    let kinds = match flag_value(args, "--kinds") {
+        Some(path) => KindMap::load(Path::new(path))?,
+        None => KindMap::default(),
+    };
+    let level = import_png(image, &kinds)?;
+    let out = flag_value(args, "--out").map_or_else(|| default_output(image), PathBuf::from);
+    level.save(&out).map_err(ImportError::Save)?;
+    Ok(out)
+}
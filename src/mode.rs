@@ -0,0 +1,228 @@
+use crate::{
+    canvas::Rgba,
+    common::{BRICK_COLS, BRICK_ROWS, PADDLE_WIDTH},
+    generator::generate,
+    level::{BrickKind, BrickSpec},
+};
+
+/// Frames of play between two steps of the wall in Progressive.
+pub const SHIFT_FRAMES: usize = 8 * 60;
+/// Seed of the wall played in Time Attack, the same one every game so times
+/// can be compared.
+pub const TIME_ATTACK_SEED: u64 = 0x7157_A77A;
+/// Difficulty of the Time Attack wall, see `generate`.
+pub const TIME_ATTACK_DIFFICULTY: f32 = 0.3;
+/// Par time for the Time Attack wall, one and a half minutes.
+pub const PAR_FRAMES: usize = 90 * 60;
+/// Length of a Score Attack game in frames, three minutes.
+pub const SCORE_ATTACK_FRAMES: usize = 3 * 60 * 60;
+/// Stages cleared in Endless between two extra balls.
+pub const BALL_EVERY: usize = 5;
+/// Most balls an Endless game has in hand, extra ones beyond are not given.
+pub const MAX_BALLS: usize = 6;
+/// Color of the rows sent over in Versus.
+pub const GARBAGE_COLOR: Rgba = Rgba::new(0x60, 0x60, 0x60, 0xFF);
+
+/// What a game is played for, picked on the menu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Mode {
+    /// clear walls until the balls run out
+    #[default]
+    Standard,
+    /// the wall keeps moving down, see `Progressive`
+    Progressive,
+    /// extra balls wait in pockets of the wall, see `cavity::CavityBall`
+    Cavity,
+    /// one wall, cleared as fast as possible
+    TimeAttack,
+    /// as many points as possible in `SCORE_ATTACK_FRAMES`, balls are free
+    ScoreAttack,
+    /// generated walls that get harder until the balls run out, see
+    /// `EndlessStage`
+    Endless,
+    /// two players take turns, see `hot_seat::HotSeat`
+    HotSeat,
+    /// two players with a paddle each share the balls and the score
+    Coop,
+    /// two players side by side, rows one clears go to the other's wall, see
+    /// `screens::versus::Versus`
+    Versus,
+    /// versus against a game on the network, see `net::Session`
+    Online,
+}
+
+impl Mode {
+    pub const ALL: [Mode; 10] = [
+        Mode::Standard,
+        Mode::Progressive,
+        Mode::Cavity,
+        Mode::TimeAttack,
+        Mode::ScoreAttack,
+        Mode::Endless,
+        Mode::HotSeat,
+        Mode::Coop,
+        Mode::Versus,
+        Mode::Online,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Mode::Standard => "Standard",
+            Mode::Progressive => "Progressive",
+            Mode::Cavity => "Cavity",
+            Mode::TimeAttack => "Time Attack",
+            Mode::ScoreAttack => "Score Attack",
+            Mode::Endless => "Endless",
+            Mode::HotSeat => "Two Players",
+            Mode::Coop => "Co-op",
+            Mode::Versus => "Versus",
+            Mode::Online => "Online Versus",
+        }
+    }
+
+    pub fn next(self) -> Mode {
+        let i = Mode::ALL.iter().position(|m| *m == self).unwrap_or(0);
+        Mode::ALL[(i + 1) % Mode::ALL.len()]
+    }
+
+    /// Modes whose results go to the high scores. Time Attack keeps the
+    /// fastest time in frames, Score Attack the highest score.
+    pub fn is_ranked(self) -> bool {
+        matches!(self, Mode::TimeAttack | Mode::ScoreAttack)
+    }
+
+    /// Whether the difficulty, rules and variant picked on the menu apply.
+    /// The versus modes play both sides with the defaults.
+    pub fn has_settings(self) -> bool {
+        !matches!(self, Mode::Versus | Mode::Online)
+    }
+
+    /// Whether `a` is a better result than `b` in this mode.
+    pub fn beats(self, a: usize, b: usize) -> bool {
+        match self {
+            Mode::TimeAttack => a < b,
+            _ => a > b,
+        }
+    }
+}
+
+/// How a stage of an Endless game is played, stage 0 is the first wall.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EndlessStage {
+    /// of the generated wall, more and tougher bricks as it goes up
+    pub difficulty: f32,
+    /// ball speed as a multiple of the first stage's
+    pub speed: f32,
+    pub paddle_width: f32,
+}
+
+impl EndlessStage {
+    pub fn new(stage: usize) -> Self {
+        let n = stage as f32;
+        Self {
+            difficulty: (0.2 + 0.08 * n).min(1.0),
+            speed: (1.0 + 0.1 * n).min(2.5),
+            paddle_width: PADDLE_WIDTH * (1.0 - 0.04 * n).max(0.5),
+        }
+    }
+
+    /// Whether reaching `stage` earns an extra ball.
+    pub fn grants_ball(stage: usize) -> bool {
+        stage > 0 && stage.is_multiple_of(BALL_EVERY)
+    }
+}
+
+/// What a Time Attack time earns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Medal {
+    Gold,
+    Silver,
+    Bronze,
+}
+
+impl Medal {
+    /// Gold within par, silver within one and a half times par, bronze within
+    /// twice par.
+    pub fn for_time(frames: usize) -> Option<Medal> {
+        if frames <= PAR_FRAMES {
+            Some(Medal::Gold)
+        } else if frames <= PAR_FRAMES * 3 / 2 {
+            Some(Medal::Silver)
+        } else if frames <= PAR_FRAMES * 2 {
+            Some(Medal::Bronze)
+        } else {
+            None
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Medal::Gold => "Gold",
+            Medal::Silver => "Silver",
+            Medal::Bronze => "Bronze",
+        }
+    }
+}
+
+/// Moves the wall down a row every `SHIFT_FRAMES` and makes up the rows that
+/// come in on top. The rows get harder as the game goes on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Progressive {
+    seed: u64,
+    /// rows added so far
+    added: usize,
+    /// frames until the next step
+    frames: usize,
+}
+
+impl Progressive {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            added: 0,
+            frames: SHIFT_FRAMES,
+        }
+    }
+
+    pub fn rows_added(&self) -> usize {
+        self.added
+    }
+
+    /// Counts a frame of play, true when the wall has to move down.
+    pub fn tick(&mut self) -> bool {
+        self.frames -= 1;
+        if self.frames == 0 {
+            self.frames = SHIFT_FRAMES;
+        }
+        self.frames == SHIFT_FRAMES
+    }
+
+    /// The row to put on top of the wall next, the top row of a generated
+    /// wall. Solid bricks would pile up until they reach the paddle, they come
+    /// in as tough ones.
+    pub fn next_row(&mut self) -> [Option<BrickSpec>; BRICK_COLS] {
+        let n = self.added;
+        self.added += 1;
+        self.frames = SHIFT_FRAMES;
+        let difficulty = (0.2 + 0.02 * n as f32).min(1.0);
+        let level = generate(self.seed.wrapping_add(n as u64), difficulty);
+        std::array::from_fn(|col| {
+            level.get(0, col).map(|spec| {
+                let kind = match spec.kind {
+                    BrickKind::Solid => BrickKind::Tough,
+                    kind => kind,
+                };
+                BrickSpec::row_points(kind, spec.color, n % BRICK_ROWS)
+            })
+        })
+    }
+}
+
+/// The row a Versus player gets on top of their wall for every row the other
+/// one clears, full but for the column `gap` and worth the least.
+pub fn garbage_row(gap: usize) -> [Option<BrickSpec>; BRICK_COLS] {
+    std::array::from_fn(|col| {
+        (col != gap)
+            .then(|| BrickSpec::row_points(BrickKind::Normal, GARBAGE_COLOR, BRICK_ROWS - 1))
+    })
+}
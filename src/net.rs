@@ -0,0 +1,507 @@
+use std::{
+    collections::{BTreeMap, btree_map::Entry},
+    fmt,
+    io::{self, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    common::ScreenMsg,
+    input::Pad,
+    screens::versus::{SIDES, Versus},
+};
+
+/// Bumped whenever the messages change, games only play others on the same
+/// version.
+pub const PROTOCOL_VERSION: u16 = 1;
+/// Port a game hosts on from the menu.
+pub const DEFAULT_PORT: u16 = 7811;
+/// Frames between reading a pad and playing it, the time it has to reach the
+/// other game.
+pub const INPUT_DELAY: usize = 3;
+/// Frames between two pings.
+pub const PING_EVERY: usize = 30;
+/// Silence after which the other game counts as gone.
+pub const TIMEOUT: Duration = Duration::from_secs(5);
+/// How long joining waits for the host.
+pub const CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+
+const HELLO: u8 = 1;
+const PAD: u8 = 2;
+const PING: u8 = 3;
+const PONG: u8 = 4;
+const BYE: u8 = 5;
+
+#[derive(Debug)]
+pub enum NetError {
+    Io(io::Error),
+    /// the address does not resolve
+    Address(String),
+    /// a message this version does not know
+    BadMsg(u8),
+    /// the other game speaks another protocol version
+    Version {
+        ours: u16,
+        theirs: u16,
+    },
+    /// the other game hung up
+    Closed,
+    /// nothing came from the other game for `TIMEOUT`
+    Timeout,
+}
+
+impl fmt::Display for NetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NetError::Io(err) => write!(f, "connection failed: {err}"),
+            NetError::Address(addr) => write!(f, "no such address: {addr}"),
+            NetError::BadMsg(tag) => write!(f, "unknown message {tag}"),
+            NetError::Version { ours, theirs } => {
+                write!(f, "the other game is version {theirs}, this one {ours}")
+            }
+            NetError::Closed => write!(f, "the other player left"),
+            NetError::Timeout => write!(f, "the other player stopped answering"),
+        }
+    }
+}
+
+impl std::error::Error for NetError {}
+
+impl From<io::Error> for NetError {
+    fn from(err: io::Error) -> Self {
+        NetError::Io(err)
+    }
+}
+
+/// What two games say to each other. A tag byte, then the fields in big
+/// endian.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Msg {
+    /// the first message both ways, the host's carries the seed of the game
+    Hello {
+        version: u16,
+        seed: u64,
+    },
+    /// the sender's pad for frame `frame`
+    Pad {
+        frame: u32,
+        pad: Pad,
+    },
+    Ping(u32),
+    /// answers the ping with the same id
+    Pong(u32),
+    /// the sender leaves
+    Bye,
+}
+
+impl Msg {
+    pub fn encode(self, out: &mut Vec<u8>) {
+        match self {
+            Msg::Hello { version, seed } => {
+                out.push(HELLO);
+                out.extend(version.to_be_bytes());
+                out.extend(seed.to_be_bytes());
+            }
+            Msg::Pad { frame, pad } => {
+                out.push(PAD);
+                out.extend(frame.to_be_bytes());
+                out.push(pad.bits());
+            }
+            Msg::Ping(id) => {
+                out.push(PING);
+                out.extend(id.to_be_bytes());
+            }
+            Msg::Pong(id) => {
+                out.push(PONG);
+                out.extend(id.to_be_bytes());
+            }
+            Msg::Bye => out.push(BYE),
+        }
+    }
+
+    /// The message at the start of `buf` and how many bytes it takes, `None`
+    /// while it is not all there.
+    pub fn decode(buf: &[u8]) -> Result<Option<(Msg, usize)>, NetError> {
+        let Some(&tag) = buf.first() else {
+            return Ok(None);
+        };
+        let len = match tag {
+            HELLO => 11,
+            PAD => 6,
+            PING | PONG => 5,
+            BYE => 1,
+            tag => return Err(NetError::BadMsg(tag)),
+        };
+        if buf.len() < len {
+            return Ok(None);
+        }
+        let body = &buf[1..len];
+        let u32_at =
+            |i: usize| u32::from_be_bytes([body[i], body[i + 1], body[i + 2], body[i + 3]]);
+        let msg = match tag {
+            HELLO => Msg::Hello {
+                version: u16::from_be_bytes([body[0], body[1]]),
+                seed: u64::from_be_bytes(body[2..10].try_into().unwrap()),
+            },
+            PAD => Msg::Pad {
+                frame: u32_at(0),
+                pad: Pad::from_bits(body[4]),
+            },
+            PING => Msg::Ping(u32_at(0)),
+            PONG => Msg::Pong(u32_at(0)),
+            _ => Msg::Bye,
+        };
+        Ok(Some((msg, len)))
+    }
+}
+
+/// A connection to the other game. Sending and receiving never wait, what
+/// the socket does not take right away goes out with the next poll.
+pub struct Link {
+    stream: TcpStream,
+    /// bytes received but not decoded yet
+    inbox: Vec<u8>,
+    /// bytes to send the socket did not take yet
+    outbox: Vec<u8>,
+    last_heard: Instant,
+    /// the other game closed the connection
+    closed: bool,
+}
+
+impl Link {
+    pub fn new(stream: TcpStream) -> Result<Self, NetError> {
+        stream.set_nonblocking(true)?;
+        stream.set_nodelay(true)?;
+        Ok(Self {
+            stream,
+            inbox: Vec::new(),
+            outbox: Vec::new(),
+            last_heard: Instant::now(),
+            closed: false,
+        })
+    }
+
+    /// Connects to the game hosting at `addr`, waits at most
+    /// `CONNECT_TIMEOUT`.
+    pub fn connect(addr: &str) -> Result<Self, NetError> {
+        let resolved = addr
+            .to_socket_addrs()
+            .ok()
+            .and_then(|mut addrs| addrs.next())
+            .ok_or_else(|| NetError::Address(addr.to_string()))?;
+        Self::new(TcpStream::connect_timeout(&resolved, CONNECT_TIMEOUT)?)
+    }
+
+    pub fn send(&mut self, msg: Msg) -> Result<(), NetError> {
+        msg.encode(&mut self.outbox);
+        self.flush()
+    }
+
+    fn flush(&mut self) -> Result<(), NetError> {
+        while !self.outbox.is_empty() {
+            match self.stream.write(&self.outbox) {
+                Ok(0) => break,
+                Ok(n) => {
+                    self.outbox.drain(..n);
+                }
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => (),
+                Err(err) if hung_up(&err) => {
+                    self.closed = true;
+                    self.outbox.clear();
+                }
+                Err(err) => return Err(err.into()),
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the messages that came in and sends what is left to send.
+    /// Messages sent before the other game hung up still come in, see
+    /// `is_closed`.
+    pub fn poll(&mut self) -> Result<Vec<Msg>, NetError> {
+        let mut buf = [0; 1024];
+        while !self.closed {
+            match self.stream.read(&mut buf) {
+                Ok(0) => self.closed = true,
+                Ok(n) => {
+                    self.inbox.extend_from_slice(&buf[..n]);
+                    self.last_heard = Instant::now();
+                }
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => (),
+                Err(err) if hung_up(&err) => self.closed = true,
+                Err(err) => return Err(err.into()),
+            }
+        }
+        self.flush()?;
+        let mut msgs = Vec::new();
+        let mut at = 0;
+        while let Some((msg, len)) = Msg::decode(&self.inbox[at..])? {
+            msgs.push(msg);
+            at += len;
+        }
+        self.inbox.drain(..at);
+        Ok(msgs)
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    /// Time since anything came in.
+    pub fn silence(&self) -> Duration {
+        self.last_heard.elapsed()
+    }
+}
+
+impl Drop for Link {
+    /// Says goodbye, so the other game does not wait for `TIMEOUT`.
+    fn drop(&mut self) {
+        if !self.closed {
+            let _ = self.send(Msg::Bye);
+        }
+    }
+}
+
+/// The other end of the connection is gone.
+fn hung_up(err: &io::Error) -> bool {
+    matches!(
+        err.kind(),
+        io::ErrorKind::BrokenPipe
+            | io::ErrorKind::ConnectionReset
+            | io::ErrorKind::ConnectionAborted
+    )
+}
+
+/// Waits for a game to join without blocking.
+pub struct Host {
+    listener: TcpListener,
+    addr: SocketAddr,
+}
+
+impl Host {
+    /// Listens on `addr`, port 0 picks a free one.
+    pub fn bind(addr: &str) -> Result<Self, NetError> {
+        let listener = TcpListener::bind(addr)?;
+        listener.set_nonblocking(true)?;
+        let addr = listener.local_addr()?;
+        Ok(Self { listener, addr })
+    }
+
+    /// Where the host listens.
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// The session with a game that joined, `None` while none has. The
+    /// game is played from `seed`.
+    pub fn accept(&mut self, seed: u64) -> Result<Option<Session>, NetError> {
+        match self.listener.accept() {
+            Ok((stream, peer)) => {
+                println!("[INFO] {peer} joined");
+                Session::host(Link::new(stream)?, seed).map(Some)
+            }
+            Err(err) if err.kind() == io::ErrorKind::WouldBlock => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+/// A versus game played together with another game, in lockstep. Only the
+/// pads go over the wire: both games run the same seeded `Versus` and a frame
+/// is played once both pads for it are in. A pad is read `INPUT_DELAY`
+/// frames before it is played, to give it time to arrive.
+pub struct Session {
+    link: Link,
+    /// the side this game plays, the host's is 0
+    side: usize,
+    /// the other game said hello with the same version
+    ready: bool,
+    versus: Versus,
+    /// next frame to play
+    frame: usize,
+    /// pads of both sides for the frames not played yet
+    pads: [BTreeMap<usize, Pad>; SIDES],
+    /// this side's pad waiting for a free frame: the latest moves and any
+    /// serve since the last one went out
+    pending: Pad,
+    /// updates since the session started, for the pings
+    ticks: usize,
+    /// the ping waiting for its pong and when it went out
+    ping: Option<(u32, Instant)>,
+    /// round trip time of the last ping
+    latency: Option<Duration>,
+    /// updates in a row without the other pad
+    stalled: usize,
+    /// the other game said goodbye
+    left: bool,
+}
+
+impl Session {
+    fn new(link: Link, side: usize) -> Self {
+        // nobody moves in the frames before the first pads arrive
+        let pads = std::array::from_fn(|_| (0..INPUT_DELAY).map(|f| (f, Pad::default())).collect());
+        Self {
+            link,
+            side,
+            ready: false,
+            versus: Versus::init(),
+            frame: 0,
+            pads,
+            pending: Pad::default(),
+            ticks: 0,
+            ping: None,
+            latency: None,
+            stalled: 0,
+            left: false,
+        }
+    }
+
+    /// The host plays the left side and picks the seed.
+    pub fn host(link: Link, seed: u64) -> Result<Self, NetError> {
+        let mut session = Self::new(link, 0);
+        session.start(seed);
+        session.link.send(Msg::Hello {
+            version: PROTOCOL_VERSION,
+            seed,
+        })?;
+        Ok(session)
+    }
+
+    /// Joins the game hosted at `addr` and plays the right side.
+    pub fn join(addr: &str) -> Result<Self, NetError> {
+        let mut session = Self::new(Link::connect(addr)?, 1);
+        session.link.send(Msg::Hello {
+            version: PROTOCOL_VERSION,
+            seed: 0,
+        })?;
+        Ok(session)
+    }
+
+    fn start(&mut self, seed: u64) {
+        self.versus.seed(seed);
+        self.versus.reset();
+    }
+
+    pub fn side(&self) -> usize {
+        self.side
+    }
+
+    /// Both games said hello, the game is on.
+    pub fn is_ready(&self) -> bool {
+        self.ready
+    }
+
+    pub fn versus(&self) -> &Versus {
+        &self.versus
+    }
+
+    /// Frames played so far.
+    pub fn frame(&self) -> usize {
+        self.frame
+    }
+
+    /// Round trip time to the other game, once a ping came back.
+    pub fn latency(&self) -> Option<Duration> {
+        self.latency
+    }
+
+    /// Updates in a row spent waiting for the other game's pad.
+    pub fn stalled(&self) -> usize {
+        self.stalled
+    }
+
+    fn receive(&mut self, msg: Msg) -> Result<(), NetError> {
+        match msg {
+            Msg::Hello { version, seed } => {
+                if version != PROTOCOL_VERSION {
+                    return Err(NetError::Version {
+                        ours: PROTOCOL_VERSION,
+                        theirs: version,
+                    });
+                }
+                if self.side == 1 {
+                    self.start(seed);
+                }
+                self.ready = true;
+                println!("[INFO] playing versus as player {}", self.side + 1);
+            }
+            Msg::Pad { frame, pad } => {
+                let other = (self.side + 1) % SIDES;
+                self.pads[other].insert(frame as usize, pad);
+            }
+            Msg::Ping(id) => self.link.send(Msg::Pong(id))?,
+            Msg::Pong(id) => {
+                if let Some((sent, at)) = self.ping
+                    && sent == id
+                {
+                    self.latency = Some(at.elapsed());
+                    self.ping = None;
+                }
+            }
+            Msg::Bye => self.left = true,
+        }
+        Ok(())
+    }
+
+    /// Sends `pad` off for its frame and plays the next frame if the other
+    /// game's pad for it is in. While waiting for it, `pad` is kept for the
+    /// next free frame so a tap of serve is not lost. Errors once the other game is gone or
+    /// breaks the protocol.
+    pub fn update(&mut self, pad: Pad, msgs: &mut Vec<ScreenMsg>) -> Result<(), NetError> {
+        if self.versus.is_over() {
+            return Ok(());
+        }
+        for msg in self.link.poll()? {
+            self.receive(msg)?;
+        }
+        if !self.ready {
+            return self.check_gone();
+        }
+
+        self.ticks += 1;
+        if self.ping.is_none() && self.ticks.is_multiple_of(PING_EVERY) {
+            let id = self.ticks as u32;
+            self.link.send(Msg::Ping(id))?;
+            self.ping = Some((id, Instant::now()));
+        }
+        self.pending = Pad {
+            serve: self.pending.serve || pad.serve,
+            ..pad
+        };
+        let ahead = self.frame + INPUT_DELAY;
+        if let Entry::Vacant(entry) = self.pads[self.side].entry(ahead) {
+            let pad = std::mem::take(&mut self.pending);
+            entry.insert(pad);
+            self.link.send(Msg::Pad {
+                frame: ahead as u32,
+                pad,
+            })?;
+        }
+
+        let other = (self.side + 1) % SIDES;
+        if !self.pads[other].contains_key(&self.frame) {
+            self.stalled += 1;
+            return self.check_gone();
+        }
+        let frame = self.frame;
+        let pads = std::array::from_fn(|side| self.pads[side].remove(&frame).unwrap_or_default());
+        self.stalled = 0;
+        self.frame += 1;
+        self.versus.step(pads, msgs);
+        Ok(())
+    }
+
+    /// Errors if the other game can not send anything more.
+    fn check_gone(&self) -> Result<(), NetError> {
+        if self.left || self.link.is_closed() {
+            Err(NetError::Closed)
+        } else if self.link.silence() > TIMEOUT {
+            Err(NetError::Timeout)
+        } else {
+            Ok(())
+        }
+    }
+}
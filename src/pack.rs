@@ -0,0 +1,318 @@
+use std::{
+    collections::BTreeMap,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    assets::data_home,
+    level::{Level, LevelError, checksum},
+};
+
+/// File name of the manifest inside a pack directory.
+pub const MANIFEST: &str = "pack.manifest";
+
+/// Where packs are installed, one directory per pack.
+pub fn packs_dir() -> PathBuf {
+    data_home().join("packs")
+}
+
+/// Where cleared stages and best scores are kept.
+pub fn progress_path() -> PathBuf {
+    data_home().join("progress")
+}
+
+#[derive(Debug)]
+pub enum PackError {
+    Io(PathBuf, io::Error),
+    Parse { line: usize, msg: String },
+}
+
+impl fmt::Display for PackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PackError::Io(path, err) => write!(f, "could not read {}: {err}", path.display()),
+            PackError::Parse { line, msg } => write!(f, "manifest line {line}: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for PackError {}
+
+/// Why a single stage of an otherwise fine pack can not be played.
+#[derive(Debug)]
+pub enum StageError {
+    Level(LevelError),
+    Checksum { expected: u32, found: u32 },
+    Empty,
+}
+
+impl fmt::Display for StageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StageError::Level(err) => err.fmt(f),
+            StageError::Checksum { expected, found } => {
+                write!(f, "checksum is {found:08x}, manifest says {expected:08x}")
+            }
+            StageError::Empty => write!(f, "no breakable bricks"),
+        }
+    }
+}
+
+impl std::error::Error for StageError {}
+
+#[derive(Debug)]
+pub struct Stage {
+    /// level file, relative to the pack directory
+    pub file: String,
+    pub title: String,
+    pub par: Option<usize>,
+    pub music: Option<String>,
+    pub theme: Option<String>,
+    pub checksum: Option<u32>,
+    pub level: Result<Level, StageError>,
+}
+
+impl Stage {
+    fn new(file: &str) -> Self {
+        Self {
+            file: file.to_string(),
+            title: file.to_string(),
+            par: None,
+            music: None,
+            theme: None,
+            checksum: None,
+            level: Err(StageError::Empty),
+        }
+    }
+
+    fn load(&mut self, dir: &Path) {
+        self.level = fs::read(dir.join(&self.file))
+            .map_err(|err| StageError::Level(LevelError::Io(err)))
+            .and_then(|bytes| {
+                let found = checksum(&bytes);
+                match self.checksum {
+                    Some(expected) if expected != found => {
+                        Err(StageError::Checksum { expected, found })
+                    }
+                    _ => Level::parse(&String::from_utf8_lossy(&bytes)).map_err(StageError::Level),
+                }
+            })
+            .and_then(|level| match level.breakable_count() {
+                0 => Err(StageError::Empty),
+                _ => Ok(level),
+            });
+    }
+}
+
+/// Stages played in order, described by a manifest of `key = value` lines.
+/// Keys before the first `stage` describe the pack, the ones after a `stage`
+/// line belong to that stage:
+///
+/// ```text
+/// name = Starter
+/// author = Someone
+/// description = Five walls to warm up
+/// stage = 01.level
+/// title = Opening
+/// par = 2000
+/// music = calm
+/// theme = teal
+/// checksum = 1a2b3c4d
+/// ```
+#[derive(Debug)]
+pub struct Pack {
+    /// directory name, progress is saved under it
+    pub id: String,
+    pub name: String,
+    pub author: String,
+    pub description: String,
+    pub stages: Vec<Stage>,
+}
+
+impl Pack {
+    /// Reads the manifest in `dir` and every stage it lists. Only a missing
+    /// or malformed manifest fails, stages keep their own errors.
+    pub fn load(dir: &Path) -> Result<Pack, PackError> {
+        let path = dir.join(MANIFEST);
+        let text = fs::read_to_string(&path).map_err(|err| PackError::Io(path, err))?;
+        let id = dir
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let mut pack = Pack::parse(&id, &text)?;
+        for stage in pack.stages.iter_mut() {
+            stage.load(dir);
+            if let Err(err) = &stage.level {
+                println!("[ERROR] pack {id}, stage {}: {err}", stage.file);
+            }
+        }
+        Ok(pack)
+    }
+
+    /// The manifest alone, stages are left unloaded.
+    pub fn parse(id: &str, text: &str) -> Result<Pack, PackError> {
+        let mut pack = Pack {
+            id: id.to_string(),
+            name: id.to_string(),
+            author: String::new(),
+            description: String::new(),
+            stages: Vec::new(),
+        };
+        for (i, line) in text.lines().enumerate() {
+            let err = |msg: String| PackError::Parse { line: i + 1, msg };
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                return Err(err(format!("expected `key = value`, got `{line}`")));
+            };
+            let (key, value) = (key.trim(), value.trim().to_string());
+            if key == "stage" {
+                pack.stages.push(Stage::new(&value));
+                continue;
+            }
+            match (pack.stages.last_mut(), key) {
+                (None, "name") => pack.name = value,
+                (None, "author") => pack.author = value,
+                (None, "description") => pack.description = value,
+                (Some(stage), "title") => stage.title = value,
+                (Some(stage), "par") => {
+                    let par = value
+                        .parse()
+                        .map_err(|_| err(format!("bad par `{value}`")))?;
+                    stage.par = Some(par);
+                }
+                (Some(stage), "music") => stage.music = Some(value),
+                (Some(stage), "theme") => stage.theme = Some(value),
+                (Some(stage), "checksum") => {
+                    let sum = u32::from_str_radix(&value, 16)
+                        .map_err(|_| err(format!("bad checksum `{value}`")))?;
+                    stage.checksum = Some(sum);
+                }
+                (_, key) => return Err(err(format!("unexpected key `{key}`"))),
+            }
+        }
+        if pack.stages.is_empty() {
+            return Err(PackError::Parse {
+                line: text.lines().count(),
+                msg: "no stages".to_string(),
+            });
+        }
+        Ok(pack)
+    }
+
+    pub fn broken_count(&self) -> usize {
+        self.stages.iter().filter(|s| s.level.is_err()).count()
+    }
+
+    /// The first stage from `from` on that can be played.
+    pub fn next_playable(&self, from: usize) -> Option<usize> {
+        (from..self.stages.len()).find(|&i| self.stages[i].level.is_ok())
+    }
+}
+
+/// Every pack in `dir`, sorted by directory name. Directories without a
+/// manifest are skipped, broken manifests are reported with their directory.
+pub fn scan(dir: &Path) -> Vec<Result<Pack, (String, PackError)>> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut dirs: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.join(MANIFEST).is_file())
+        .collect();
+    dirs.sort();
+    dirs.iter()
+        .map(|dir| {
+            Pack::load(dir).map_err(|err| {
+                let id = dir.file_name().unwrap_or_default().to_string_lossy();
+                println!("[ERROR] pack {id}: {err}");
+                (id.into_owned(), err)
+            })
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PackProgress {
+    /// one past the furthest stage cleared, the stages before it were
+    /// cleared or skipped as broken
+    pub furthest: usize,
+    pub best: usize,
+}
+
+/// Per pack progress, one `<pack id> = <furthest> <best score>` line each.
+#[derive(Debug)]
+pub struct Progress {
+    path: PathBuf,
+    packs: BTreeMap<String, PackProgress>,
+}
+
+impl Progress {
+    /// No progress, saved to `path`.
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            packs: BTreeMap::new(),
+        }
+    }
+
+    /// Reads `path`, a missing file is no progress yet.
+    pub fn load(path: PathBuf) -> Self {
+        let mut packs = BTreeMap::new();
+        let text = fs::read_to_string(&path).unwrap_or_default();
+        for line in text.lines() {
+            let entry = line.split_once('=').and_then(|(id, value)| {
+                let (furthest, best) = value.trim().split_once(' ')?;
+                let progress = PackProgress {
+                    furthest: furthest.parse().ok()?,
+                    best: best.trim().parse().ok()?,
+                };
+                Some((id.trim().to_string(), progress))
+            });
+            match entry {
+                Some((id, progress)) => {
+                    packs.insert(id, progress);
+                }
+                None if line.trim().is_empty() => (),
+                None => println!("[ERROR] {}: skipping `{line}`", path.display()),
+            }
+        }
+        Self { path, packs }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn get(&self, id: &str) -> PackProgress {
+        self.packs.get(id).copied().unwrap_or_default()
+    }
+
+    /// Stage `stage` of pack `id` was cleared, a stage before the furthest
+    /// one changes nothing.
+    pub fn record_clear(&mut self, id: &str, stage: usize) {
+        let entry = self.packs.entry(id.to_string()).or_default();
+        entry.furthest = entry.furthest.max(stage + 1);
+    }
+
+    pub fn record_score(&mut self, id: &str, score: usize) {
+        let entry = self.packs.entry(id.to_string()).or_default();
+        entry.best = entry.best.max(score);
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let text: String = self
+            .packs
+            .iter()
+            .map(|(id, p)| format!("{id} = {} {}\n", p.furthest, p.best))
+            .collect();
+        fs::write(&self.path, text)
+    }
+}
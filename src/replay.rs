@@ -0,0 +1,94 @@
+use std::{fmt, fs, io, iter, path::Path};
+
+use crate::input::Key;
+
+#[derive(Debug)]
+pub enum ReplayError {
+    Io(io::Error),
+    Parse { line: usize, msg: String },
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::Io(err) => write!(f, "could not read replay: {err}"),
+            ReplayError::Parse { line, msg } => write!(f, "line {line}: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+impl From<io::Error> for ReplayError {
+    fn from(err: io::Error) -> Self {
+        ReplayError::Io(err)
+    }
+}
+
+/// The keys held during a game, frame by frame, to play it again.
+///
+/// Files are plain text, one `key = value` per line, lines starting with `#`
+/// are comments. Each `hold` line holds its keys, or none, for a number of
+/// frames:
+///
+/// ```text
+/// seed = 7
+/// hold = <frames> [<key> ...]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Replay {
+    pub seed: u64,
+    /// frames and the keys held during them
+    pub holds: Vec<(usize, Vec<Key>)>,
+}
+
+impl Replay {
+    pub fn parse(text: &str) -> Result<Replay, ReplayError> {
+        let mut replay = Replay::default();
+        for (i, line) in text.lines().enumerate() {
+            let err = |msg: String| ReplayError::Parse { line: i + 1, msg };
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                return Err(err(format!("expected `key = value`, got `{line}`")));
+            };
+            match key.trim() {
+                "seed" => match value.trim().parse() {
+                    Ok(seed) => replay.seed = seed,
+                    Err(_) => return Err(err(format!("bad seed `{}`", value.trim()))),
+                },
+                "hold" => replay.holds.push(parse_hold(value).map_err(err)?),
+                key => return Err(err(format!("unknown key `{key}`"))),
+            }
+        }
+        Ok(replay)
+    }
+
+    pub fn load(path: &Path) -> Result<Replay, ReplayError> {
+        Replay::parse(&fs::read_to_string(path)?)
+    }
+
+    /// The keys held in each frame.
+    pub fn frames(&self) -> impl Iterator<Item = &[Key]> {
+        self.holds
+            .iter()
+            .flat_map(|(frames, keys)| iter::repeat_n(keys.as_slice(), *frames))
+    }
+}
+
+fn parse_hold(value: &str) -> Result<(usize, Vec<Key>), String> {
+    let mut parts = value.split_whitespace();
+    let frames = parts.next().unwrap_or_default();
+    let Ok(frames) = frames.parse() else {
+        return Err(format!(
+            "expected `<frames> [<key> ...]`, got `{}`",
+            value.trim()
+        ));
+    };
+    let keys = parts
+        .map(|name| Key::from_name(name).ok_or(format!("unknown key `{name}`")))
+        .collect::<Result<_, _>>()?;
+    Ok((frames, keys))
+}
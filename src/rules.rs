@@ -0,0 +1,128 @@
+use crate::common::BRICK_ROWS;
+
+/// Points per grid row under the classic rules, top row first. They go by
+/// where a brick sits, not by its own colour and points, so any wall scores
+/// like the classic one.
+pub const CLASSIC_POINTS: [usize; BRICK_ROWS] = [7, 7, 5, 5, 4, 4, 1, 1];
+/// Paddle hits after which the ball speeds up.
+pub const CLASSIC_HITS: [usize; 2] = [4, 12];
+/// Ball speed as a multiple of the base speed, one entry per speed up.
+pub const CLASSIC_SPEEDS: [f32; 5] = [1.0, 1.25, 1.5, 1.75, 2.0];
+/// Grid rows that speed the ball up the first time it touches them, where
+/// the classic wall is red and orange whatever colour a brick there has.
+const RED_ROWS: [usize; 2] = [0, 1];
+const ORANGE_ROWS: [usize; 2] = [2, 3];
+
+/// The rules `Play` runs by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Ruleset {
+    /// points from the wall, speed from the difficulty curve, endless walls
+    #[default]
+    Standard,
+    /// the arcade and Atari 2600 game, see `Classic`
+    Classic,
+}
+
+impl Ruleset {
+    pub const ALL: [Ruleset; 2] = [Ruleset::Standard, Ruleset::Classic];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Ruleset::Standard => "Standard",
+            Ruleset::Classic => "Classic",
+        }
+    }
+
+    pub fn next(self) -> Ruleset {
+        match self {
+            Ruleset::Standard => Ruleset::Classic,
+            Ruleset::Classic => Ruleset::Standard,
+        }
+    }
+
+    /// Balls per game.
+    pub fn balls(self) -> usize {
+        match self {
+            Ruleset::Standard => 3,
+            Ruleset::Classic => 5,
+        }
+    }
+
+    /// Walls per game, `None` for no end.
+    pub fn walls(self) -> Option<usize> {
+        match self {
+            Ruleset::Standard => None,
+            Ruleset::Classic => Some(2),
+        }
+    }
+}
+
+/// Speed ups and paddle size under the classic rules: the ball gets faster
+/// after 4 and 12 paddle hits and the first time it reaches the orange and
+/// the red rows, each ball starts slow again. Once the ball breaks through
+/// to the back wall the paddle is halved for the rest of the game.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Classic {
+    paddle_hits: usize,
+    orange: bool,
+    red: bool,
+    speedups: usize,
+    shrunk: bool,
+}
+
+impl Classic {
+    /// Index into `CLASSIC_SPEEDS`.
+    pub fn speedups(&self) -> usize {
+        self.speedups
+    }
+
+    pub fn is_shrunk(&self) -> bool {
+        self.shrunk
+    }
+
+    /// Ball speed as a multiple of the base speed.
+    pub fn speed(&self) -> f32 {
+        CLASSIC_SPEEDS[self.speedups]
+    }
+
+    /// Counts a paddle hit, true if the ball speeds up.
+    pub fn paddle_hit(&mut self) -> bool {
+        self.paddle_hits += 1;
+        CLASSIC_HITS.contains(&self.paddle_hits) && self.speed_up()
+    }
+
+    /// The ball touched a brick in grid row `row`, true if it speeds up.
+    pub fn brick_hit(&mut self, row: usize) -> bool {
+        if ORANGE_ROWS.contains(&row) && !self.orange {
+            self.orange = true;
+            self.speed_up()
+        } else if RED_ROWS.contains(&row) && !self.red {
+            self.red = true;
+            self.speed_up()
+        } else {
+            false
+        }
+    }
+
+    /// The ball hit the back wall, true if the paddle has to shrink now.
+    pub fn back_wall(&mut self) -> bool {
+        !std::mem::replace(&mut self.shrunk, true)
+    }
+
+    /// A new ball is served, the speed starts over.
+    pub fn new_ball(&mut self) {
+        *self = Classic {
+            shrunk: self.shrunk,
+            ..Classic::default()
+        };
+    }
+
+    fn speed_up(&mut self) -> bool {
+        if self.speedups + 1 < CLASSIC_SPEEDS.len() {
+            self.speedups += 1;
+            true
+        } else {
+            false
+        }
+    }
+}
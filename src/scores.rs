@@ -0,0 +1,117 @@
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    assets::data_home, difficulty::Difficulty, mode::Mode, rules::Ruleset, variant::VariantKind,
+};
+
+/// Where the best results of the ranked modes are kept.
+pub fn scores_path() -> PathBuf {
+    data_home().join("scores")
+}
+
+/// A ranked mode and the settings it was played with, results only count
+/// against others of the same board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Board {
+    pub mode: Mode,
+    pub difficulty: Difficulty,
+    pub ruleset: Ruleset,
+    pub variant: VariantKind,
+}
+
+impl Board {
+    /// The board of `mode` with the default settings.
+    pub fn new(mode: Mode) -> Self {
+        Self {
+            mode,
+            difficulty: Difficulty::default(),
+            ruleset: Ruleset::default(),
+            variant: VariantKind::default(),
+        }
+    }
+
+    /// How the board is written in the high score file.
+    pub fn key(&self) -> String {
+        format!(
+            "{}, {}, {}, {}",
+            self.mode.name(),
+            self.difficulty.name(),
+            self.ruleset.name(),
+            self.variant.name()
+        )
+    }
+}
+
+/// Best result per board, one `<mode>, <difficulty>, <rules>, <variant> =
+/// <result>` line each, see `Mode::is_ranked`.
+#[derive(Debug)]
+pub struct HighScores {
+    path: PathBuf,
+    best: BTreeMap<String, usize>,
+}
+
+impl HighScores {
+    /// No results, saved to `path`.
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            best: BTreeMap::new(),
+        }
+    }
+
+    /// Reads `path`, a missing file is no results yet.
+    pub fn load(path: PathBuf) -> Self {
+        let mut best = BTreeMap::new();
+        let text = fs::read_to_string(&path).unwrap_or_default();
+        for line in text.lines() {
+            let entry = line
+                .split_once('=')
+                .and_then(|(mode, value)| Some((mode.trim(), value.trim().parse().ok()?)));
+            match entry {
+                Some((mode, result)) => {
+                    best.insert(mode.to_string(), result);
+                }
+                None if line.trim().is_empty() => (),
+                None => println!("[ERROR] {}: skipping `{line}`", path.display()),
+            }
+        }
+        Self { path, best }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn best(&self, board: &Board) -> Option<usize> {
+        self.best.get(&board.key()).copied()
+    }
+
+    /// Keeps `result` if it beats the best one of `board`, returns true if
+    /// it did.
+    pub fn record(&mut self, board: &Board, result: usize) -> bool {
+        if self
+            .best(board)
+            .is_some_and(|best| !board.mode.beats(result, best))
+        {
+            return false;
+        }
+        self.best.insert(board.key(), result);
+        true
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let text: String = self
+            .best
+            .iter()
+            .map(|(mode, result)| format!("{mode} = {result}\n"))
+            .collect();
+        fs::write(&self.path, text)
+    }
+}
@@ -0,0 +1,117 @@
+use grapes::vx2;
+
+use crate::{
+    canvas::{Canvas, Rgba},
+    clipboard,
+    code::{ALPHABET, decode},
+    common::{Drawable, GameScreen, HEIGHT, ScreenMsg, WIDTH},
+    input::{Input, Key, spin_text},
+};
+
+use super::Screen;
+
+const TITLE: &str = "Level Code";
+const HELP: [&str; 2] = [
+    "Tab Paste  Up/Down Change Character  Right Next Character",
+    "Backspace Delete  Enter Play  Escape Menu",
+];
+const FS: f32 = 18.0;
+const SMALL_FS: f32 = 14.0;
+/// Characters per line when the code is shown.
+const WRAP: usize = 40;
+
+/// Takes a level code from the clipboard, or puts it together with the
+/// arrows, and plays it. The window types nothing, see `spin_text`.
+pub struct CodeEntry {
+    code: String,
+    error: String,
+}
+
+impl CodeEntry {
+    pub fn init() -> Self {
+        Self {
+            code: String::new(),
+            error: String::new(),
+        }
+    }
+
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    pub fn set_code(&mut self, code: &str) {
+        self.code = code.trim().to_string();
+        self.error.clear();
+    }
+
+    pub fn error(&self) -> &str {
+        &self.error
+    }
+
+    fn paste(&mut self) {
+        match clipboard::paste() {
+            Ok(text) => self.set_code(&text),
+            Err(err) => {
+                println!("[ERROR] could not paste: {err}");
+                self.error = format!("Paste failed: {err}");
+            }
+        }
+    }
+}
+
+impl GameScreen for CodeEntry {
+    fn update(&mut self, input: &dyn Input, msgs: &mut Vec<ScreenMsg>) {
+        if input.key_pressed(Key::Escape) {
+            msgs.push(ScreenMsg::Goto(Screen::Menu));
+            return;
+        }
+        if input.key_pressed(Key::Tab) {
+            self.paste();
+        }
+        if input.key_pressed(Key::Backspace) {
+            self.code.pop();
+            self.error.clear();
+        }
+        for c in input.text().chars().filter(|c| c.is_ascii_graphic()) {
+            self.code.push(c.to_ascii_uppercase());
+            self.error.clear();
+        }
+        if spin_text(&mut self.code, input, ALPHABET) {
+            self.error.clear();
+        }
+        if input.key_pressed(Key::Enter) {
+            match decode(&self.code) {
+                Ok(level) => {
+                    println!("[INFO] playing code for {}", level.name);
+                    msgs.push(ScreenMsg::PlayCode(Box::new(level)));
+                }
+                Err(err) => self.error = err.to_string(),
+            }
+        }
+    }
+}
+
+impl Drawable for CodeEntry {
+    fn draw(&self, canvas: &mut dyn Canvas) {
+        canvas.label_centered(TITLE, vx2!(WIDTH * 0.5, 100.0), 32.0, Rgba::TEAL);
+        // the code with a cursor after it
+        let chars: Vec<char> = self.code.chars().chain(['_']).collect();
+        for (i, line) in chars.chunks(WRAP).enumerate() {
+            let line: String = line.iter().collect();
+            let y = 220.0 + i as f32 * FS * 1.5;
+            canvas.label_centered(&line, vx2!(WIDTH * 0.5, y), FS, Rgba::TEAL);
+        }
+        if !self.error.is_empty() {
+            canvas.label_centered(
+                &self.error,
+                vx2!(WIDTH * 0.5, 460.0),
+                SMALL_FS,
+                Rgba::MAROON,
+            );
+        }
+        for (i, line) in HELP.iter().enumerate() {
+            let y = HEIGHT - 70.0 + i as f32 * 28.0;
+            canvas.label_centered(line, vx2!(WIDTH * 0.5, y), SMALL_FS, Rgba::TEAL);
+        }
+    }
+}
@@ -0,0 +1,302 @@
+use std::path::{Path, PathBuf};
+
+use grapes::{objects::rectangle::Rectangle, vx2};
+
+use crate::{
+    canvas::{Canvas, Rgba},
+    clipboard,
+    code::encode,
+    common::{
+        BRICK_COLS, BRICK_ROWS, Drawable, FRAME_OFFSET, GameScreen, HEADING_SIZE, HEIGHT,
+        ScreenMsg, WIDTH,
+    },
+    input::{Input, Key},
+    level::{BrickKind, BrickSpec, LEVEL_EXT, Level, PALETTE, POINT_STEPS, levels_dir},
+    sprites::bricks::{brick_rect, draw_brick},
+    validate::validate,
+};
+
+use super::Screen;
+
+const FS: f32 = 18.0;
+const HELP_FS: f32 = 14.0;
+const UNDO_LIMIT: usize = 200;
+const GRID_COLOR: Rgba = Rgba::new(0x30, 0x30, 0x30, 0xFF);
+const CURSOR_COLOR: Rgba = Rgba::new(0xFF, 0xFF, 0xFF, 0xFF);
+const HELP: [&str; 2] = [
+    "Arrows Move  Space Paint  X Erase  F Fill Row  K Kind  C Colour  P Points",
+    "Z Undo  Y Redo  S Save  L Load  G Copy Code  Enter Test  Escape Menu",
+];
+
+/// Paints bricks onto the same grid `Play` uses.
+pub struct Editor {
+    level: Level,
+    cursor: (usize, usize),
+    kind: BrickKind,
+    color: usize,
+    points: usize,
+    undo: Vec<Level>,
+    redo: Vec<Level>,
+    path: PathBuf,
+    status: String,
+}
+
+impl Editor {
+    pub fn init() -> Self {
+        Self {
+            level: Level::new("Untitled"),
+            cursor: (0, 0),
+            kind: BrickKind::Normal,
+            color: 0,
+            points: 3,
+            undo: Vec::new(),
+            redo: Vec::new(),
+            path: levels_dir().join(format!("untitled.{LEVEL_EXT}")),
+            status: String::new(),
+        }
+    }
+
+    pub fn level(&self) -> &Level {
+        &self.level
+    }
+
+    /// Starts over with `level`, the undo history is dropped.
+    pub fn set_level(&mut self, level: Level) {
+        self.level = level;
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The file `S` saves to and `L` loads from.
+    pub fn set_path(&mut self, path: PathBuf) {
+        self.path = path;
+    }
+
+    pub fn cursor(&self) -> (usize, usize) {
+        self.cursor
+    }
+
+    pub fn status(&self) -> &str {
+        &self.status
+    }
+
+    /// What gets painted.
+    pub fn brush(&self) -> BrickSpec {
+        BrickSpec::new(self.kind, PALETTE[self.color], POINT_STEPS[self.points])
+    }
+
+    fn edit<F: FnOnce(&mut Level)>(&mut self, f: F) {
+        let before = self.level.clone();
+        f(&mut self.level);
+        if self.level == before {
+            return;
+        }
+        self.undo.push(before);
+        if self.undo.len() > UNDO_LIMIT {
+            self.undo.remove(0);
+        }
+        self.redo.clear();
+    }
+
+    fn paint(&mut self, brick: Option<BrickSpec>) {
+        let (row, col) = self.cursor;
+        self.edit(|level| level.set(row, col, brick));
+    }
+
+    fn undo(&mut self) {
+        if let Some(level) = self.undo.pop() {
+            self.redo.push(std::mem::replace(&mut self.level, level));
+        }
+    }
+
+    fn redo(&mut self) {
+        if let Some(level) = self.redo.pop() {
+            self.undo.push(std::mem::replace(&mut self.level, level));
+        }
+    }
+
+    fn save(&mut self) {
+        self.status = match self.level.save(&self.path) {
+            Ok(()) => {
+                println!("[INFO] level saved to {}", self.path.display());
+                let problems = validate(&self.level);
+                for problem in &problems {
+                    println!("[ERROR] {problem}");
+                }
+                match problems.first() {
+                    Some(first) if problems.len() > 1 => format!(
+                        "Saved {}, {first} and {} more problems",
+                        self.path.display(),
+                        problems.len() - 1
+                    ),
+                    Some(first) => format!("Saved {}, {first}", self.path.display()),
+                    None => format!("Saved {}", self.path.display()),
+                }
+            }
+            Err(err) => {
+                println!("[ERROR] {err}");
+                format!("Save failed: {err}")
+            }
+        };
+    }
+
+    fn load(&mut self) {
+        self.status = match Level::load(&self.path) {
+            Ok(level) => {
+                let before = std::mem::replace(&mut self.level, level);
+                self.undo.push(before);
+                self.redo.clear();
+                format!("Loaded {}", self.path.display())
+            }
+            Err(err) => {
+                println!("[ERROR] {}: {err}", self.path.display());
+                format!("Load failed: {err}")
+            }
+        };
+    }
+
+    /// Copies the level code, it also goes to the log in case there is no
+    /// clipboard.
+    fn copy_code(&mut self) {
+        let code = encode(&self.level);
+        println!("[INFO] level code: {code}");
+        self.status = match clipboard::copy(&code) {
+            Ok(()) => "Level code copied".to_string(),
+            Err(err) => {
+                println!("[ERROR] could not copy: {err}");
+                format!("Copy failed, code is in the log: {err}")
+            }
+        };
+    }
+
+    fn move_cursor(&mut self, input: &dyn Input) {
+        let (row, col) = &mut self.cursor;
+        if input.key_pressed(Key::Up) {
+            *row = row.saturating_sub(1);
+        }
+        if input.key_pressed(Key::Down) {
+            *row = (*row + 1).min(BRICK_ROWS - 1);
+        }
+        if input.key_pressed(Key::Left) {
+            *col = col.saturating_sub(1);
+        }
+        if input.key_pressed(Key::Right) {
+            *col = (*col + 1).min(BRICK_COLS - 1);
+        }
+    }
+}
+
+impl GameScreen for Editor {
+    fn update(&mut self, input: &dyn Input, msgs: &mut Vec<ScreenMsg>) {
+        if input.key_pressed(Key::Escape) {
+            msgs.push(ScreenMsg::Goto(Screen::Menu));
+            return;
+        }
+        if input.key_pressed(Key::Enter) {
+            msgs.push(ScreenMsg::PlayLevel(Box::new(self.level.clone())));
+            return;
+        }
+
+        self.move_cursor(input);
+
+        if input.key_pressed(Key::Space) {
+            self.paint(Some(self.brush()));
+        }
+        if input.key_pressed(Key::X) {
+            self.paint(None);
+        }
+        if input.key_pressed(Key::F) {
+            let (row, brush) = (self.cursor.0, self.brush());
+            self.edit(|level| level.fill_row(row, Some(brush)));
+        }
+        if input.key_pressed(Key::K) {
+            let i = BrickKind::ALL
+                .iter()
+                .position(|k| *k == self.kind)
+                .unwrap_or(0);
+            self.kind = BrickKind::ALL[(i + 1) % BrickKind::ALL.len()];
+        }
+        if input.key_pressed(Key::C) {
+            self.color = (self.color + 1) % PALETTE.len();
+        }
+        if input.key_pressed(Key::P) {
+            self.points = (self.points + 1) % POINT_STEPS.len();
+        }
+        if input.key_pressed(Key::Z) {
+            self.undo();
+        }
+        if input.key_pressed(Key::Y) {
+            self.redo();
+        }
+        if input.key_pressed(Key::S) {
+            self.save();
+        }
+        if input.key_pressed(Key::L) {
+            self.load();
+        }
+        if input.key_pressed(Key::G) {
+            self.copy_code();
+        }
+    }
+}
+
+impl Drawable for Editor {
+    fn draw(&self, canvas: &mut dyn Canvas) {
+        for row in 0..BRICK_ROWS {
+            for col in 0..BRICK_COLS {
+                let rect = brick_rect(row, col);
+                match self.level.get(row, col) {
+                    Some(b) => {
+                        draw_brick(canvas, &rect, b.kind, b.color, b.kind.hits().unwrap_or(0))
+                    }
+                    None => canvas.stroke_rect(&rect, GRID_COLOR),
+                }
+            }
+        }
+        let cell = brick_rect(self.cursor.0, self.cursor.1);
+        let cursor = Rectangle::new(cell.pos, vx2!(cell.size.x + 4.0, cell.size.y + 4.0));
+        canvas.stroke_rect(&cursor, CURSOR_COLOR);
+
+        // brush in the heading
+        let brush = self.brush();
+        let swatch = Rectangle::new(
+            vx2!(FRAME_OFFSET + 40.0, HEADING_SIZE * 0.5 + FRAME_OFFSET * 0.5),
+            cell.size,
+        );
+        draw_brick(
+            canvas,
+            &swatch,
+            brush.kind,
+            brush.color,
+            brush.kind.hits().unwrap_or(0),
+        );
+        let text = format!(
+            "{} {} pts  {}",
+            brush.kind.name(),
+            brush.points,
+            self.level.name
+        );
+        let pos = vx2!(
+            swatch.pos.x + swatch.size.x * 0.5 + 12.0,
+            swatch.pos.y - FS * 0.5 - 4.0
+        );
+        canvas.label(&text, pos, FS, Rgba::TEAL);
+
+        if !self.status.is_empty() {
+            canvas.label_centered(
+                &self.status,
+                vx2!(WIDTH * 0.5, HEIGHT - 110.0),
+                HELP_FS,
+                Rgba::TEAL,
+            );
+        }
+        for (i, line) in HELP.iter().enumerate() {
+            let y = HEIGHT - 70.0 + i as f32 * 28.0;
+            canvas.label_centered(line, vx2!(WIDTH * 0.5, y), HELP_FS, Rgba::TEAL);
+        }
+    }
+}
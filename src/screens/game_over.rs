@@ -1,44 +1,78 @@
-use grapes::{events::keyboard::K, objects::rectangle::Rectangle, textures::Texture, vx2};
+use grapes::vx2;
 
-use crate::common::GameControl;
+use crate::{
+    canvas::{Canvas, Rgba},
+    common::{
+        Drawable, GAME_OVER_ACTION, GAME_OVER_TITLE, GameScreen, HEIGHT, MENU_QUIT, MENU_SCORE,
+        ScreenMsg, WIDTH,
+    },
+    input::{Input, Key},
+};
 
 use super::Screen;
 
 pub struct GameOver {
-    score: Texture,
-    text_continue: Texture,
-    text_quit: Texture,
-    rectangle: Rectangle,
+    score: usize,
+    /// how a Time or Score Attack went, under the score
+    result: Option<String>,
 }
 
 impl GameOver {
     pub fn init() -> Self {
         Self {
-            score: Texture::init(vx2!(10.0)),
-            text_continue: Texture::init(vx2!(10.0)),
-            text_quit: Texture::init(vx2!(10.0)),
-            rectangle: Rectangle::new(vx2!(10.0), vx2!(10.0)),
+            score: 0,
+            result: None,
         }
     }
-}
 
-impl GameControl for GameOver {
-    fn update(
-        &mut self,
-        renderer: &mut grapes::renderer::two_d::Renderer,
-        events: &grapes::events::input::Events,
-    ) -> Option<super::Screen> {
-        match events.key_pressed(K::Escape) {
-            true => return Some(Screen::Quit),
-            false => return None,
-        }
+    pub fn set_score(&mut self, score: usize) {
+        self.score = score;
+    }
+
+    pub fn set_result(&mut self, result: Option<String>) {
+        self.result = result;
+    }
+
+    pub fn result(&self) -> Option<&str> {
+        self.result.as_deref()
     }
+}
 
-    fn draw(&self, renderer: &mut grapes::renderer::two_d::Renderer) {
-        todo!()
+impl GameScreen for GameOver {
+    fn update(&mut self, input: &dyn Input, msgs: &mut Vec<ScreenMsg>) {
+        if input.key_pressed(Key::Enter) {
+            msgs.push(ScreenMsg::Goto(Screen::Menu));
+        } else if input.key_pressed(Key::Escape) {
+            msgs.push(ScreenMsg::Quit);
+        }
     }
+}
 
-    fn get_score(&mut self, score: usize) {
-        
+impl Drawable for GameOver {
+    fn draw(&self, canvas: &mut dyn Canvas) {
+        let color = Rgba::TEAL;
+        canvas.label_centered(
+            GAME_OVER_TITLE,
+            vx2!(WIDTH * 0.5, 300.0),
+            48.0,
+            Rgba::MAROON,
+        );
+        canvas.label_centered(MENU_SCORE, vx2!(WIDTH * 0.5, HEIGHT - 320.0), 32.0, color);
+        canvas.label_centered(
+            &self.score.to_string(),
+            vx2!(WIDTH * 0.5, HEIGHT - 280.0),
+            32.0,
+            color,
+        );
+        if let Some(result) = &self.result {
+            canvas.label_centered(result, vx2!(WIDTH * 0.5, HEIGHT - 230.0), 24.0, color);
+        }
+        canvas.label_centered(
+            GAME_OVER_ACTION,
+            vx2!(WIDTH * 0.5, HEIGHT - 150.0),
+            24.0,
+            color,
+        );
+        canvas.label_centered(MENU_QUIT, vx2!(WIDTH * 0.5, HEIGHT - 50.0), 14.0, color);
     }
 }
@@ -1,164 +1,135 @@
-use grapes::{
-    colors::presets::GrapesColors,
-    events::keyboard::K,
-    fonts::Font,
-    objects::rectangle::Rectangle,
-    renderer::two_d::{Render, Renderer},
-    textures::Texture,
-    vx2,
+use grapes::vx2;
+
+use crate::{
+    canvas::{Canvas, Rgba},
+    common::{
+        Drawable, GameScreen, HEIGHT, MENU_ACTION, MENU_CODE, MENU_DIFFICULTY, MENU_EDITOR,
+        MENU_MODE, MENU_PACKS, MENU_QUIT, MENU_RULES, MENU_SCORE, MENU_TITLE, MENU_VARIANT,
+        ScreenMsg, WIDTH,
+    },
+    difficulty::Difficulty,
+    input::{Input, Key},
+    mode::Mode,
+    rules::Ruleset,
+    variant::VariantKind,
 };
 
-use crate::common::{GameControl, HEIGHT, MENU_ACTION, MENU_QUIT, MENU_SCORE, MENU_TITLE, WIDTH};
-
 use super::Screen;
 
 //TODO ADD Hit Escape at any time to Quit
 pub struct Menu {
-    title: Texture,
-    title_rect: Rectangle,
-    quit: Texture,
-    quit_rect: Rectangle,
-    score: Texture,
-    score_rect: Rectangle,
-    action: Texture,
-    action_rect: Rectangle,
-    render_score: bool,
+    score: Option<usize>,
+    difficulty: Difficulty,
+    ruleset: Ruleset,
+    variant: VariantKind,
+    mode: Mode,
 }
 
 impl Menu {
-    pub fn init(font: &Font) -> Self {
-        let title_width = font.width(MENU_TITLE, 48.0);
-        let action_width = font.width(MENU_ACTION, 24.0);
-        let quit_width = font.width(MENU_QUIT, 14.0);
-        let score_width = font.width(MENU_SCORE, 32.0);
-
-        let mut title = Texture::init_with_background_color(
-            vx2!(title_width + 2.0 * 8.0, 60.0),
-            GrapesColors::GrapesBlack.into(),
-        );
-
-        let title_rect = Rectangle::new(
-            vx2!(WIDTH * 0.5, 300.0),
-            vx2!(title_width + 2.0 * 8.0, 60.0),
-        );
-
-        font.render_into_texture(
-            MENU_TITLE,
-            vx2!(8.0, 56.0),
-            48.0,
-            GrapesColors::Teal.into(),
-            &mut title,
-        );
+    pub fn init() -> Self {
+        Self {
+            score: None,
+            difficulty: Difficulty::default(),
+            ruleset: Ruleset::default(),
+            variant: VariantKind::default(),
+            mode: Mode::default(),
+        }
+    }
 
-        let mut quit = Texture::init_with_background_color(
-            vx2!(quit_width + 2.0 * 8.0, 50.0),
-            GrapesColors::GrapesBlack.into(),
-        );
+    pub fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
 
-        let quit_rect = Rectangle::new(
-            vx2!(WIDTH * 0.5, HEIGHT - 50.0),
-            vx2!(quit_width + 2.0 * 8.0, 50.0),
-        );
+    pub fn set_difficulty(&mut self, difficulty: Difficulty) {
+        self.difficulty = difficulty;
+    }
 
-        font.render_into_texture(
-            MENU_QUIT,
-            vx2!(8.0, 18.0),
-            14.0,
-            GrapesColors::Teal.into(),
-            &mut quit,
-        );
+    pub fn ruleset(&self) -> Ruleset {
+        self.ruleset
+    }
 
-        let mut action = Texture::init_with_background_color(
-            vx2!(action_width + 2.0 * 8.0, 50.0),
-            GrapesColors::GrapesBlack.into(),
-        );
+    pub fn set_ruleset(&mut self, ruleset: Ruleset) {
+        self.ruleset = ruleset;
+    }
 
-        let action_rect = Rectangle::new(
-            vx2!(WIDTH * 0.5, HEIGHT - 150.0),
-            vx2!(action_width + 2.0 * 8.0, 50.0),
-        );
+    pub fn variant(&self) -> VariantKind {
+        self.variant
+    }
 
-        font.render_into_texture(
-            MENU_ACTION,
-            vx2!(8.0, 28.0),
-            24.0,
-            GrapesColors::Teal.into(),
-            &mut action,
-        );
+    pub fn set_variant(&mut self, variant: VariantKind) {
+        self.variant = variant;
+    }
 
-        let score = Texture::init_with_background_color(
-            vx2!(score_width + 2.0 * 8.0, 84.0),
-            GrapesColors::GrapesBlack.into(),
-        );
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
 
-        let score_rect = Rectangle::new(
-            vx2!(WIDTH * 0.5, HEIGHT - 300.0),
-            vx2!(score_width + 2.0 * 8.0, 84.0),
-        );
+    pub fn set_mode(&mut self, mode: Mode) {
+        self.mode = mode;
+    }
 
-        Self {
-            title,
-            title_rect,
-            action,
-            action_rect,
-            quit,
-            quit_rect,
-            score,
-            score_rect,
-            render_score: false,
-        }
+    pub fn prepare_render_score(&mut self, score: usize) {
+        self.score = Some(score);
     }
 }
 
-impl GameControl for Menu {
-    fn update(
-        &mut self,
-        _renderer: &mut Renderer,
-        events: &grapes::events::input::Events,
-    ) -> Option<Screen> {
-        if events.key_down(K::Enter) {
-            return Some(Screen::Play);
+impl GameScreen for Menu {
+    fn update(&mut self, input: &dyn Input, msgs: &mut Vec<ScreenMsg>) {
+        // the versus modes have nothing to set
+        let settings = self.mode.has_settings();
+        if input.key_pressed(Key::Enter) {
+            msgs.push(ScreenMsg::Goto(Screen::Play));
+        } else if input.key_pressed(Key::E) {
+            msgs.push(ScreenMsg::Goto(Screen::Editor));
+        } else if input.key_pressed(Key::P) {
+            msgs.push(ScreenMsg::Goto(Screen::Packs));
+        } else if input.key_pressed(Key::C) {
+            msgs.push(ScreenMsg::Goto(Screen::Code));
+        } else if settings && input.key_pressed(Key::D) {
+            self.difficulty = self.difficulty.next();
+            msgs.push(ScreenMsg::Difficulty(self.difficulty));
+        } else if settings && input.key_pressed(Key::R) {
+            self.ruleset = self.ruleset.next();
+            msgs.push(ScreenMsg::Ruleset(self.ruleset));
+        } else if settings && input.key_pressed(Key::V) {
+            self.variant = self.variant.next();
+            msgs.push(ScreenMsg::Variant(self.variant));
+        } else if input.key_pressed(Key::M) {
+            self.mode = self.mode.next();
+            msgs.push(ScreenMsg::Mode(self.mode));
+        } else if input.key_pressed(Key::Escape) {
+            msgs.push(ScreenMsg::Quit);
         }
-        if events.key_pressed(K::Escape) {
-            return Some(Screen::Quit);
-        }
-        return None;
     }
+}
 
-    fn draw(&self, renderer: &mut Renderer) {
-        self.title_rect.with_texture(renderer, &self.title);
-        self.action_rect.with_texture(renderer, &self.action);
-        self.quit_rect.with_texture(renderer, &self.quit);
-        if self.render_score {
-            self.score_rect.with_texture(renderer, &self.score);
+impl Drawable for Menu {
+    fn draw(&self, canvas: &mut dyn Canvas) {
+        let color = Rgba::TEAL;
+        canvas.label_centered(MENU_TITLE, vx2!(WIDTH * 0.5, 300.0), 48.0, color);
+        canvas.label_centered(MENU_ACTION, vx2!(WIDTH * 0.5, HEIGHT - 310.0), 24.0, color);
+        let mode = format!("{MENU_MODE}: {}", self.mode.name());
+        canvas.label_centered(&mode, vx2!(WIDTH * 0.5, HEIGHT - 260.0), 14.0, color);
+        if self.mode.has_settings() {
+            let variant = format!("{MENU_VARIANT}: {}", self.variant.name());
+            canvas.label_centered(&variant, vx2!(WIDTH * 0.5, HEIGHT - 230.0), 14.0, color);
+            let rules = format!("{MENU_RULES}: {}", self.ruleset.name());
+            canvas.label_centered(&rules, vx2!(WIDTH * 0.5, HEIGHT - 200.0), 14.0, color);
+            let difficulty = format!("{MENU_DIFFICULTY}: {}", self.difficulty.name());
+            canvas.label_centered(&difficulty, vx2!(WIDTH * 0.5, HEIGHT - 170.0), 14.0, color);
+        }
+        canvas.label_centered(MENU_PACKS, vx2!(WIDTH * 0.5, HEIGHT - 140.0), 14.0, color);
+        canvas.label_centered(MENU_CODE, vx2!(WIDTH * 0.5, HEIGHT - 110.0), 14.0, color);
+        canvas.label_centered(MENU_EDITOR, vx2!(WIDTH * 0.5, HEIGHT - 80.0), 14.0, color);
+        canvas.label_centered(MENU_QUIT, vx2!(WIDTH * 0.5, HEIGHT - 50.0), 14.0, color);
+        if let Some(score) = self.score {
+            canvas.label_centered(MENU_SCORE, vx2!(WIDTH * 0.5, HEIGHT - 420.0), 32.0, color);
+            canvas.label_centered(
+                &score.to_string(),
+                vx2!(WIDTH * 0.5, HEIGHT - 380.0),
+                32.0,
+                color,
+            );
         }
-    }
-
-    fn prepare_render_score(&mut self, score: usize, font: &Font) {
-        self.render_score = true;
-        self.score.clear(GrapesColors::GrapesBlack.into());
-
-        let sscore = score.to_string();
-        let width = font.width(sscore.as_bytes(), 32.0);
-
-        let texture_width = self.score.size().x;
-        let color = GrapesColors::Teal;
-
-        font.render_into_texture(
-            MENU_SCORE,
-            vx2!(8.0, 40.0),
-            32.0,
-            color.into(),
-            &mut self.score,
-        );
-
-        let x_pos = (texture_width * 0.5) - (width * 0.5);
-        font.render_into_texture(
-            sscore.as_bytes(),
-            vx2!(x_pos, 80.0),
-            32.0,
-            color.into(),
-            &mut self.score,
-        );
     }
 }
@@ -1,61 +1,77 @@
 use std::ops::{Index, IndexMut};
 
-use grapes::{
-    colors::presets::GrapesColors, fonts::Font, objects::line::Line2d, textures::Texture, vx2,
-};
+use code::CodeEntry;
+use editor::Editor;
+use game_over::GameOver;
+use grapes::objects::line::Line2d;
 use menu::Menu;
+use online::Online;
+use packs::Packs;
 use play::Play;
+use versus::Versus;
 
-use crate::common::{
-    GameControl, PAUSE_FS, PAUSE_MARGIN_LEFT, PAUSE_TEXT_HEIGHT, PAUSE_TEXT_WIDTH,
-};
+use crate::common::GameScreen;
 
+pub mod code;
+pub mod editor;
+pub mod game_over;
 pub mod menu;
+pub mod online;
+pub mod packs;
 pub mod play;
+pub mod versus;
 
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Screen {
     Menu,
     Play,
     GameOver,
-    Quit,
+    Editor,
+    Packs,
+    Code,
+    Versus,
+    Online,
 }
 
 pub struct Screens {
-    screens: [Box<dyn GameControl>; 2],
+    pub menu: Menu,
+    pub play: Play,
+    pub game_over: GameOver,
+    pub editor: Editor,
+    pub packs: Packs,
+    pub code: CodeEntry,
+    pub versus: Versus,
+    pub online: Online,
 }
 
 impl Screens {
     pub fn init(frame: [Line2d; 5]) -> Self {
-        let font = Font::load("./assets/NotoSansMono.ttf");
-        let menu = Menu::init(&font);
-        let mut pause_texture = Texture::init_with_background_color(
-            vx2!(PAUSE_TEXT_WIDTH, PAUSE_TEXT_HEIGHT),
-            GrapesColors::GrapesBlack.into(),
-        );
-        font.render_into_texture(
-            b"PAUSED",
-            vx2!(PAUSE_MARGIN_LEFT, PAUSE_FS),
-            PAUSE_FS,
-            GrapesColors::Maroon.into(),
-            &mut pause_texture,
-        );
-        let play = Play::init(frame, pause_texture);
-
         Self {
-            screens: [Box::new(menu), Box::new(play)],
+            menu: Menu::init(),
+            play: Play::init(frame),
+            game_over: GameOver::init(),
+            editor: Editor::init(),
+            packs: Packs::init(),
+            code: CodeEntry::init(),
+            versus: Versus::init(),
+            online: Online::init(),
         }
     }
 }
 
 impl Index<&Screen> for Screens {
-    type Output = Box<dyn GameControl>;
+    type Output = dyn GameScreen;
 
     fn index(&self, index: &Screen) -> &Self::Output {
         match index {
-            Screen::Menu => &self.screens[0],
-            Screen::Play => &self.screens[1],
-            _ => panic!(),
+            Screen::Menu => &self.menu,
+            Screen::Play => &self.play,
+            Screen::GameOver => &self.game_over,
+            Screen::Editor => &self.editor,
+            Screen::Packs => &self.packs,
+            Screen::Code => &self.code,
+            Screen::Versus => &self.versus,
+            Screen::Online => &self.online,
         }
     }
 }
@@ -63,9 +79,14 @@ impl Index<&Screen> for Screens {
 impl IndexMut<&Screen> for Screens {
     fn index_mut(&mut self, index: &Screen) -> &mut Self::Output {
         match index {
-            Screen::Menu => &mut self.screens[0],
-            Screen::Play => &mut self.screens[1],
-            _ => panic!(),
+            Screen::Menu => &mut self.menu,
+            Screen::Play => &mut self.play,
+            Screen::GameOver => &mut self.game_over,
+            Screen::Editor => &mut self.editor,
+            Screen::Packs => &mut self.packs,
+            Screen::Code => &mut self.code,
+            Screen::Versus => &mut self.versus,
+            Screen::Online => &mut self.online,
         }
     }
 }
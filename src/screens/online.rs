@@ -0,0 +1,365 @@
+use std::net::SocketAddr;
+
+use grapes::vx2;
+use rand::Rng;
+
+use crate::{
+    canvas::{Canvas, Rgba},
+    clipboard,
+    common::{Drawable, GAME_OVER_ACTION, GameScreen, HEIGHT, ScreenMsg, WIDTH},
+    input::{Input, Key, Pad, spin_text},
+    lobby::{Announcer, DISCOVERY_PORT, Finder, Found, Lobby, broadcast_addr, lobby_name},
+    net::{DEFAULT_PORT, Host, NetError, PROTOCOL_VERSION, Session},
+};
+
+use super::Screen;
+
+const TITLE: &str = "Online Versus";
+const HOST: &str = "Hit H To Host A Game";
+const JOIN: &str = "Hit J To Join One";
+const LOBBIES: &str = "Games On The Network";
+const LOBBY_HELP: &str = "Up/Down Choose  Enter Join  A Enter An Address  Escape Menu";
+const HELP: &str = "Escape Menu";
+const JOIN_HELP: &str =
+    "Up/Down Change  Right Next  Backspace Delete  Tab Paste  Enter Join  Escape Menu";
+/// What the arrows spell an address from.
+const ADDRESS_CHARS: &str = ".0123456789:";
+const FS: f32 = 18.0;
+const SMALL_FS: f32 = 14.0;
+/// Keys of the player at this game, the other side is played over the
+/// network.
+pub const LOCAL_KEYS: [Key; 3] = [Key::Left, Key::Right, Key::Space];
+/// Updates without the other player's pad before the game says it waits.
+const STALL_SHOWN: usize = 30;
+
+enum Stage {
+    /// host or join
+    Choose,
+    /// picking one of the lobbies announced on the network
+    Lobbies {
+        finder: Finder,
+        selected: usize,
+        /// why the selected lobby can not be joined
+        notice: Option<String>,
+    },
+    /// entering the host's address
+    Join,
+    /// the announcer is missing if the lobby could not be announced
+    Hosting(Host, Option<Announcer>),
+    Playing(Box<Session>),
+    /// the game could not start or was cut short, says why
+    Ended(String),
+}
+
+/// Hosts or joins a versus game with another game on the network and plays
+/// it, see `net::Session`.
+pub struct Online {
+    stage: Stage,
+    /// of the host to join
+    address: String,
+    /// of the lobbies hosted here
+    lobby_name: String,
+    /// where hosted lobbies are announced
+    announce_addr: SocketAddr,
+}
+
+impl Online {
+    pub fn init() -> Self {
+        Self {
+            stage: Stage::Choose,
+            address: format!("127.0.0.1:{DEFAULT_PORT}"),
+            lobby_name: lobby_name(),
+            announce_addr: broadcast_addr(),
+        }
+    }
+
+    /// Back to hosting or joining, a game still connected is left.
+    pub fn reset(&mut self) {
+        self.stage = Stage::Choose;
+    }
+
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    pub fn set_address(&mut self, address: &str) {
+        self.address = address.trim().to_string();
+    }
+
+    pub fn set_lobby_name(&mut self, name: &str) {
+        self.lobby_name = name.to_string();
+    }
+
+    /// Announces hosted lobbies to `addr` instead of the whole LAN.
+    pub fn set_announce_addr(&mut self, addr: SocketAddr) {
+        self.announce_addr = addr;
+    }
+
+    /// Waits for a game to join on `addr` and announces the lobby, returns
+    /// where it listens.
+    pub fn host(&mut self, addr: &str) -> Result<SocketAddr, NetError> {
+        let host = Host::bind(addr)?;
+        let addr = host.addr();
+        println!("[INFO] hosting on {addr}");
+        let lobby = Lobby::new(&self.lobby_name, addr.port());
+        let announcer = match Announcer::new(&lobby, self.announce_addr) {
+            Ok(announcer) => Some(announcer),
+            Err(err) => {
+                println!("[ERROR] could not announce the lobby: {err}");
+                None
+            }
+        };
+        self.stage = Stage::Hosting(host, announcer);
+        Ok(addr)
+    }
+
+    /// Lists the lobbies announced to `addr`, returns where it listens.
+    pub fn find(&mut self, addr: &str) -> Result<SocketAddr, NetError> {
+        let finder = Finder::bind(addr)?;
+        let addr = finder.addr();
+        println!("[INFO] looking for lobbies on {addr}");
+        self.stage = Stage::Lobbies {
+            finder,
+            selected: 0,
+            notice: None,
+        };
+        Ok(addr)
+    }
+
+    /// The lobbies found while picking one.
+    pub fn lobbies(&self) -> &[Found] {
+        match &self.stage {
+            Stage::Lobbies { finder, .. } => finder.lobbies(),
+            _ => &[],
+        }
+    }
+
+    /// Why the selected lobby can not be joined.
+    pub fn notice(&self) -> Option<&str> {
+        match &self.stage {
+            Stage::Lobbies { notice, .. } => notice.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Joins the game hosted at `address`.
+    pub fn join(&mut self) {
+        match Session::join(&self.address) {
+            Ok(session) => {
+                println!("[INFO] joined {}", self.address);
+                self.stage = Stage::Playing(Box::new(session));
+            }
+            Err(err) => self.fail(err),
+        }
+    }
+
+    pub fn session(&self) -> Option<&Session> {
+        match &self.stage {
+            Stage::Playing(session) => Some(session),
+            _ => None,
+        }
+    }
+
+    pub fn is_hosting(&self) -> bool {
+        matches!(self.stage, Stage::Hosting(..))
+    }
+
+    /// Why the game could not start or ended early.
+    pub fn error(&self) -> Option<&str> {
+        match &self.stage {
+            Stage::Ended(err) => Some(err),
+            _ => None,
+        }
+    }
+
+    fn fail(&mut self, err: NetError) {
+        println!("[ERROR] {err}");
+        self.stage = Stage::Ended(err.to_string());
+    }
+
+    /// Picks a lobby, joins it if it runs this version.
+    fn update_lobbies(&mut self, input: &dyn Input) {
+        let Stage::Lobbies {
+            finder,
+            selected,
+            notice,
+        } = &mut self.stage
+        else {
+            return;
+        };
+        finder.poll();
+        let lobbies = finder.lobbies();
+        if input.key_pressed(Key::Up) {
+            *selected = selected.saturating_sub(1);
+            *notice = None;
+        }
+        if input.key_pressed(Key::Down) && *selected + 1 < lobbies.len() {
+            *selected += 1;
+            *notice = None;
+        }
+        *selected = (*selected).min(lobbies.len().saturating_sub(1));
+        if input.key_pressed(Key::A) {
+            self.stage = Stage::Join;
+            return;
+        }
+        if !input.key_pressed(Key::Enter) {
+            return;
+        }
+        let Some(found) = lobbies.get(*selected) else {
+            return;
+        };
+        let lobby = &found.lobby;
+        if !lobby.is_compatible() {
+            let text = format!(
+                "{} runs version {}, this game runs version {PROTOCOL_VERSION}",
+                lobby.name, lobby.version
+            );
+            println!("[ERROR] {text}");
+            *notice = Some(text);
+            return;
+        }
+        self.address = found.addr.to_string();
+        self.join();
+    }
+
+    fn paste(&mut self) {
+        match clipboard::paste() {
+            Ok(text) => self.set_address(&text),
+            Err(err) => println!("[ERROR] could not paste: {err}"),
+        }
+    }
+}
+
+impl GameScreen for Online {
+    fn update(&mut self, input: &dyn Input, msgs: &mut Vec<ScreenMsg>) {
+        if input.key_pressed(Key::Escape) {
+            self.reset();
+            msgs.push(ScreenMsg::Goto(Screen::Menu));
+            return;
+        }
+        let result = match &mut self.stage {
+            Stage::Choose if input.key_pressed(Key::H) => {
+                self.host(&format!("0.0.0.0:{DEFAULT_PORT}")).map(|_| ())
+            }
+            Stage::Choose if input.key_pressed(Key::J) => {
+                self.find(&format!("0.0.0.0:{DISCOVERY_PORT}")).map(|_| ())
+            }
+            Stage::Lobbies { .. } => {
+                self.update_lobbies(input);
+                Ok(())
+            }
+            Stage::Join => {
+                if input.key_pressed(Key::Tab) {
+                    self.paste();
+                }
+                if input.key_pressed(Key::Backspace) {
+                    self.address.pop();
+                }
+                let typed = input.text().chars().filter(|c| c.is_ascii_graphic());
+                self.address.extend(typed);
+                spin_text(&mut self.address, input, ADDRESS_CHARS);
+                if input.key_pressed(Key::Enter) {
+                    self.join();
+                }
+                Ok(())
+            }
+            Stage::Hosting(host, announcer) => {
+                if let Some(announcer) = announcer {
+                    announcer.tick();
+                }
+                match host.accept(rand::rng().random()) {
+                    Ok(Some(session)) => {
+                        self.stage = Stage::Playing(Box::new(session));
+                        Ok(())
+                    }
+                    Ok(None) => Ok(()),
+                    Err(err) => Err(err),
+                }
+            }
+            Stage::Playing(session) => session.update(Pad::read(input, LOCAL_KEYS), msgs),
+            Stage::Ended(_) if input.key_pressed(Key::Enter) => {
+                self.reset();
+                msgs.push(ScreenMsg::Goto(Screen::Menu));
+                Ok(())
+            }
+            _ => Ok(()),
+        };
+        if let Err(err) = result {
+            self.fail(err);
+        }
+    }
+}
+
+impl Drawable for Online {
+    fn draw(&self, canvas: &mut dyn Canvas) {
+        let center = |y: f32| vx2!(WIDTH * 0.5, y);
+        let help = match &self.stage {
+            Stage::Playing(session) if session.is_ready() => {
+                session.versus().draw(canvas);
+                let ping = session
+                    .latency()
+                    .map_or("-".to_string(), |l| l.as_millis().to_string());
+                let status = format!("You Are P{}  Ping {ping} ms", session.side() + 1);
+                canvas.label_centered(&status, center(HEIGHT - 12.0), SMALL_FS, Rgba::TEAL);
+                if session.stalled() > STALL_SHOWN {
+                    let text = "Waiting For The Other Player";
+                    canvas.label_centered(text, center(HEIGHT * 0.5), FS, Rgba::MAROON);
+                }
+                return;
+            }
+            Stage::Choose => {
+                canvas.label_centered(HOST, center(300.0), FS, Rgba::TEAL);
+                canvas.label_centered(JOIN, center(340.0), FS, Rgba::TEAL);
+                HELP
+            }
+            Stage::Lobbies {
+                finder,
+                selected,
+                notice,
+            } => {
+                canvas.label_centered(LOBBIES, center(180.0), FS, Rgba::TEAL);
+                if finder.lobbies().is_empty() {
+                    canvas.label_centered("Looking For Games", center(240.0), FS, Rgba::TEAL);
+                }
+                for (i, found) in finder.lobbies().iter().enumerate() {
+                    let lobby = &found.lobby;
+                    let marker = if i == *selected { ">" } else { " " };
+                    let mut text = format!("{marker} {}  {}", lobby.name, found.addr.ip());
+                    let color = if lobby.is_compatible() {
+                        Rgba::TEAL
+                    } else {
+                        text.push_str(&format!("  Version {}", lobby.version));
+                        Rgba::MAROON
+                    };
+                    let y = 240.0 + i as f32 * FS * 1.8;
+                    canvas.label_centered(&text, center(y), SMALL_FS, color);
+                }
+                if let Some(notice) = notice {
+                    canvas.label_centered(notice, center(HEIGHT - 100.0), SMALL_FS, Rgba::MAROON);
+                }
+                LOBBY_HELP
+            }
+            Stage::Join => {
+                let address = format!("{}_", self.address);
+                canvas.label_centered(&address, center(300.0), FS, Rgba::TEAL);
+                JOIN_HELP
+            }
+            Stage::Hosting(host, _) => {
+                let port = format!("On Port {}", host.addr().port());
+                canvas.label_centered("Waiting For A Player", center(300.0), FS, Rgba::TEAL);
+                canvas.label_centered(&port, center(340.0), FS, Rgba::TEAL);
+                HELP
+            }
+            Stage::Playing(_) => {
+                canvas.label_centered("Connecting", center(300.0), FS, Rgba::TEAL);
+                HELP
+            }
+            Stage::Ended(err) => {
+                canvas.label_centered(err, center(300.0), FS, Rgba::MAROON);
+                GAME_OVER_ACTION
+            }
+        };
+        canvas.label_centered(TITLE, center(100.0), 32.0, Rgba::TEAL);
+        canvas.label_centered(help, center(HEIGHT - 50.0), SMALL_FS, Rgba::TEAL);
+    }
+}
@@ -0,0 +1,168 @@
+use std::path::PathBuf;
+
+use grapes::vx2;
+
+use crate::{
+    canvas::{Canvas, Rgba},
+    common::{Drawable, FRAME_OFFSET, GameScreen, HEIGHT, ScreenMsg, WIDTH},
+    input::{Input, Key},
+    pack::{Pack, PackError, Progress, packs_dir, progress_path, scan},
+};
+
+use super::Screen;
+
+const TITLE: &str = "Level Packs";
+const HELP: &str = "Up/Down Choose  Enter Play  Escape Menu";
+const FS: f32 = 18.0;
+const SMALL_FS: f32 = 14.0;
+const LINE: f32 = 30.0;
+const LEFT: f32 = FRAME_OFFSET + 36.0;
+
+/// Lists the installed packs and starts one at the first stage not cleared.
+pub struct Packs {
+    dir: PathBuf,
+    packs: Vec<Result<Pack, (String, PackError)>>,
+    progress: Progress,
+    selected: usize,
+}
+
+impl Packs {
+    pub fn init() -> Self {
+        Self {
+            dir: packs_dir(),
+            packs: Vec::new(),
+            progress: Progress::new(progress_path()),
+            selected: 0,
+        }
+    }
+
+    /// Looks for packs in `dir` and keeps progress in `progress` instead of
+    /// the user's data directory.
+    pub fn set_dirs(&mut self, dir: PathBuf, progress: PathBuf) {
+        self.dir = dir;
+        self.progress = Progress::new(progress);
+    }
+
+    /// Scans the packs directory and reads the saved progress again.
+    pub fn refresh(&mut self) {
+        self.packs = scan(&self.dir);
+        self.progress = Progress::load(self.progress.path().to_path_buf());
+        self.selected = self.selected.min(self.packs.len().saturating_sub(1));
+    }
+
+    pub fn packs(&self) -> &[Result<Pack, (String, PackError)>] {
+        &self.packs
+    }
+
+    pub fn pack(&self, idx: usize) -> Option<&Pack> {
+        self.packs.get(idx).and_then(|p| p.as_ref().ok())
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    pub fn progress(&self) -> &Progress {
+        &self.progress
+    }
+
+    pub fn progress_mut(&mut self) -> &mut Progress {
+        &mut self.progress
+    }
+
+    /// Where a run of `pack` starts, after the furthest stage cleared.
+    fn start_stage(&self, pack: &Pack) -> Option<usize> {
+        let furthest = self.progress.get(&pack.id).furthest;
+        pack.next_playable(furthest)
+            .or_else(|| pack.next_playable(0))
+    }
+
+    fn draw_details(&self, canvas: &mut dyn Canvas, pack: &Pack, top: f32) {
+        let mut y = top;
+        if !pack.description.is_empty() {
+            canvas.label(&pack.description, vx2!(LEFT, y), SMALL_FS, Rgba::TEAL);
+            y += LINE;
+        }
+        let progress = self.progress.get(&pack.id);
+        for (i, stage) in pack.stages.iter().enumerate() {
+            // broken stages are skipped, not cleared
+            let cleared = i < progress.furthest && stage.level.is_ok();
+            let mark = if cleared { "*" } else { " " };
+            let mut text = format!("{mark} {}. {}", i + 1, stage.title);
+            if let Some(par) = stage.par {
+                text.push_str(&format!("  par {par}"));
+            }
+            let color = match &stage.level {
+                Ok(_) => Rgba::TEAL,
+                Err(err) => {
+                    text.push_str(&format!("  {err}"));
+                    Rgba::MAROON
+                }
+            };
+            canvas.label(&text, vx2!(LEFT, y), SMALL_FS, color);
+            y += LINE * 0.8;
+        }
+    }
+}
+
+impl GameScreen for Packs {
+    fn update(&mut self, input: &dyn Input, msgs: &mut Vec<ScreenMsg>) {
+        if input.key_pressed(Key::Escape) {
+            msgs.push(ScreenMsg::Goto(Screen::Menu));
+            return;
+        }
+        if input.key_pressed(Key::Up) {
+            self.selected = self.selected.saturating_sub(1);
+        }
+        if input.key_pressed(Key::Down) && self.selected + 1 < self.packs.len() {
+            self.selected += 1;
+        }
+        if input.key_pressed(Key::Enter) {
+            if let Some(stage) = self.pack(self.selected).and_then(|p| self.start_stage(p)) {
+                msgs.push(ScreenMsg::PlayPack {
+                    pack: self.selected,
+                    stage,
+                });
+            }
+        }
+    }
+}
+
+impl Drawable for Packs {
+    fn draw(&self, canvas: &mut dyn Canvas) {
+        canvas.label_centered(TITLE, vx2!(WIDTH * 0.5, 100.0), 32.0, Rgba::TEAL);
+        if self.packs.is_empty() {
+            let text = format!("No packs in {}", self.dir.display());
+            canvas.label_centered(&text, vx2!(WIDTH * 0.5, 200.0), SMALL_FS, Rgba::TEAL);
+        }
+
+        let mut y = 160.0;
+        for (i, pack) in self.packs.iter().enumerate() {
+            let marker = if i == self.selected { ">" } else { " " };
+            let (text, color) = match pack {
+                Ok(pack) => {
+                    let progress = self.progress.get(&pack.id);
+                    let mut text = format!(
+                        "{marker} {}  {}/{}  best {}",
+                        pack.name,
+                        progress.furthest.min(pack.stages.len()),
+                        pack.stages.len(),
+                        progress.best
+                    );
+                    if !pack.author.is_empty() {
+                        text.push_str(&format!("  by {}", pack.author));
+                    }
+                    (text, Rgba::TEAL)
+                }
+                Err((id, err)) => (format!("{marker} {id}: {err}"), Rgba::MAROON),
+            };
+            canvas.label(&text, vx2!(LEFT - 20.0, y), FS, color);
+            y += LINE;
+        }
+
+        if let Some(pack) = self.pack(self.selected) {
+            self.draw_details(canvas, pack, y + LINE);
+        }
+        canvas.label_centered(HELP, vx2!(WIDTH * 0.5, HEIGHT - 50.0), SMALL_FS, Rgba::TEAL);
+    }
+}
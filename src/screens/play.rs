@@ -1,30 +1,89 @@
-use std::f32;
-
 use grapes::{
-    colors::{color::Color, presets::GrapesColors},
-    events::keyboard::K,
     linal::vertx2::VX2,
     objects::{line::Line2d, rectangle::Rectangle},
-    renderer::two_d::Render,
-    textures::Texture,
     vx2,
 };
+use rand::{Rng, SeedableRng, rngs::StdRng};
 
 use crate::{
+    canvas::{Canvas, Rgba},
+    cavity::{CavityBall, Pocket},
     common::{
-        BALL_BASE_VEL_X, BALL_BASE_VEL_Y, BALL_RADIUS, GameControl, HEIGHT, PAUSE_TEXT_HEIGHT,
-        PAUSE_TEXT_WIDTH, WIDTH,
+        BALL_BASE_VEL_X, BALL_BASE_VEL_Y, BALL_RADIUS, BRICK_COLS, BRICK_ROWS, Drawable, Field,
+        GameScreen, PADDLE_VEL, PADDLE_WIDTH, PAUSE_FS, PAUSE_TEXT_HEIGHT, PAUSE_TEXT_WIDTH,
+        ScreenMsg, Sprite, ball_base_speed, clock_text,
     },
+    hot_seat::HotSeat,
+    input::{Input, Key},
+    level::{BrickSpec, Level},
+    mode::{Mode, Progressive, SCORE_ATTACK_FRAMES},
+    rules::{CLASSIC_POINTS, Classic, Ruleset},
     sprites::{
         ball::{Ball, get_rand_init_vel},
         bricks::Bricks,
         paddle::Paddle,
     },
+    variant::{Variant, VariantKind},
 };
 
 use super::Screen;
 
-const POINTS: [usize; 8] = [100, 100, 50, 50, 25, 25, 10, 10];
+/// Share of the speed that always goes up after a paddle hit.
+pub const MIN_VY_RATIO: f32 = 0.25;
+/// Longest distance the ball moves between two collision checks, short
+/// enough that it can not pass through a brick or a wall at any speed.
+pub const MAX_STEP: f32 = BALL_RADIUS * 0.5;
+/// Keys of the second paddle in Co-op, the first one keeps the arrows.
+pub const PARTNER_KEYS: [Key; 2] = [Key::A, Key::D];
+/// Color of the second paddle in Co-op.
+pub const PARTNER_COLOR: Rgba = Rgba::new(0x80, 0x80, 0x00, 0xFF);
+
+/// Velocity after bouncing off the paddle, `offset` is the ball's x distance
+/// to the paddle centre and `half_width` half the paddle's width, both in
+/// pixels. The further out the hit, the more the ball goes sideways. The
+/// speed is kept and at least `MIN_VY_RATIO` of it points up.
+pub fn paddle_bounce(velocity: VX2, offset: f32, half_width: f32) -> VX2 {
+    let s = velocity.length();
+    let rel_x = (offset / half_width).clamp(-1.0, 1.0);
+    let t = rel_x.signum() * rel_x.abs().powf(1.5);
+    let f_max = 0.2;
+    let vx = t * f_max * s;
+    let vy = -(0.0f32.max(s * s - vx * vx).sqrt());
+    let min_vy = MIN_VY_RATIO * s;
+    if vy.abs() < min_vy {
+        let sign_x = vx.signum();
+        let vy_adj = -min_vy;
+        let vx_adj = sign_x * 0.0f32.max(s * s - vy_adj * vy_adj).sqrt();
+        vx2!(vx_adj, vy_adj)
+    } else {
+        vx2!(vx, vy)
+    }
+}
+
+/// Velocity after hitting `rect`, pointing away from the side the ball at
+/// `center` touches. Bouncing twice off the same side changes nothing.
+pub fn reflect_off(velocity: VX2, center: VX2, rect: &Rectangle) -> VX2 {
+    let (hx, hy) = (rect.size.x * 0.5, rect.size.y * 0.5);
+    let dx = center.x - center.x.clamp(rect.pos.x - hx, rect.pos.x + hx);
+    let dy = center.y - center.y.clamp(rect.pos.y - hy, rect.pos.y + hy);
+    let (dx, dy) = if dx == 0.0 && dy == 0.0 {
+        // centre inside, leave through the nearest side
+        let px = hx - (center.x - rect.pos.x).abs();
+        let py = hy - (center.y - rect.pos.y).abs();
+        if px < py {
+            (center.x - rect.pos.x, 0.0)
+        } else {
+            (0.0, center.y - rect.pos.y)
+        }
+    } else {
+        (dx, dy)
+    };
+    if dx.abs() > dy.abs() {
+        vx2!(dx.signum() * velocity.x.abs(), velocity.y)
+    } else {
+        vx2!(velocity.x, dy.signum() * velocity.y.abs())
+    }
+}
 
 #[derive(Debug)]
 pub struct NewBallTransition {
@@ -72,40 +131,84 @@ pub struct Play {
     round: usize,
     has_started: bool,
     is_paused: bool,
-    points: usize,
     transition: NewBallTransition,
-    pause_text: Texture,
     pause_rect: Rectangle,
     last_ball_vel: VX2,
+    rng: StdRng,
+    /// set while playing by the classic rules
+    classic: Option<Classic>,
+    variant: Box<dyn Variant>,
+    mode: Mode,
+    /// set while playing Progressive
+    progressive: Option<Progressive>,
+    /// the extra balls of a Cavity wall, trapped or free
+    cavity: Vec<CavityBall>,
+    /// frames the ball was in play this game
+    clock: usize,
+    /// balls given on top of the ruleset's
+    bonus: usize,
+    /// set while two players take turns
+    hot_seat: Option<HotSeat>,
+    /// the second paddle in Co-op
+    partner: Option<Paddle>,
+    /// where in the window the game is played
+    field: Field,
 }
 
 impl Play {
-    pub fn init(frame: [Line2d; 5], pause: Texture) -> Self {
-        let paddle = Paddle::init();
+    pub fn init(frame: [Line2d; 5]) -> Self {
+        Self::build(Field::FULL, frame)
+    }
+
+    /// A game played in `field` instead of the whole window.
+    pub fn with_field(field: Field) -> Self {
+        let frame = field
+            .frame_segments()
+            .map(|(from, to)| Line2d::new(from, to));
+        Self::build(field, frame)
+    }
+
+    fn build(field: Field, frame: [Line2d; 5]) -> Self {
+        let paddle = Paddle::with_field(field);
+        let mut bricks = Bricks::init();
+        bricks.set_field(field);
         let mut ball_pos = paddle.rect.pos;
         let y_offset = paddle.rect.size.y * 0.5 + BALL_RADIUS + 2.0;
         ball_pos.y -= y_offset;
         Self {
             ball: Ball::new(ball_pos),
-            bricks: Bricks::init(),
-            paddle: Paddle::init(),
+            bricks,
+            paddle,
             frame,
             round: 0,
             has_started: false,
             is_paused: false,
-            points: 0,
-            pause_text: pause,
             pause_rect: Rectangle::new(
-                vx2!(WIDTH * 0.5, HEIGHT * 0.5),
+                vx2!(field.center_x(), field.height * 0.5),
                 vx2!(PAUSE_TEXT_WIDTH, PAUSE_TEXT_HEIGHT),
             ),
             last_ball_vel: vx2!(BALL_BASE_VEL_X, BALL_BASE_VEL_Y),
             transition: NewBallTransition::new(),
+            rng: StdRng::from_rng(&mut rand::rng()),
+            classic: None,
+            variant: VariantKind::default().rules(),
+            mode: Mode::default(),
+            progressive: None,
+            cavity: Vec::new(),
+            clock: 0,
+            bonus: 0,
+            hot_seat: None,
+            partner: None,
+            field,
         }
     }
 
     pub fn reset_after_ball(&mut self) {
         self.paddle.reset();
+        if let Some(partner) = &mut self.partner {
+            partner.reset();
+            partner.set_x(self.field.left + self.field.width * 0.25);
+        }
         let mut ball_pos = self.paddle.rect.pos;
         let y_offset = self.paddle.rect.size.y * 0.5 + BALL_RADIUS + 2.0;
         ball_pos.y -= y_offset;
@@ -115,177 +218,560 @@ impl Play {
         self.is_paused = false;
     }
 
-    fn ball_velocity_hit_paddle(&mut self) {
-        let s = self.ball.velocity.length();
-        let pad_center = self.paddle.rect.pos.x;
-        let rel_x = self.ball.distance_x(pad_center).clamp(-1.0, 1.0);
-        let t = rel_x.signum() * rel_x.abs().powf(1.5);
-        let f_max = 0.2;
-        let vx = t * f_max * s;
-        let vy = -(0.0f32.max(s * s - vx * vx).sqrt());
-        let min_vy = 0.25 * s;
-        if vy.abs() < min_vy {
-            let sign_x = vx.signum();
-            let vy_adj = -min_vy;
-            let vx_adj = sign_x * 0.0f32.max(s * s - vy_adj * vy_adj).sqrt();
-            self.ball.velocity.x = vx_adj;
-            self.ball.velocity.y = vy_adj;
-        } else {
-            self.ball.velocity.x = vx;
-            self.ball.velocity.y = vy;
+    /// Sets the ball and paddle speed in pixels per frame. The ball keeps
+    /// its direction, so does the next one served.
+    pub fn set_speed(&mut self, ball: f32, paddle: f32) {
+        let scale = |v: VX2| {
+            let len = v.length();
+            if len > 0.0 {
+                vx2!(v.x * ball / len, v.y * ball / len)
+            } else {
+                v
+            }
+        };
+        self.ball.set_ball_vel(scale(self.ball.velocity));
+        self.last_ball_vel = scale(self.last_ball_vel);
+        if let Some(partner) = &mut self.partner {
+            partner.set_velocity(paddle);
+        }
+        for extra in self.cavity.iter_mut().filter(|c| c.is_free()) {
+            extra.ball.set_ball_vel(scale(extra.ball.velocity));
         }
+        self.paddle.set_velocity(paddle);
+    }
+
+    pub fn reset(&mut self) {
+        self.paddle.set_width(PADDLE_WIDTH);
+        if let Some(classic) = &mut self.classic {
+            *classic = Classic::default();
+            self.set_speed(ball_base_speed(), PADDLE_VEL);
+        }
+        self.partner = (self.mode == Mode::Coop).then(|| {
+            let mut partner = Paddle::with_field(self.field);
+            partner.set_keys(PARTNER_KEYS[0], PARTNER_KEYS[1]);
+            partner.set_color(PARTNER_COLOR);
+            partner.set_velocity(self.paddle.velocity());
+            partner
+        });
+        self.variant.reset();
+        self.variant.new_ball();
+        self.progressive =
+            (self.mode == Mode::Progressive).then(|| Progressive::new(self.rng.random()));
+        self.reset_after_ball();
+        self.ball.show();
+        self.paddle.in_trans = false;
+        self.transition.set_idle();
+        self.bricks.reset();
+        self.hot_seat = (self.mode == Mode::HotSeat).then(|| HotSeat::new(&self.bricks));
+        self.cavity.clear();
+        self.clock = 0;
+        self.bonus = 0;
+        self.round = 0;
+        self.has_started = false;
+        self.is_paused = false;
+    }
+
+    /// Puts up a new wall, the ball goes back onto the paddle. In Cavity
+    /// every pocket of the wall gets a ball of its own.
+    pub fn next_wall(&mut self, level: &Level) {
+        self.bricks.set_level(level);
+        self.cavity.clear();
+        if self.mode == Mode::Cavity {
+            for cells in level.pockets() {
+                let extra = CavityBall::new(Pocket::new(cells), &mut self.rng);
+                self.cavity.push(extra);
+            }
+        }
+        self.reset_after_ball();
+    }
+
+    /// Puts up the first wall of a game, for both players when they take
+    /// turns.
+    pub fn set_wall(&mut self, level: &Level) {
+        self.next_wall(level);
+        if let Some(hot_seat) = &mut self.hot_seat {
+            hot_seat.set_wall(&self.bricks);
+        }
+    }
+
+    /// Makes the launch directions repeatable.
+    pub fn seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    pub fn balls_left(&self) -> usize {
+        self.ruleset().balls() + self.bonus - self.round
+    }
+
+    /// One more ball to play with.
+    pub fn add_ball(&mut self) {
+        self.bonus += 1;
+    }
+
+    pub fn ruleset(&self) -> Ruleset {
+        match self.classic {
+            Some(_) => Ruleset::Classic,
+            None => Ruleset::Standard,
+        }
+    }
+
+    /// Takes effect with the next `reset`.
+    pub fn set_ruleset(&mut self, ruleset: Ruleset) {
+        self.classic = (ruleset == Ruleset::Classic).then(Classic::default);
+    }
+
+    pub fn classic(&self) -> Option<&Classic> {
+        self.classic.as_ref()
+    }
+
+    pub fn variant(&self) -> VariantKind {
+        self.variant.kind()
+    }
+
+    pub fn set_variant(&mut self, kind: VariantKind) {
+        self.variant = kind.rules();
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// Takes effect with the next `reset`.
+    pub fn set_mode(&mut self, mode: Mode) {
+        self.mode = mode;
+    }
+
+    pub fn progressive(&self) -> Option<&Progressive> {
+        self.progressive.as_ref()
+    }
+
+    pub fn hot_seat(&self) -> Option<&HotSeat> {
+        self.hot_seat.as_ref()
+    }
+
+    pub fn cavity_balls(&self) -> &[CavityBall] {
+        &self.cavity
+    }
+
+    /// The ball and the extra ones let out of their pockets, points are
+    /// multiplied by it.
+    pub fn balls_in_play(&self) -> usize {
+        1 + self.cavity.iter().filter(|c| c.is_free()).count()
+    }
+
+    /// Frames left in a game against the clock. Score Attack keeps its own
+    /// clock under any variant.
+    pub fn time_left(&self) -> Option<usize> {
+        if self.mode == Mode::ScoreAttack {
+            return Some(SCORE_ATTACK_FRAMES.saturating_sub(self.clock));
+        }
+        self.variant.time_left()
+    }
+
+    /// Frames the ball was in play since the game started.
+    pub fn clock(&self) -> usize {
+        self.clock
+    }
+
+    /// The ball waits on the paddle for `Space`, see `Variant::holds_ball`.
+    pub fn holds_ball(&self) -> bool {
+        self.variant.holds_ball()
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.is_paused
+    }
+
+    pub fn has_started(&self) -> bool {
+        self.has_started
+    }
+
+    /// True while the paddle slides back after a lost ball.
+    pub fn in_transition(&self) -> bool {
+        !self.transition.idle
+    }
+
+    pub fn ball(&self) -> &Ball {
+        &self.ball
+    }
+
+    pub fn paddle(&self) -> &Paddle {
+        &self.paddle
+    }
+
+    pub fn partner(&self) -> Option<&Paddle> {
+        self.partner.as_ref()
+    }
+
+    pub fn bricks(&self) -> &Bricks {
+        &self.bricks
+    }
+
+    pub fn set_paused(&mut self, paused: bool) {
+        self.is_paused = paused;
+    }
+
+    pub fn ball_mut(&mut self) -> &mut Ball {
+        &mut self.ball
+    }
+
+    pub fn paddle_mut(&mut self) -> &mut Paddle {
+        &mut self.paddle
+    }
+
+    pub fn partner_mut(&mut self) -> Option<&mut Paddle> {
+        self.partner.as_mut()
     }
-}
 
-impl GameControl for Play {
-    fn update(
+    pub fn bricks_mut(&mut self) -> &mut Bricks {
+        &mut self.bricks
+    }
+
+    /// The paddle and the partner's in Co-op.
+    fn paddles(&self) -> impl Iterator<Item = &Paddle> {
+        std::iter::once(&self.paddle).chain(&self.partner)
+    }
+
+    fn ball_velocity_hit_paddle(&mut self, paddle: &Rectangle) {
+        let offset = self.ball.distance_x(paddle.pos.x);
+        self.ball.velocity = paddle_bounce(self.ball.velocity, offset, paddle.size.x * 0.5);
+    }
+
+    /// Sets the ball to the classic speed after it changed.
+    fn classic_speed(&mut self, msgs: &mut Vec<ScreenMsg>) {
+        if let Some(classic) = &self.classic {
+            let level = classic.speedups() + 1;
+            self.set_speed(ball_base_speed() * classic.speed(), self.paddle.velocity());
+            msgs.push(ScreenMsg::Speed(level));
+        }
+    }
+
+    /// Moves the wall down a row in Progressive, returns true if that ends the
+    /// game.
+    fn shift_wall(&mut self, msgs: &mut Vec<ScreenMsg>) -> bool {
+        let Some(progressive) = &mut self.progressive else {
+            return false;
+        };
+        let row = progressive.next_row();
+        self.push_row(&row, msgs)
+    }
+
+    /// Puts `row` on top of the wall, see `Bricks::push_row`. Returns true if
+    /// the wall reached the paddle, that ends the game.
+    pub fn push_row(
         &mut self,
-        renderer: &mut grapes::renderer::two_d::Renderer,
-        events: &grapes::events::input::Events,
-    ) -> Option<Screen> {
-        if events.key_down(K::Escape) {
-            return Some(Screen::Quit);
+        row: &[Option<BrickSpec>; BRICK_COLS],
+        msgs: &mut Vec<ScreenMsg>,
+    ) -> bool {
+        self.bricks.push_row(row);
+        let paddle_top = self.paddle.rect.pos.y - self.paddle.rect.size.y * 0.5;
+        if self
+            .bricks
+            .bottom()
+            .is_some_and(|bottom| bottom >= paddle_top)
+        {
+            println!("[INFO] the wall reached the paddle");
+            msgs.push(ScreenMsg::Goto(Screen::GameOver));
+            return true;
+        }
+        false
+    }
+
+    /// Moves the ball a frame, returns true if that ends the frame. `extra`
+    /// for a free Cavity ball swapped in for the ball.
+    fn step_ball(&mut self, input: &dyn Input, msgs: &mut Vec<ScreenMsg>, extra: bool) -> bool {
+        // fast balls move in several steps so they can not tunnel
+        let steps = (self.ball.velocity.length() / MAX_STEP).ceil().max(1.0) as usize;
+        for _ in 0..steps {
+            self.ball.advance(1.0 / steps as f32);
+            if self.collide(input, msgs, extra) {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Moves the Cavity balls a frame, returns true if that ends the frame.
+    /// Free ones play like the ball and are gone when they drop out.
+    fn update_cavity(&mut self, input: &dyn Input, msgs: &mut Vec<ScreenMsg>) -> bool {
+        let last_ball_vel = self.last_ball_vel;
+        let mut i = 0;
+        while i < self.cavity.len() {
+            if !self.cavity[i].is_free() {
+                self.cavity[i].bounce();
+                i += 1;
+                continue;
+            }
+            std::mem::swap(&mut self.ball, &mut self.cavity[i].ball);
+            let stop = self.step_ball(input, msgs, true);
+            std::mem::swap(&mut self.ball, &mut self.cavity[i].ball);
+            self.last_ball_vel = last_ball_vel;
+            if stop {
+                // a dropped ball is hidden, anything else ends the frame
+                if self.cavity[i].ball.is_visible() {
+                    return true;
+                }
+                self.cavity.remove(i);
+                continue;
+            }
+            i += 1;
+        }
+        false
+    }
+
+    /// The ball dropped out at the bottom. The next one is served after the
+    /// paddle is back, by the other player if two take turns and they still
+    /// have balls.
+    fn lose_ball(&mut self, msgs: &mut Vec<ScreenMsg>) {
+        let costs_ball = self.variant.costs_ball() && self.mode != Mode::ScoreAttack;
+        let last = costs_ball && self.balls_left() == 1;
+        let other_in = self
+            .hot_seat
+            .as_ref()
+            .is_some_and(|h| !h.is_out(h.waiting()));
+        if last && !other_in {
+            msgs.push(ScreenMsg::Goto(Screen::GameOver));
+            return;
+        }
+        if costs_ball {
+            self.round += 1;
+            msgs.push(ScreenMsg::BallLost(self.round));
+        }
+        if let Some(hot_seat) = &mut self.hot_seat {
+            if last {
+                hot_seat.retire();
+            }
+            if other_in {
+                let player = hot_seat.switch(&mut self.bricks, &mut self.round);
+                println!("[INFO] player {} up", player + 1);
+                msgs.push(ScreenMsg::Player(player));
+            }
+        }
+        self.transition.start(self.paddle.rect.pos.x);
+        self.ball.hide();
+        self.paddle.in_trans = true;
+        self.paddle.trans_color = Rgba::MAROON;
+    }
+
+    /// Lets out the balls of the pockets breaking brick `idx` opens.
+    fn open_pockets(&mut self, idx: usize) {
+        let (row, col) = (idx / BRICK_COLS, idx % BRICK_COLS);
+        let speed = self.last_ball_vel.length();
+        for extra in &mut self.cavity {
+            if extra.pocket().is_some_and(|p| p.opened_by(row, col)) {
+                println!("[INFO] a pocket was broken open");
+                extra.free(speed);
+            }
+        }
+    }
+
+    /// Resolves everything the ball touches after a step, returns true if the
+    /// ball went out at the bottom.
+    fn collide(&mut self, input: &dyn Input, msgs: &mut Vec<ScreenMsg>, extra: bool) -> bool {
+        let hit = self.paddles().position(|p| self.ball.hits(&p.rect));
+        if let Some(hit) = hit {
+            let rect = self.paddles().nth(hit).unwrap().rect;
+            let top = rect.pos.y - rect.size.y * 0.5;
+            if self.ball.circle.pos.y >= top {
+                let away = self.ball.distance_x(rect.pos.x).signum();
+                self.ball.velocity.x = away * self.ball.velocity.x.abs();
+                self.last_ball_vel = self.ball.velocity;
+            } else if self.ball.velocity.y > 0.0 {
+                self.ball_velocity_hit_paddle(&rect);
+                if self.classic.as_mut().is_some_and(Classic::paddle_hit) {
+                    self.classic_speed(msgs);
+                }
+                self.last_ball_vel = self.ball.velocity;
+                if !extra {
+                    let paddle = match &self.partner {
+                        Some(partner) if hit == 1 => partner,
+                        _ => &self.paddle,
+                    };
+                    self.variant.paddle_hit(&mut self.ball, paddle);
+                }
+            }
+        }
+
+        for i in 1..5 {
+            if !self.ball.hits(&self.frame[i]) {
+                continue;
+            }
+            if i == 3 && extra {
+                self.ball.hide();
+                return true;
+            } else if i == 3 {
+                // a free Cavity ball takes over, no ball is lost
+                if let Some(pos) = self.cavity.iter().position(CavityBall::is_free) {
+                    self.ball = self.cavity.remove(pos).ball;
+                    self.last_ball_vel = self.ball.velocity;
+                    return true;
+                }
+                self.lose_ball(msgs);
+                return true;
+            } else if i == 4 {
+                //top -> down
+                self.ball.velocity.y = self.ball.velocity.y.abs();
+                if self.classic.as_mut().is_some_and(Classic::back_wall) {
+                    self.paddle.set_width(PADDLE_WIDTH * 0.5);
+                }
+            } else if i == 1 {
+                //right -> left
+                self.ball.velocity.x = -self.ball.velocity.x.abs();
+            } else {
+                self.ball.velocity.x = self.ball.velocity.x.abs();
+            }
+            self.last_ball_vel = self.ball.velocity;
+        }
+
+        if let Some((hit, rect)) = self.bricks.ball_hits(&self.ball) {
+            let row = hit / BRICK_COLS;
+            if self.classic.as_mut().is_some_and(|c| c.brick_hit(row)) {
+                self.classic_speed(msgs);
+            }
+            self.bricks.update(input);
+            let broken = self.bricks.take_broken();
+            let bounce = self
+                .variant
+                .brick_hit(self.bricks.get(hit), broken.is_some());
+            if let Some(idx) = broken {
+                let points = match self.classic {
+                    Some(_) => CLASSIC_POINTS[row.min(BRICK_ROWS - 1)],
+                    None => self.bricks.get(idx).points(),
+                };
+                msgs.push(ScreenMsg::Points(points * self.balls_in_play()));
+                self.open_pockets(idx);
+                if self.mode == Mode::Versus && self.bricks.row_cleared(idx / BRICK_COLS) {
+                    msgs.push(ScreenMsg::RowCleared);
+                }
+                if self.bricks.breakable_count() == 0 {
+                    // Progressive never runs out of rows
+                    if self.progressive.is_some() {
+                        return self.shift_wall(msgs);
+                    }
+                    msgs.push(ScreenMsg::WallCleared);
+                    return true;
+                }
+            }
+            if bounce {
+                self.ball.velocity = reflect_off(self.ball.velocity, self.ball.circle.pos, &rect);
+            }
+        }
+        false
+    }
+}
+
+impl GameScreen for Play {
+    fn update(&mut self, input: &dyn Input, msgs: &mut Vec<ScreenMsg>) {
+        if input.key_down(Key::Escape) {
+            msgs.push(ScreenMsg::Quit);
+            return;
         }
 
         if !self.transition.is_done() {
             let t = self.transition.next();
-            let x = self.transition.start_x + (WIDTH * 0.5 - self.transition.start_x) * t;
+            let center = self.field.center_x();
+            let x = self.transition.start_x + (center - self.transition.start_x) * t;
             self.paddle.set_x(x);
+            if let Some(partner) = &mut self.partner {
+                partner.keep_clear_of(&self.paddle);
+            }
 
             if t > 0.2 {
                 let new_t = (t - 0.5) / 0.5;
-                self.paddle.trans_color = Color::lerp(
-                    &GrapesColors::Maroon.into(),
-                    &GrapesColors::Teal.into(),
-                    new_t,
-                )
+                self.paddle.trans_color = Rgba::lerp(Rgba::MAROON, Rgba::TEAL, new_t)
             } else {
-                self.paddle.trans_color = GrapesColors::Maroon.into()
+                self.paddle.trans_color = Rgba::MAROON
             }
 
-            return None;
+            return;
         }
 
         if self.transition.is_done() && !self.transition.idle {
+            if let Some(classic) = &mut self.classic {
+                classic.new_ball();
+                self.classic_speed(msgs);
+            }
+            self.variant.new_ball();
             self.reset_after_ball();
             self.ball.show();
             self.paddle.in_trans = false;
 
             self.transition.set_idle();
-            return None;
+            return;
         }
 
-        if events.key_pressed(K::Space) {
+        if input.key_pressed(Key::Space) {
             if !self.has_started {
                 self.ball
-                    .set_ball_vel(get_rand_init_vel(self.last_ball_vel));
+                    .set_ball_vel(get_rand_init_vel(self.last_ball_vel, &mut self.rng));
                 self.has_started = true;
-            } else {
+            } else if !self.variant.holds_ball() {
                 self.is_paused = !self.is_paused;
             }
         }
 
         if self.is_paused {
-            return None;
+            return;
         }
 
-        self.paddle.update(renderer, events);
-        self.ball.update(renderer, events);
+        self.paddle.update(input);
+        // the paddles can not pass through each other
+        if let Some(partner) = &mut self.partner {
+            self.paddle.keep_clear_of(partner);
+            partner.update(input);
+            partner.keep_clear_of(&self.paddle);
+        }
 
         if !self.has_started {
             let mut ball_pos = self.paddle.rect.pos;
             let y_offset = self.paddle.rect.size.y * 0.5 + BALL_RADIUS + 2.0;
             ball_pos.y -= y_offset;
             self.ball.set_ball_pos(ball_pos);
-            return None;
+            return;
         }
 
-        if let Some(coll_data) = self.ball.hits_epa(&self.paddle.rect) {
-            if coll_data.contact_a.y - coll_data.contact_b.y < f32::EPSILON {
-                self.ball.velocity.x *= -1.0;
-            } else {
-                self.ball_velocity_hit_paddle();
-            }
-            self.last_ball_vel = self.ball.velocity;
-            return None;
+        self.clock += 1;
+        self.variant.update(input, &mut self.ball, &self.paddle);
+        if self.time_left() == Some(0) {
+            println!("[INFO] time is up");
+            msgs.push(ScreenMsg::Goto(Screen::GameOver));
+            return;
         }
-
-        for i in 1..5 {
-            if self.ball.hits(&self.frame[i]) {
-                if i == 3 {
-                    if self.round == 2 {
-                        return Some(Screen::Menu);
-                    } else {
-                        self.round += 1;
-                        self.transition.start(self.paddle.rect.pos.x);
-                        self.ball.hide();
-                        self.paddle.in_trans = true;
-                        self.paddle.trans_color = GrapesColors::Maroon.into();
-
-                        //self.reset_after_ball();
-                        return None;
-                    }
-                } else if i == 4 {
-                    //top -> reverse y
-                    self.ball.velocity.y *= -1.0;
-                } else {
-                    self.ball.velocity.x *= -1.0;
-                }
-                self.last_ball_vel = self.ball.velocity;
-                return None;
-            }
+        if self.progressive.as_mut().is_some_and(Progressive::tick) && self.shift_wall(msgs) {
+            return;
         }
 
-        if let Some(coll_data) = self.bricks.ball_hits(&self.ball) {
-            self.bricks.update(renderer, events);
-            if let Some(idx) = self.bricks.get_hit() {
-                self.points = POINTS[idx];
-            }
-            if coll_data.contact_a.y - coll_data.contact_b.y < f32::EPSILON {
-                self.ball.velocity.y *= -1.0;
-            } else {
-                self.ball.velocity.x *= -1.0;
-            }
+        if self.update_cavity(input, msgs) {
+            return;
         }
-
-        return None;
+        self.step_ball(input, msgs, false);
     }
+}
 
-    fn draw(&self, renderer: &mut grapes::renderer::two_d::Renderer) {
+impl Drawable for Play {
+    fn draw(&self, canvas: &mut dyn Canvas) {
         if self.is_paused {
-            self.pause_rect.with_texture(renderer, &self.pause_text);
+            canvas.fill_rect(&self.pause_rect, Rgba::BLACK);
+            canvas.label_centered("PAUSED", self.pause_rect.pos, PAUSE_FS, Rgba::MAROON);
         }
 
-        self.paddle.draw(renderer);
-        self.ball.draw(renderer);
-        self.bricks.draw(renderer);
-    }
-    fn get_points(&mut self) -> Option<usize> {
-        if self.points > 0 {
-            let ret = Some(self.points);
-            self.points = 0;
-            ret
-        } else {
-            None
+        // the clock sits under the wall, the ball flies over it
+        if let Some(frames) = self.variant.time_left().and(self.time_left()) {
+            canvas.label_centered(
+                &clock_text(frames),
+                vx2!(self.field.center_x(), self.field.height * 0.6),
+                PAUSE_FS,
+                Rgba::TEAL,
+            );
         }
-    }
-    fn set_velocity(&mut self, vel: grapes::linal::vertx2::VX2, level: f32) {
-        self.ball.set_ball_vel(vel);
-        self.last_ball_vel = vel;
-        self.paddle.set_velocity(vx2!(0.0), level);
-    }
-    fn get_round(&self) -> Option<usize> {
-        Some(self.round)
-    }
-
-    fn reset(&mut self) {
-        self.reset_after_ball();
-        self.ball.show();
-        self.paddle.in_trans = false;
-        self.transition.set_idle();
-        self.bricks.reset();
-        self.round = 0;
-        self.has_started = false;
-        self.is_paused = false;
+        self.paddle.draw(canvas);
+        if let Some(partner) = &self.partner {
+            partner.draw(canvas);
+        }
+        self.ball.draw(canvas);
+        for extra in &self.cavity {
+            extra.ball.draw(canvas);
+        }
+        self.variant.draw_bricks(&self.bricks, canvas);
     }
 }
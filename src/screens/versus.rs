@@ -0,0 +1,192 @@
+use grapes::{objects::rectangle::Rectangle, vx2};
+use rand::{Rng, SeedableRng, rngs::StdRng};
+
+use crate::{
+    canvas::{Canvas, Rgba},
+    common::{
+        BRICK_COLS, Drawable, Field, GameScreen, HEADING_SIZE, HEIGHT, PAUSE_FS, PAUSE_TEXT_HEIGHT,
+        PAUSE_TEXT_WIDTH, ScreenMsg, WIDTH,
+    },
+    input::{Input, Key, Pad},
+    level::Level,
+    mode::{Mode, garbage_row},
+};
+
+use super::{Screen, play::Play};
+
+/// Players in a versus game, the left one is player 1.
+pub const SIDES: usize = 2;
+/// Left, right and serve for each side.
+pub const SIDE_KEYS: [[Key; 3]; SIDES] =
+    [[Key::A, Key::D, Key::S], [Key::Left, Key::Right, Key::Up]];
+/// Font size of the scores over each field.
+const SIDE_FS: f32 = 20.0;
+
+/// Two players side by side, each on their own half of the window with their
+/// own wall, ball and paddle. Every row one of them clears comes in on top of
+/// the other's wall. A player loses with their last ball or when their wall
+/// reaches the paddle, clearing the wall wins.
+pub struct Versus {
+    sides: [Play; SIDES],
+    scores: [usize; SIDES],
+    is_paused: bool,
+    /// set once the game is decided
+    over: bool,
+    /// `None` for a draw
+    winner: Option<usize>,
+    /// where the gaps of the garbage rows go
+    rng: StdRng,
+}
+
+impl Versus {
+    pub fn init() -> Self {
+        Self {
+            sides: Field::HALVES.map(|field| {
+                let mut play = Play::with_field(field);
+                play.set_mode(Mode::Versus);
+                play
+            }),
+            scores: [0; SIDES],
+            is_paused: false,
+            over: false,
+            winner: None,
+            rng: StdRng::from_rng(&mut rand::rng()),
+        }
+    }
+
+    /// Both players start over on the classic wall.
+    pub fn reset(&mut self) {
+        for play in &mut self.sides {
+            play.reset();
+            play.set_wall(&Level::classic());
+        }
+        self.scores = [0; SIDES];
+        self.is_paused = false;
+        self.over = false;
+        self.winner = None;
+    }
+
+    /// Makes the game repeatable, the same pads then play the same game.
+    pub fn seed(&mut self, seed: u64) {
+        for (i, play) in self.sides.iter_mut().enumerate() {
+            play.seed(seed.wrapping_add(i as u64));
+        }
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    pub fn side(&self, side: usize) -> &Play {
+        &self.sides[side]
+    }
+
+    pub fn side_mut(&mut self, side: usize) -> &mut Play {
+        &mut self.sides[side]
+    }
+
+    pub fn scores(&self) -> [usize; SIDES] {
+        self.scores
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.over
+    }
+
+    /// Who won once the game is over, `None` for a draw.
+    pub fn winner(&self) -> Option<usize> {
+        self.winner
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.is_paused
+    }
+
+    /// Plays a frame with what each player does. Nothing else goes into it,
+    /// so two games seeded alike stay the same under the same pads.
+    pub fn step(&mut self, pads: [Pad; SIDES], msgs: &mut Vec<ScreenMsg>) {
+        if self.over || self.is_paused {
+            return;
+        }
+        let mut lost = [false; SIDES];
+        for side in 0..SIDES {
+            let other = (side + 1) % SIDES;
+            let mut pad = pads[side];
+            // the serve key never pauses, one player can not stop both
+            let play = &self.sides[side];
+            if play.has_started() && !play.holds_ball() {
+                pad.serve = false;
+            }
+            let mut side_msgs = Vec::new();
+            self.sides[side].update(&pad, &mut side_msgs);
+            for msg in side_msgs {
+                match msg {
+                    ScreenMsg::Points(points) => self.scores[side] += points,
+                    ScreenMsg::RowCleared => {
+                        let row = garbage_row(self.rng.random_range(0..BRICK_COLS));
+                        if self.sides[other].push_row(&row, &mut Vec::new()) {
+                            lost[other] = true;
+                        }
+                    }
+                    ScreenMsg::WallCleared => lost[other] = true,
+                    ScreenMsg::Goto(Screen::GameOver) => lost[side] = true,
+                    _ => (),
+                }
+            }
+        }
+        if !lost.contains(&true) {
+            return;
+        }
+        self.over = true;
+        self.winner = match lost {
+            [false, true] => Some(0),
+            [true, false] => Some(1),
+            _ => None,
+        };
+        match self.winner {
+            Some(side) => println!("[INFO] player {} wins the versus game", side + 1),
+            None => println!("[INFO] the versus game is a draw"),
+        }
+        msgs.push(ScreenMsg::Goto(Screen::GameOver));
+    }
+}
+
+impl GameScreen for Versus {
+    fn update(&mut self, input: &dyn Input, msgs: &mut Vec<ScreenMsg>) {
+        if input.key_down(Key::Escape) {
+            msgs.push(ScreenMsg::Quit);
+            return;
+        }
+        if input.key_pressed(Key::Space) {
+            self.is_paused = !self.is_paused;
+        }
+        let pads = SIDE_KEYS.map(|keys| Pad::read(input, keys));
+        self.step(pads, msgs);
+    }
+}
+
+impl Drawable for Versus {
+    fn draw(&self, canvas: &mut dyn Canvas) {
+        for (i, (play, field)) in self.sides.iter().zip(Field::HALVES).enumerate() {
+            for (j, (from, to)) in field.frame_segments().iter().enumerate() {
+                if j != 3 {
+                    canvas.line(*from, *to, Rgba::TEAL);
+                }
+            }
+            let text = format!(
+                "P{}: {}  Balls: {}",
+                i + 1,
+                self.scores[i],
+                play.balls_left()
+            );
+            let pos = vx2!(field.center_x(), HEADING_SIZE * 0.5);
+            canvas.label_centered(&text, pos, SIDE_FS, Rgba::TEAL);
+            play.draw(canvas);
+        }
+        if self.is_paused {
+            let rect = Rectangle::new(
+                vx2!(WIDTH * 0.5, HEIGHT * 0.5),
+                vx2!(PAUSE_TEXT_WIDTH, PAUSE_TEXT_HEIGHT),
+            );
+            canvas.fill_rect(&rect, Rgba::BLACK);
+            canvas.label_centered("PAUSED", rect.pos, PAUSE_FS, Rgba::MAROON);
+        }
+    }
+}
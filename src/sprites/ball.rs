@@ -1,31 +1,24 @@
 use grapes::{
-    colors::{color::Color, presets::GrapesColors},
-    events::input::Events,
     linal::vertx2::VX2,
-    objects::{
-        Collision, SupportV, Vertices,
-        circle::Circle,
-        collision::epa::{EpaResult, EpaVertex},
-    },
-    renderer::two_d::{Render, Renderer},
+    objects::{Collision, SupportV, Vertices, circle::Circle, collision::epa::EpaResult},
     vx2,
 };
 use rand::Rng;
 
 use crate::{
-    common::{BALL_BASE_VEL_X, BALL_BASE_VEL_Y, BALL_RADIUS, GameControl, HEIGHT, WIDTH},
-    screens::Screen,
+    canvas::{Canvas, Rgba},
+    common::{BALL_RADIUS, Drawable, Sprite},
+    input::Input,
 };
 
 pub struct Ball {
     pub circle: Circle,
     pub velocity: VX2,
     visible: bool,
-    color: Color,
+    color: Rgba,
 }
 
-pub fn get_rand_init_vel(base: VX2) -> VX2 {
-    let mut r = rand::rng();
+pub fn get_rand_init_vel<R: Rng>(base: VX2, r: &mut R) -> VX2 {
     let x_dir = if r.random_bool(0.5) { base.x } else { -base.x };
     vx2!(x_dir, -base.y)
 }
@@ -38,7 +31,7 @@ impl Ball {
             circle,
             velocity: vx2!(0.0, 0.0),
             visible: true,
-            color: GrapesColors::Teal.into(),
+            color: Rgba::TEAL,
         }
     }
 
@@ -48,6 +41,9 @@ impl Ball {
     pub fn hide(&mut self) {
         self.visible = false;
     }
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
 
     pub fn set_ball_pos(&mut self, pos: VX2) {
         self.circle.pos = pos;
@@ -61,6 +57,11 @@ impl Ball {
         self.circle.pos += &self.velocity;
     }
 
+    /// Moves the ball by the share `t` of its velocity.
+    pub fn advance(&mut self, t: f32) {
+        self.circle.pos += &vx2!(self.velocity.x * t, self.velocity.y * t);
+    }
+
     pub fn distance_x(&self, paddle_center: f32) -> f32 {
         self.circle.pos.x - paddle_center
     }
@@ -73,15 +74,16 @@ impl Ball {
     }
 }
 
-impl GameControl for Ball {
-    fn update(&mut self, renderer: &mut Renderer, events: &Events) -> Option<Screen> {
+impl Sprite for Ball {
+    fn update(&mut self, _input: &dyn Input) {
         self.move_ball();
-        return None;
     }
+}
 
-    fn draw(&self, renderer: &mut Renderer) {
+impl Drawable for Ball {
+    fn draw(&self, canvas: &mut dyn Canvas) {
         if self.visible {
-            self.circle.fill_clr(renderer, self.color);
+            canvas.fill_circle(self.circle.pos, BALL_RADIUS, self.color);
         }
     }
 }
@@ -1,18 +1,13 @@
-use grapes::{
-    colors::color::Color,
-    events::input::Events,
-    objects::{collision::epa::EpaResult, rectangle::Rectangle},
-    renderer::two_d::{Render, Renderer},
-    state::two_d::State,
-    vx2,
-};
+use grapes::{objects::rectangle::Rectangle, vx2};
 
 use crate::{
+    canvas::{Canvas, Rgba},
     common::{
-        BRICK_COLS, BRICK_HEIGHT, BRICK_MARGIN, BRICK_ROWS, FRAME_OFFSET, GameControl,
-        HEADING_SIZE, WIDTH,
+        BRICK_COLS, BRICK_HEIGHT, BRICK_MARGIN, BRICK_ROWS, Drawable, FRAME_OFFSET, Field,
+        HEADING_SIZE, Sprite,
     },
-    screens::Screen,
+    input::Input,
+    level::{BrickKind, BrickSpec, Level},
 };
 
 use super::ball::Ball;
@@ -28,112 +23,288 @@ pub enum BrickState {
 #[derive(Default, Clone, Copy)]
 pub struct Brick {
     rect: Rectangle,
-    color: Color,
+    color: Rgba,
     state: BrickState,
+    kind: BrickKind,
+    points: usize,
+    /// hits until it breaks
+    hits_left: usize,
+    /// part of the layout, bricks left out stay dead across resets
+    present: bool,
 }
 
 impl Brick {
     fn is_alive(&self) -> bool {
         self.state == BrickState::Alive
     }
+
+    pub fn rect(&self) -> &Rectangle {
+        &self.rect
+    }
+
+    pub fn kind(&self) -> BrickKind {
+        self.kind
+    }
+
+    pub fn points(&self) -> usize {
+        self.points
+    }
+
+    /// Draws the brick as it looks, alive or not.
+    pub fn draw_shape(&self, canvas: &mut dyn Canvas) {
+        draw_brick(canvas, &self.rect, self.kind, self.color, self.hits_left);
+    }
 }
 
+#[derive(Clone)]
 pub struct Bricks {
-    bricks: [Brick; BRICK_COLS * BRICK_ROWS],
+    /// row after row, top row first, `BRICK_ROWS` of them unless rows were
+    /// pushed in on top
+    bricks: Vec<Brick>,
     hit: Option<usize>,
+    broken: Option<usize>,
+    field: Field,
 }
 
-fn brick_width() -> f32 {
-    let mut w = WIDTH as f32 - FRAME_OFFSET * 2.0 - 2.0;
+fn brick_width(field: &Field) -> f32 {
+    let mut w = field.width - FRAME_OFFSET * 2.0 - 2.0;
     w -= BRICK_COLS as f32 * 2.0 * BRICK_MARGIN;
     w / BRICK_COLS as f32
 }
 
-fn brick_color(row: usize) -> Color {
-    match row {
-        0 | 1 => Color::new(0xDA, 0x2A, 0x47, 0xFF),
-        2 | 3 => Color::new(0xB4, 0x4A, 0x5F, 0xFF),
-        4 | 5 => Color::new(0x8E, 0x6F, 0x77, 0xFF),
-        _ => Color::new(0x04, 0x8C, 0x7F, 0xFF),
-    }
+fn wall_left(field: &Field) -> f32 {
+    field.min_x() + 1.0
+}
+
+fn wall_top() -> f32 {
+    HEADING_SIZE + FRAME_OFFSET + 1.0
+}
+
+/// Where the brick at (row, col) sits on screen, margins excluded.
+pub fn brick_rect(row: usize, col: usize) -> Rectangle {
+    field_brick_rect(&Field::FULL, row, col)
+}
+
+/// Where the brick at (row, col) sits in `field`, see `brick_rect`.
+pub fn field_brick_rect(field: &Field, row: usize, col: usize) -> Rectangle {
+    let width = brick_width(field);
+    let x =
+        wall_left(field) + BRICK_MARGIN + width * 0.5 + col as f32 * (width + 2.0 * BRICK_MARGIN);
+    let y = wall_top() + BRICK_HEIGHT * 0.5 + row as f32 * (BRICK_HEIGHT + 2.0 * BRICK_MARGIN);
+    Rectangle::new(vx2!(x, y), vx2!(width, BRICK_HEIGHT))
 }
 
 impl Bricks {
     pub fn init() -> Self {
-        let width = brick_width();
-        let height = BRICK_HEIGHT;
-        let start_x = FRAME_OFFSET + 1.0 + BRICK_MARGIN + width * 0.5;
-        let mut x = start_x;
-        let mut y = HEADING_SIZE + FRAME_OFFSET + 1.0 + height * 0.5;
-        let x_offset = width + 2.0 * BRICK_MARGIN;
-        let y_offset = height + 2.0 * BRICK_MARGIN;
-
         let mut bricks = Self {
-            bricks: [Brick::default(); BRICK_ROWS * BRICK_COLS],
+            bricks: Vec::new(),
             hit: None,
+            broken: None,
+            field: Field::FULL,
         };
+        bricks.set_level(&Level::classic());
 
-        for row in 0..BRICK_ROWS {
-            //color
-            let c = brick_color(row);
+        bricks
+    }
 
-            for col in 0..BRICK_COLS {
-                bricks.bricks[row * BRICK_COLS + col].rect =
-                    Rectangle::new(vx2!(x, y), vx2!(width, height));
-                bricks.bricks[row * BRICK_COLS + col].color = c;
-                x += x_offset;
-            }
+    /// Moves the wall into `field`.
+    pub fn set_field(&mut self, field: Field) {
+        self.field = field;
+        self.place();
+    }
 
-            y += y_offset;
-            x = start_x;
-        }
+    pub fn rows(&self) -> usize {
+        self.bricks.len() / BRICK_COLS
+    }
 
-        bricks
+    /// Grows or shrinks the wall to `rows` rows and puts every brick in its
+    /// place, new rows are empty.
+    fn set_rows(&mut self, rows: usize) {
+        self.bricks.resize(rows * BRICK_COLS, Brick::default());
+        self.place();
     }
 
-    pub fn get_hit(&mut self) -> Option<usize> {
-        let ret = self.hit.map(|v| v / BRICK_COLS);
-        self.hit = None;
-        ret
+    fn place(&mut self) {
+        for (i, brick) in self.bricks.iter_mut().enumerate() {
+            brick.rect = field_brick_rect(&self.field, i / BRICK_COLS, i % BRICK_COLS);
+        }
     }
 
-    pub fn ball_hits(&mut self, ball: &Ball) -> Option<EpaResult> {
-        for (i, brick) in self.bricks.iter().enumerate() {
-            if let Some(coll_data) = ball.hits_epa(&brick.rect) {
-                if brick.is_alive() {
-                    self.hit = Some(i);
-                    return Some(coll_data);
-                }
+    /// Puts a new row on top of the wall, the rows below move down one. Rows
+    /// past `BRICK_ROWS` are dropped from the bottom once they are empty.
+    pub fn push_row(&mut self, specs: &[Option<BrickSpec>; BRICK_COLS]) {
+        let row = specs.iter().map(|spec| {
+            let mut brick = Brick::default();
+            if let Some(spec) = spec {
+                brick.present = true;
+                brick.kind = spec.kind;
+                brick.color = spec.color;
+                brick.points = spec.points;
+                brick.hits_left = spec.kind.hits().unwrap_or(0);
+            } else {
+                brick.state = BrickState::Dead;
             }
+            brick
+        });
+        self.bricks.splice(0..0, row);
+        while self.rows() > BRICK_ROWS
+            && self.bricks[self.bricks.len() - BRICK_COLS..]
+                .iter()
+                .all(|b| !b.is_alive())
+        {
+            self.bricks.truncate(self.bricks.len() - BRICK_COLS);
         }
-        return None;
+        self.place();
+    }
+
+    /// Lower edge of the lowest standing brick.
+    pub fn bottom(&self) -> Option<f32> {
+        self.bricks
+            .iter()
+            .rposition(|b| b.is_alive())
+            .map(|i| field_brick_rect(&self.field, i / BRICK_COLS, 0).pos.y + BRICK_HEIGHT * 0.5)
+    }
+
+    /// Index of the brick that broke in the last update.
+    pub fn take_broken(&mut self) -> Option<usize> {
+        self.broken.take()
+    }
+
+    pub fn get(&self, idx: usize) -> &Brick {
+        &self.bricks[idx]
     }
-}
 
-impl GameControl for Bricks {
-    fn update(&mut self, _renderer: &mut Renderer, _events: &Events) -> Option<Screen> {
-        if let Some(hit) = self.hit {
-            self.bricks[hit].state = BrickState::Dead;
+    /// Index of the first live brick the ball touches and where it is, it
+    /// is marked as hit.
+    pub fn ball_hits(&mut self, ball: &Ball) -> Option<(usize, Rectangle)> {
+        for (i, brick) in self.bricks.iter().enumerate() {
+            if brick.is_alive() && ball.hits(&brick.rect) {
+                self.hit = Some(i);
+                return Some((i, brick.rect));
+            }
         }
         return None;
     }
 
-    fn draw(&self, renderer: &mut Renderer) {
-        for brick in self.bricks.iter() {
-            if brick.is_alive() {
-                let mut fill = brick.color.clone();
-                fill.saturation(0.4);
-                brick.rect.fill_clr(renderer, fill);
-                brick.rect.draw_clr(renderer, brick.color);
+    /// Builds the wall from `level` and resets it.
+    pub fn set_level(&mut self, level: &Level) {
+        self.set_rows(BRICK_ROWS);
+        for row in 0..BRICK_ROWS {
+            for col in 0..BRICK_COLS {
+                let brick = &mut self.bricks[row * BRICK_COLS + col];
+                brick.present = false;
+                if let Some(spec) = level.get(row, col) {
+                    brick.present = true;
+                    brick.kind = spec.kind;
+                    brick.color = spec.color;
+                    brick.points = spec.points;
+                }
             }
         }
+        self.reset();
     }
-    fn reset(&mut self) {
-        self.hit = None;
+
+    /// Keeps only the bricks for which `present(row, col)` holds, the others
+    /// are left out of the wall until the layout changes again.
+    pub fn set_layout<F: Fn(usize, usize) -> bool>(&mut self, present: F) {
+        self.set_rows(BRICK_ROWS);
         for row in 0..BRICK_ROWS {
             for col in 0..BRICK_COLS {
-                self.bricks[row * BRICK_COLS + col].state = BrickState::Alive;
+                self.bricks[row * BRICK_COLS + col].present = present(row, col);
             }
         }
+        self.reset();
+    }
+
+    pub fn alive_rects(&self) -> impl Iterator<Item = &Rectangle> {
+        self.bricks.iter().filter(|b| b.is_alive()).map(|b| &b.rect)
+    }
+
+    pub fn alive_count(&self) -> usize {
+        self.bricks.iter().filter(|b| b.is_alive()).count()
+    }
+
+    /// Live bricks that still have to go before the wall is cleared.
+    pub fn breakable_count(&self) -> usize {
+        self.bricks
+            .iter()
+            .filter(|b| b.is_alive() && b.kind != BrickKind::Solid)
+            .count()
+    }
+
+    /// Whether row `row` has no breakable brick standing.
+    pub fn row_cleared(&self, row: usize) -> bool {
+        self.bricks[row * BRICK_COLS..(row + 1) * BRICK_COLS]
+            .iter()
+            .all(|b| !b.is_alive() || b.kind == BrickKind::Solid)
+    }
+
+    pub fn kill(&mut self, idx: usize) {
+        self.bricks[idx].state = BrickState::Dead;
+    }
+
+    /// Draws brick `idx` if it is still standing.
+    pub fn draw_one(&self, idx: usize, canvas: &mut dyn Canvas) {
+        let brick = &self.bricks[idx];
+        if brick.is_alive() {
+            brick.draw_shape(canvas);
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.hit = None;
+        self.broken = None;
+        for brick in self.bricks.iter_mut() {
+            brick.state = match brick.present {
+                true => BrickState::Alive,
+                false => BrickState::Dead,
+            };
+            brick.hits_left = brick.kind.hits().unwrap_or(0);
+        }
+    }
+}
+
+impl Sprite for Bricks {
+    fn update(&mut self, _input: &dyn Input) {
+        let Some(hit) = self.hit.take() else {
+            return;
+        };
+        let brick = &mut self.bricks[hit];
+        if brick.kind == BrickKind::Solid {
+            return;
+        }
+        brick.hits_left = brick.hits_left.saturating_sub(1);
+        if brick.hits_left == 0 {
+            brick.state = BrickState::Dead;
+            self.broken = Some(hit);
+        }
+    }
+}
+
+impl Drawable for Bricks {
+    fn draw(&self, canvas: &mut dyn Canvas) {
+        for idx in 0..self.bricks.len() {
+            self.draw_one(idx, canvas);
+        }
+    }
+}
+
+/// Draws a brick of `kind`, tough bricks show a second outline until hit.
+pub fn draw_brick(
+    canvas: &mut dyn Canvas,
+    rect: &Rectangle,
+    kind: BrickKind,
+    color: Rgba,
+    hits_left: usize,
+) {
+    let fill = match kind {
+        BrickKind::Solid => color.saturation(0.0),
+        _ => color.saturation(0.4),
+    };
+    canvas.fill_rect(rect, fill);
+    canvas.stroke_rect(rect, color);
+    if kind == BrickKind::Tough && hits_left > 1 {
+        let inner = Rectangle::new(rect.pos, vx2!(rect.size.x - 8.0, rect.size.y - 8.0));
+        canvas.stroke_rect(&inner, color);
     }
 }
@@ -1,3 +1,3 @@
-pub mod bricks;
 pub mod ball;
+pub mod bricks;
 pub mod paddle;
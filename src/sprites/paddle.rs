@@ -1,75 +1,122 @@
 use grapes::{
-    colors::{color::Color, presets::GrapesColors},
-    events::{input::Events, keyboard::K},
     objects::{rectangle::Rectangle, utils::BBox2d},
-    renderer::two_d::{Render, Renderer},
     vx2,
 };
 
 use crate::{
-    common::{FRAME_OFFSET, GameControl, HEIGHT, PADDLE_HEIGHT, PADDLE_VEL, PADDLE_WIDTH, WIDTH},
-    screens::Screen,
+    canvas::{Canvas, Rgba},
+    common::{Drawable, FRAME_OFFSET, Field, PADDLE_HEIGHT, PADDLE_VEL, PADDLE_WIDTH, Sprite},
+    input::{Input, Key},
 };
 
 pub struct Paddle {
     pub rect: Rectangle,
-    color: Color,
-    pub trans_color: Color,
+    color: Rgba,
+    pub trans_color: Rgba,
     pub in_trans: bool,
     vel: f32,
+    /// moving left and right
+    keys: [Key; 2],
+    field: Field,
 }
 
 impl Paddle {
     pub fn init() -> Self {
+        Self::with_field(Field::FULL)
+    }
+
+    /// A paddle at the bottom of `field` that stays inside it.
+    pub fn with_field(field: Field) -> Self {
         Self {
             rect: Rectangle::new(
-                vx2!(WIDTH * 0.5, HEIGHT - (FRAME_OFFSET + PADDLE_HEIGHT)),
+                vx2!(
+                    field.center_x(),
+                    field.height - (FRAME_OFFSET + PADDLE_HEIGHT)
+                ),
                 vx2!(PADDLE_WIDTH, PADDLE_HEIGHT),
             ),
             vel: PADDLE_VEL,
-            color: GrapesColors::Teal.into(),
-            trans_color: GrapesColors::Teal.into(),
+            color: Rgba::TEAL,
+            trans_color: Rgba::TEAL,
             in_trans: false,
+            keys: [Key::Left, Key::Right],
+            field,
         }
     }
 
-
-
-
     pub fn reset(&mut self) {
-        self.rect.pos = vx2!(WIDTH * 0.5, HEIGHT - (FRAME_OFFSET + PADDLE_HEIGHT));
+        let field = self.field;
+        self.rect.pos = vx2!(
+            field.center_x(),
+            field.height - (FRAME_OFFSET + PADDLE_HEIGHT)
+        );
     }
     pub fn set_x(&mut self, x: f32) {
         self.rect.pos.x = x;
     }
+    /// How far the paddle moves in a frame.
+    pub fn velocity(&self) -> f32 {
+        self.vel
+    }
+
+    pub fn set_width(&mut self, width: f32) {
+        self.rect.size.x = width;
+    }
+
+    /// Sets how far the paddle moves in a frame.
+    pub fn set_velocity(&mut self, vel: f32) {
+        self.vel = vel;
+    }
+
+    /// The keys moving the paddle instead of the arrows.
+    pub fn set_keys(&mut self, left: Key, right: Key) {
+        self.keys = [left, right];
+    }
+
+    pub fn set_color(&mut self, color: Rgba) {
+        self.color = color;
+    }
+
+    /// Pushes the paddle back out of `other` to the side it is on, staying
+    /// inside the frame.
+    pub fn keep_clear_of(&mut self, other: &Paddle) {
+        let gap = (self.rect.size.x + other.rect.size.x) * 0.5;
+        if (self.rect.pos.x - other.rect.pos.x).abs() >= gap {
+            return;
+        }
+        let half = self.rect.size.x * 0.5;
+        let x = if self.rect.pos.x < other.rect.pos.x {
+            other.rect.pos.x - gap
+        } else {
+            other.rect.pos.x + gap
+        };
+        self.rect.pos.x = x.clamp(self.field.min_x() + half, self.field.max_x() - half);
+    }
 }
 
-impl GameControl for Paddle {
-    fn update(&mut self, _renderer: &mut Renderer, events: &Events) -> Option<Screen> {
+impl Sprite for Paddle {
+    fn update(&mut self, input: &dyn Input) {
         let paddle_bbox = self.rect.bbox();
-        if events.key_pressed(K::ArrowRight) || events.key_down(K::ArrowRight) {
-            if (paddle_bbox.max_x + self.vel) < WIDTH - FRAME_OFFSET {
+        let [left, right] = self.keys;
+        if input.key_pressed(right) || input.key_down(right) {
+            if (paddle_bbox.max_x + self.vel) < self.field.max_x() {
                 self.rect.pos.x += self.vel;
             }
         }
-        if events.key_pressed(K::ArrowLeft) || events.key_down(K::ArrowLeft) {
-            if (paddle_bbox.min_x - self.vel) > FRAME_OFFSET {
+        if input.key_pressed(left) || input.key_down(left) {
+            if (paddle_bbox.min_x - self.vel) > self.field.min_x() {
                 self.rect.pos.x -= self.vel;
             }
         }
-
-        return None;
     }
+}
 
-    fn draw(&self, renderer: &mut Renderer) {
+impl Drawable for Paddle {
+    fn draw(&self, canvas: &mut dyn Canvas) {
         if self.in_trans {
-            self.rect.fill_clr(renderer, self.trans_color);
+            canvas.fill_rect(&self.rect, self.trans_color);
         } else {
-            self.rect.fill_clr(renderer, self.color);
+            canvas.fill_rect(&self.rect, self.color);
         }
     }
-
-    fn set_velocity(&mut self, _vel: grapes::linal::vertx2::VX2, level: f32) {
-        self.vel *= level;
-    }
 }
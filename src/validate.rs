@@ -0,0 +1,125 @@
+use std::fmt;
+
+use grapes::objects::rectangle::Rectangle;
+
+use crate::{
+    common::{BALL_RADIUS, FRAME_OFFSET, HEADING_SIZE, HEIGHT, PADDLE_HEIGHT, WIDTH},
+    level::Level,
+    sprites::bricks::brick_rect,
+};
+
+/// Slack for rectangles that only touch.
+const EPS: f32 = 1e-3;
+
+/// What a wall is checked against.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bounds {
+    /// inner edges of the frame
+    pub left: f32,
+    pub right: f32,
+    /// bottom of the heading
+    pub top: f32,
+    /// where the paddle zone starts, the paddle and room for the ball above it
+    pub paddle_top: f32,
+    pub ball_radius: f32,
+}
+
+impl Default for Bounds {
+    /// The playfield `Play` uses.
+    fn default() -> Self {
+        let paddle_top = HEIGHT - FRAME_OFFSET - 1.5 * PADDLE_HEIGHT;
+        Self {
+            left: FRAME_OFFSET,
+            right: WIDTH - FRAME_OFFSET,
+            top: HEADING_SIZE + FRAME_OFFSET,
+            paddle_top: paddle_top - 2.0 * BALL_RADIUS,
+            ball_radius: BALL_RADIUS,
+        }
+    }
+}
+
+/// Something wrong with a wall, bricks are given as (row, col).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Problem {
+    /// a breakable brick the ball can never get to
+    Unreachable((usize, usize)),
+    Overlap((usize, usize), (usize, usize)),
+    InHeading((usize, usize)),
+    InPaddleZone((usize, usize)),
+    OutsideFrame((usize, usize)),
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Problem::Unreachable((r, c)) => write!(f, "brick {r} {c} can not be reached"),
+            Problem::Overlap((r, c), (r2, c2)) => {
+                write!(f, "brick {r} {c} overlaps brick {r2} {c2}")
+            }
+            Problem::InHeading((r, c)) => write!(f, "brick {r} {c} reaches into the heading"),
+            Problem::InPaddleZone((r, c)) => {
+                write!(f, "brick {r} {c} reaches into the paddle zone")
+            }
+            Problem::OutsideFrame((r, c)) => write!(f, "brick {r} {c} sticks out of the frame"),
+        }
+    }
+}
+
+/// Checks `level` on the grid `Play` draws it on, see `validate_with`.
+pub fn validate(level: &Level) -> Vec<Problem> {
+    validate_with(level, brick_rect, &Bounds::default())
+}
+
+/// Everything wrong with `level` when brick (row, col) sits at
+/// `rect(row, col)`, in row order. An empty list means the wall is fine.
+///
+/// The ball comes from below and breaks its way up. It can pass through a
+/// cell once the brick there is gone, but only if the cell is wider and
+/// taller than the ball, otherwise it only gets to the bottom row.
+pub fn validate_with<F>(level: &Level, rect: F, bounds: &Bounds) -> Vec<Problem>
+where
+    F: Fn(usize, usize) -> Rectangle,
+{
+    let bricks: Vec<((usize, usize), Rectangle)> = level
+        .bricks()
+        .map(|(row, col, _)| ((row, col), rect(row, col)))
+        .collect();
+    let mut problems = Vec::new();
+
+    for (i, &(cell, r)) in bricks.iter().enumerate() {
+        let (left, right) = (r.pos.x - r.size.x * 0.5, r.pos.x + r.size.x * 0.5);
+        let (top, bottom) = (r.pos.y - r.size.y * 0.5, r.pos.y + r.size.y * 0.5);
+        if left < bounds.left - EPS || right > bounds.right + EPS {
+            problems.push(Problem::OutsideFrame(cell));
+        }
+        if top < bounds.top - EPS {
+            problems.push(Problem::InHeading(cell));
+        }
+        if bottom > bounds.paddle_top + EPS {
+            problems.push(Problem::InPaddleZone(cell));
+        }
+        for &(other, o) in &bricks[i + 1..] {
+            let dx = (r.pos.x - o.pos.x).abs() * 2.0;
+            let dy = (r.pos.y - o.pos.y).abs() * 2.0;
+            if dx < r.size.x + o.size.x - EPS && dy < r.size.y + o.size.y - EPS {
+                problems.push(Problem::Overlap(cell, other));
+            }
+        }
+    }
+
+    // room a broken brick leaves, measured centre to centre
+    let pitch_x = (rect(0, 1).pos.x - rect(0, 0).pos.x).abs();
+    let pitch_y = (rect(1, 0).pos.y - rect(0, 0).pos.y).abs();
+    let fits = pitch_x.min(pitch_y) >= 2.0 * bounds.ball_radius;
+    for cell in level.unreachable_by(fits) {
+        problems.push(Problem::Unreachable(cell));
+    }
+    problems.sort_by_key(|p| match *p {
+        Problem::Unreachable(cell)
+        | Problem::Overlap(cell, _)
+        | Problem::InHeading(cell)
+        | Problem::InPaddleZone(cell)
+        | Problem::OutsideFrame(cell) => cell,
+    });
+    problems
+}
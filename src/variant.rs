@@ -0,0 +1,291 @@
+use grapes::{linal::vertx2::VX2, vx2};
+
+use crate::{
+    canvas::Canvas,
+    common::{BALL_RADIUS, Drawable},
+    input::{Input, Key},
+    screens::play::MIN_VY_RATIO,
+    sprites::{
+        ball::Ball,
+        bricks::{Brick, Bricks},
+        paddle::Paddle,
+    },
+};
+
+/// How far Steerable turns the ball per frame, in radians.
+pub const STEER_ANGLE: f32 = 0.03;
+/// Frames a brick shows in Invisible after being hit.
+pub const FLASH_FRAMES: usize = 20;
+/// Length of a Timed game in frames, two minutes.
+pub const TIMED_FRAMES: usize = 2 * 60 * 60;
+
+/// The Atari 2600 game variants, picked on the menu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VariantKind {
+    #[default]
+    Regular,
+    /// the ball goes through the bricks it breaks
+    Breakthru,
+    /// `Z` and `X` turn the ball while it flies
+    Steerable,
+    /// the ball sticks to the paddle until `Space`
+    Catch,
+    /// bricks only show for a moment when hit
+    Invisible,
+    /// as many points as possible before the clock runs out, balls are free
+    Timed,
+}
+
+impl VariantKind {
+    pub const ALL: [VariantKind; 6] = [
+        VariantKind::Regular,
+        VariantKind::Breakthru,
+        VariantKind::Steerable,
+        VariantKind::Catch,
+        VariantKind::Invisible,
+        VariantKind::Timed,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            VariantKind::Regular => "Regular",
+            VariantKind::Breakthru => "Breakthru",
+            VariantKind::Steerable => "Steerable",
+            VariantKind::Catch => "Catch",
+            VariantKind::Invisible => "Invisible",
+            VariantKind::Timed => "Timed",
+        }
+    }
+
+    pub fn next(self) -> VariantKind {
+        let i = VariantKind::ALL
+            .iter()
+            .position(|v| *v == self)
+            .unwrap_or(0);
+        VariantKind::ALL[(i + 1) % VariantKind::ALL.len()]
+    }
+
+    /// Fresh rules for a game of this variant.
+    pub fn rules(self) -> Box<dyn Variant> {
+        match self {
+            VariantKind::Regular => Box::new(Regular),
+            VariantKind::Breakthru => Box::new(Breakthru),
+            VariantKind::Steerable => Box::new(Steerable),
+            VariantKind::Catch => Box::new(Catch::default()),
+            VariantKind::Invisible => Box::new(Invisible::default()),
+            VariantKind::Timed => Box::new(Timed::default()),
+        }
+    }
+}
+
+/// Rules `Play` asks at the points where the variants differ. The defaults
+/// are the regular game.
+pub trait Variant {
+    fn kind(&self) -> VariantKind;
+
+    /// A new game starts.
+    fn reset(&mut self) {}
+
+    /// A new ball is served.
+    fn new_ball(&mut self) {}
+
+    /// Every frame the ball is in play, after the paddle moved and before the
+    /// ball does.
+    fn update(&mut self, _input: &dyn Input, _ball: &mut Ball, _paddle: &Paddle) {}
+
+    /// The ball bounced off the top of the paddle.
+    fn paddle_hit(&mut self, _ball: &mut Ball, _paddle: &Paddle) {}
+
+    /// The ball touched `brick`, `broke` if that took the brick out.
+    /// Returns whether the ball bounces off it.
+    fn brick_hit(&mut self, _brick: &Brick, _broke: bool) -> bool {
+        true
+    }
+
+    /// The ball sits on the paddle and `Space` is for letting it go, not
+    /// for pausing.
+    fn holds_ball(&self) -> bool {
+        false
+    }
+
+    /// A lost ball counts against the balls of the game.
+    fn costs_ball(&self) -> bool {
+        true
+    }
+
+    /// Frames left on the clock, `None` without one.
+    fn time_left(&self) -> Option<usize> {
+        None
+    }
+
+    fn draw_bricks(&self, bricks: &Bricks, canvas: &mut dyn Canvas) {
+        bricks.draw(canvas);
+    }
+}
+
+pub struct Regular;
+
+impl Variant for Regular {
+    fn kind(&self) -> VariantKind {
+        VariantKind::Regular
+    }
+}
+
+pub struct Breakthru;
+
+impl Variant for Breakthru {
+    fn kind(&self) -> VariantKind {
+        VariantKind::Breakthru
+    }
+
+    /// Bricks that survive the hit, tough and solid ones, still bounce.
+    fn brick_hit(&mut self, _brick: &Brick, broke: bool) -> bool {
+        !broke
+    }
+}
+
+pub struct Steerable;
+
+impl Variant for Steerable {
+    fn kind(&self) -> VariantKind {
+        VariantKind::Steerable
+    }
+
+    fn update(&mut self, input: &dyn Input, ball: &mut Ball, _paddle: &Paddle) {
+        let left = input.key_down(Key::Z) || input.key_pressed(Key::Z);
+        let right = input.key_down(Key::X) || input.key_pressed(Key::X);
+        let turn = match (left, right) {
+            (true, false) => -STEER_ANGLE,
+            (false, true) => STEER_ANGLE,
+            _ => return,
+        };
+        ball.velocity = steer(ball.velocity, turn);
+    }
+}
+
+/// `velocity` turned towards the right by `angle`, as seen from the ball's
+/// way up or down. The ball is not turned any flatter than `MIN_VY_RATIO`.
+pub fn steer(velocity: VX2, angle: f32) -> VX2 {
+    // turning right means clockwise going up and anticlockwise going down
+    let angle = if velocity.y < 0.0 { angle } else { -angle };
+    let (sin, cos) = angle.sin_cos();
+    let turned = vx2!(
+        velocity.x * cos - velocity.y * sin,
+        velocity.x * sin + velocity.y * cos
+    );
+    let too_flat = turned.y.abs() < MIN_VY_RATIO * velocity.length();
+    if too_flat || turned.y.signum() != velocity.y.signum() {
+        velocity
+    } else {
+        turned
+    }
+}
+
+/// Holds on to the ball after a paddle hit, `Space` lets it go the way it
+/// would have bounced.
+#[derive(Default)]
+pub struct Catch {
+    /// x offset from the paddle centre and the velocity to leave with
+    caught: Option<(f32, VX2)>,
+}
+
+impl Variant for Catch {
+    fn kind(&self) -> VariantKind {
+        VariantKind::Catch
+    }
+
+    fn new_ball(&mut self) {
+        self.caught = None;
+    }
+
+    fn update(&mut self, input: &dyn Input, ball: &mut Ball, paddle: &Paddle) {
+        let Some((offset, velocity)) = self.caught else {
+            return;
+        };
+        let y = paddle.rect.pos.y - paddle.rect.size.y * 0.5 - BALL_RADIUS - 2.0;
+        ball.set_ball_pos(vx2!(paddle.rect.pos.x + offset, y));
+        if input.key_pressed(Key::Space) {
+            ball.set_ball_vel(velocity);
+            self.caught = None;
+        }
+    }
+
+    fn paddle_hit(&mut self, ball: &mut Ball, paddle: &Paddle) {
+        self.caught = Some((ball.circle.pos.x - paddle.rect.pos.x, ball.velocity));
+        ball.set_ball_vel(vx2!(0.0));
+    }
+
+    fn holds_ball(&self) -> bool {
+        self.caught.is_some()
+    }
+}
+
+/// Bricks are hidden and flash up for `FLASH_FRAMES` when the ball hits them.
+#[derive(Default)]
+pub struct Invisible {
+    /// the brick as it was hit and frames left, kept apart from the wall so
+    /// broken bricks show and pushed rows do not move them
+    flashes: Vec<(Brick, usize)>,
+}
+
+impl Variant for Invisible {
+    fn kind(&self) -> VariantKind {
+        VariantKind::Invisible
+    }
+
+    fn reset(&mut self) {
+        self.flashes.clear();
+    }
+
+    fn update(&mut self, _input: &dyn Input, _ball: &mut Ball, _paddle: &Paddle) {
+        self.flashes.retain_mut(|(_, frames)| {
+            *frames -= 1;
+            *frames > 0
+        });
+    }
+
+    fn brick_hit(&mut self, brick: &Brick, _broke: bool) -> bool {
+        self.flashes.push((*brick, FLASH_FRAMES));
+        true
+    }
+
+    /// A brick hit again while it flashes shows its latest look on top.
+    fn draw_bricks(&self, _bricks: &Bricks, canvas: &mut dyn Canvas) {
+        for (brick, _) in &self.flashes {
+            brick.draw_shape(canvas);
+        }
+    }
+}
+
+/// A clock of `TIMED_FRAMES` that runs while the ball is in play.
+pub struct Timed {
+    left: usize,
+}
+
+impl Default for Timed {
+    fn default() -> Self {
+        Self { left: TIMED_FRAMES }
+    }
+}
+
+impl Variant for Timed {
+    fn kind(&self) -> VariantKind {
+        VariantKind::Timed
+    }
+
+    fn reset(&mut self) {
+        self.left = TIMED_FRAMES;
+    }
+
+    fn update(&mut self, _input: &dyn Input, _ball: &mut Ball, _paddle: &Paddle) {
+        self.left = self.left.saturating_sub(1);
+    }
+
+    fn costs_ball(&self) -> bool {
+        false
+    }
+
+    fn time_left(&self) -> Option<usize> {
+        Some(self.left)
+    }
+}
@@ -0,0 +1,150 @@
+mod support;
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use breakout::{
+    common::clock_text,
+    difficulty::Difficulty,
+    input::Key,
+    mode::{Medal, Mode, PAR_FRAMES, SCORE_ATTACK_FRAMES},
+    scores::{Board, HighScores},
+    screens::Screen,
+    variant::VariantKind,
+};
+use support::script::Script;
+
+/// A fresh high score file for one test.
+fn scores_path(name: &str) -> PathBuf {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR"))
+        .join("attack")
+        .join(name);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir.join("scores")
+}
+
+fn attack(seed: u64, mode: Mode, name: &str) -> Script {
+    let mut s = Script::in_mode(seed, mode);
+    s.game_mut().set_scores_path(scores_path(name));
+    s
+}
+
+#[test]
+fn medals_and_high_scores() {
+    assert_eq!(Medal::for_time(PAR_FRAMES), Some(Medal::Gold));
+    assert_eq!(Medal::for_time(PAR_FRAMES + 1), Some(Medal::Silver));
+    assert_eq!(Medal::for_time(PAR_FRAMES * 2), Some(Medal::Bronze));
+    assert_eq!(Medal::for_time(PAR_FRAMES * 2 + 1), None);
+
+    let path = scores_path("round_trip");
+    let time = Board::new(Mode::TimeAttack);
+    let score = Board::new(Mode::ScoreAttack);
+    let mut scores = HighScores::new(path.clone());
+    assert!(scores.record(&time, 5000));
+    assert!(!scores.record(&time, 6000));
+    assert!(scores.record(&time, 4000));
+    assert!(scores.record(&score, 300));
+    assert!(!scores.record(&score, 200));
+    scores.save().unwrap();
+
+    let loaded = HighScores::load(path);
+    assert_eq!(loaded.best(&time), Some(4000));
+    assert_eq!(loaded.best(&score), Some(300));
+    assert_eq!(loaded.best(&Board::new(Mode::Standard)), None);
+}
+
+#[test]
+fn each_setting_keeps_its_own_best() {
+    let normal = Board::new(Mode::TimeAttack);
+    let easy = Board {
+        difficulty: Difficulty::Easy,
+        ..normal
+    };
+    let timed = Board {
+        variant: VariantKind::Timed,
+        ..Board::new(Mode::ScoreAttack)
+    };
+    let mut scores = HighScores::new(scores_path("boards"));
+    assert!(scores.record(&normal, 5000));
+    assert!(scores.record(&easy, 6000));
+    assert!(scores.record(&timed, 100));
+    assert_eq!(scores.best(&normal), Some(5000));
+    assert_eq!(scores.best(&easy), Some(6000));
+    assert_eq!(scores.best(&timed), Some(100));
+    assert_eq!(scores.best(&Board::new(Mode::ScoreAttack)), None);
+}
+
+#[test]
+fn time_attack_times_one_wall() {
+    let mut s = attack(11, Mode::TimeAttack, "time");
+    assert_eq!(s.game().heading().clock(), Some("0:00"));
+    s.game_mut()
+        .screens_mut()
+        .play
+        .bricks_mut()
+        .set_layout(|row, col| row == 7 && col == 8);
+    s.press(Key::Space).wait(90);
+    let shown = clock_text(s.play().clock());
+    assert_eq!(s.game().heading().clock(), Some(shown.as_str()));
+    assert_eq!(shown, "0:02");
+
+    s.follow_ball_until("the wall to be cleared", 4000, |s| {
+        s.screen() != Screen::Play
+    });
+    assert_eq!(s.screen(), Screen::GameOver);
+    let time = s.play().clock();
+    let board = s.game().board();
+    assert_eq!(s.game().high_scores().best(&board), Some(time));
+    let result = s.game().screens().game_over.result().unwrap();
+    assert!(
+        result.starts_with("Time 0:") && result.contains("Gold"),
+        "{result}"
+    );
+    assert!(result.ends_with("New Best"), "{result}");
+    let saved = HighScores::load(s.game().high_scores().path().to_path_buf());
+    assert_eq!(saved.best(&board), Some(time));
+}
+
+#[test]
+fn score_attack_runs_on_the_clock_with_free_balls() {
+    let mut s = attack(42, Mode::ScoreAttack, "score");
+    assert_eq!(s.play().time_left(), Some(SCORE_ATTACK_FRAMES));
+    assert_eq!(s.game().heading().clock(), Some("3:00"));
+    // let every ball drop and serve the next one
+    while s.screen() == Screen::Play {
+        assert!(
+            s.tick() < 4 * SCORE_ATTACK_FRAMES,
+            "the clock never ran out"
+        );
+        if s.play().has_started() || s.play().in_transition() {
+            s.wait(1);
+        } else {
+            s.press(Key::Space);
+        }
+    }
+    assert_eq!(s.screen(), Screen::GameOver);
+    assert_eq!(s.lives(), 3);
+    let score = s.score();
+    let board = Board::new(Mode::ScoreAttack);
+    assert_eq!(s.game().high_scores().best(&board), Some(score));
+    assert_eq!(
+        s.game().screens().game_over.result(),
+        Some(format!("Score {score} - New Best").as_str())
+    );
+}
+
+#[test]
+fn score_attack_keeps_its_clock_under_the_timed_variant() {
+    let mut s = Script::new(42);
+    s.game_mut().set_mode(Mode::ScoreAttack);
+    s.game_mut().set_variant(VariantKind::Timed);
+    s.game_mut().set_scores_path(scores_path("score_timed"));
+    s.start();
+    assert_eq!(s.play().time_left(), Some(SCORE_ATTACK_FRAMES));
+    assert_eq!(s.game().heading().clock(), Some("3:00"));
+    s.press(Key::Space).wait(60);
+    assert_eq!(s.play().time_left(), Some(SCORE_ATTACK_FRAMES - 61));
+}
@@ -0,0 +1,125 @@
+mod support;
+
+use breakout::{
+    cavity::{CavityBall, Pocket},
+    common::BALL_RADIUS,
+    input::Key,
+    level::{BrickKind, BrickSpec, Level, PALETTE},
+    mode::Mode,
+    screens::Screen,
+};
+use rand::{SeedableRng, rngs::StdRng};
+use support::script::Script;
+
+const POINTS: usize = 10;
+
+fn level(rows: &[&str]) -> Level {
+    let mut level = Level::new("cavity");
+    let brick = BrickSpec::new(BrickKind::Normal, PALETTE[0], POINTS);
+    for (row, line) in rows.iter().enumerate() {
+        for (col, b) in line.bytes().enumerate() {
+            if b == b'#' {
+                level.set(row, col, Some(brick));
+            }
+        }
+    }
+    level
+}
+
+/// A single cell pocket at (6, 2), the column on the left is open.
+fn one_pocket() -> Level {
+    let rows = [
+        "",
+        "",
+        "",
+        "",
+        "",
+        ".#############",
+        ".#.###########",
+        ".#############",
+    ];
+    level(&rows)
+}
+
+fn cavity(seed: u64, level: &Level) -> Script {
+    let mut s = Script::in_mode(seed, Mode::Cavity);
+    s.game_mut().screens_mut().play.next_wall(level);
+    s
+}
+
+fn free_a_ball(s: &mut Script) {
+    s.press(Key::Space)
+        .follow_ball_until("the pocket to open", 30_000, |s| {
+            s.play().balls_in_play() == 2
+        });
+}
+
+#[test]
+fn pockets_are_the_empty_cells_walled_in() {
+    let rows = ["##############", "#..###.######.", "######.#######"];
+    let pockets = level(&rows).pockets();
+    assert_eq!(pockets, [vec![(1, 1), (1, 2)], vec![(1, 13)]]);
+    assert!(level(&[]).pockets().is_empty());
+    assert_eq!(one_pocket().pockets(), [vec![(6, 2)]]);
+
+    let pocket = Pocket::new(pockets[0].clone());
+    assert!(pocket.opened_by(0, 1) && pocket.opened_by(1, 3) && pocket.opened_by(2, 2));
+    assert!(!pocket.opened_by(0, 0) && !pocket.opened_by(2, 3) && !pocket.opened_by(3, 1));
+}
+
+#[test]
+fn trapped_balls_bounce_inside_their_pocket() {
+    let mut rng = StdRng::seed_from_u64(3);
+    let mut extra = CavityBall::new(Pocket::new(vec![(1, 1), (1, 2)]), &mut rng);
+    let bounds = *extra.pocket().unwrap().bounds();
+    let start = extra.ball.circle.pos;
+    for _ in 0..2000 {
+        extra.bounce();
+        let pos = extra.ball.circle.pos;
+        assert!((pos.x - bounds.pos.x).abs() <= bounds.size.x * 0.5 - BALL_RADIUS + 1e-3);
+        assert!((pos.y - bounds.pos.y).abs() <= bounds.size.y * 0.5 - BALL_RADIUS + 1e-3);
+    }
+    assert!(!extra.is_free());
+    assert_ne!(extra.ball.circle.pos, start);
+}
+
+#[test]
+fn every_pocket_of_the_wall_holds_a_ball() {
+    let s = cavity(1, &one_pocket());
+    assert_eq!(s.play().cavity_balls().len(), 1);
+    assert_eq!(s.play().balls_in_play(), 1);
+
+    // the generated walls of the mode have pockets of their own
+    let s = Script::in_mode(1, Mode::Cavity);
+    assert!(!s.play().cavity_balls().is_empty());
+    let mut s = Script::new(1);
+    s.start();
+    assert!(s.play().cavity_balls().is_empty());
+}
+
+#[test]
+fn opened_pockets_let_out_balls_that_multiply_the_points() {
+    let mut s = cavity(7, &one_pocket());
+    free_a_ball(&mut s);
+    assert!(s.play().cavity_balls()[0].is_free());
+
+    let score = s.score();
+    s.follow_ball_until("a brick with both balls out", 10_000, |s| {
+        s.score() != score || s.play().balls_in_play() != 2
+    });
+    assert_eq!(s.play().balls_in_play(), 2);
+    assert_eq!(s.score() - score, 2 * POINTS);
+}
+
+#[test]
+fn dropping_one_of_two_balls_costs_nothing() {
+    let mut s = cavity(7, &one_pocket());
+    free_a_ball(&mut s);
+    // the paddle stays put, sooner or later a ball drops
+    s.wait_until("a ball to drop", 5000, |s| s.play().balls_in_play() == 1);
+    assert_eq!(s.screen(), Screen::Play);
+    assert_eq!(s.lives(), 3);
+    assert!(!s.play().in_transition());
+    assert!(s.play().has_started());
+    assert!(s.play().ball().velocity.length() > 0.0);
+}
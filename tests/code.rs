@@ -0,0 +1,153 @@
+mod support;
+
+use breakout::{
+    code::{ALPHABET, CodeError, decode, encode},
+    common::ScreenMsg,
+    generator::{Pattern, generate_with},
+    input::Key,
+    level::{BrickKind, BrickSpec, Level, PALETTE},
+    mode::Mode,
+    screens::Screen,
+};
+use support::script::Script;
+
+#[test]
+fn round_trip() {
+    let mut odd = Level::new("Odd");
+    odd.set(
+        0,
+        0,
+        Some(BrickSpec::new(BrickKind::Tough, PALETTE[5], 123_456)),
+    );
+    odd.set(7, 13, Some(BrickSpec::new(BrickKind::Solid, PALETTE[0], 0)));
+
+    let mut levels = vec![Level::classic(), Level::new("Empty"), odd];
+    for (i, pattern) in Pattern::ALL.into_iter().enumerate() {
+        levels.push(generate_with(pattern, i as u64, 0.9));
+    }
+    for level in levels {
+        let code = encode(&level);
+        assert_eq!(decode(&code).unwrap(), level, "{code}");
+    }
+}
+
+#[test]
+fn codes_are_short() {
+    let classic = encode(&Level::classic());
+    assert!(classic.len() <= 60, "{} chars: {classic}", classic.len());
+    let generated = encode(&generate_with(Pattern::Symmetric, 7, 1.0));
+    assert!(
+        generated.len() <= 200,
+        "{} chars: {generated}",
+        generated.len()
+    );
+}
+
+#[test]
+fn long_names_are_cut() {
+    let level = Level::new(&"é".repeat(40));
+    let decoded = decode(&encode(&level)).unwrap();
+    assert_eq!(decoded.name, "é".repeat(16));
+}
+
+#[test]
+fn typing_is_forgiving() {
+    let level = generate_with(Pattern::Tunnels, 3, 0.5);
+    let code = encode(&level);
+    let grouped: Vec<String> = code
+        .as_bytes()
+        .chunks(5)
+        .map(|c| String::from_utf8_lossy(c).to_lowercase())
+        .collect();
+    let typed = format!(" {} \n", grouped.join("-"));
+    assert_eq!(decode(&typed).unwrap(), level);
+
+    // letters that look like digits read as the digits
+    let look_alike = code.replace('0', "O").replace('1', "l");
+    assert_eq!(decode(&look_alike).unwrap(), level);
+}
+
+#[test]
+fn mistakes_are_caught() {
+    let code = encode(&Level::classic());
+    assert_eq!(decode(""), Err(CodeError::Empty));
+    assert_eq!(decode(" - "), Err(CodeError::Empty));
+    assert_eq!(decode(&format!("{code}!")), Err(CodeError::BadChar('!')));
+    assert_eq!(decode(&code[..code.len() - 3]), Err(CodeError::Checksum));
+
+    for i in 0..code.len() {
+        let mut typo: Vec<u8> = code.bytes().collect();
+        typo[i] = if typo[i] == b'7' { b'8' } else { b'7' };
+        let typo = String::from_utf8(typo).unwrap();
+        assert!(decode(&typo).is_err(), "typo at {i} slipped through");
+    }
+}
+
+#[test]
+fn play_a_code_from_the_menu() {
+    let mut level = Level::new("Shared");
+    level.fill_row(4, Some(BrickSpec::new(BrickKind::Normal, PALETTE[4], 25)));
+
+    let mut s = Script::new(5);
+    s.press(Key::C);
+    assert_eq!(s.screen(), Screen::Code);
+
+    s.type_text("NOT-A-CODE").press(Key::Enter);
+    assert_eq!(s.screen(), Screen::Code);
+    assert!(!s.game().screens().code.error().is_empty());
+
+    for _ in 0.."NOT-A-CODE".len() {
+        s.press(Key::Backspace);
+    }
+    assert_eq!(s.game().screens().code.code(), "");
+    s.type_text(&encode(&level)).press(Key::Enter);
+    assert_eq!(s.screen(), Screen::Play);
+    assert_eq!(s.bricks_alive(), 14);
+
+    // one wall only, then back to the menu
+    s.game_mut().handle(ScreenMsg::WallCleared);
+    assert_eq!(s.screen(), Screen::Menu);
+}
+
+#[test]
+fn a_code_plays_its_own_wall_in_versus_mode() {
+    let mut level = Level::new("Shared");
+    level.fill_row(4, Some(BrickSpec::new(BrickKind::Normal, PALETTE[4], 25)));
+
+    let mut s = Script::new(5);
+    s.game_mut().set_mode(Mode::Versus);
+    s.press(Key::C).type_text(&encode(&level)).press(Key::Enter);
+    assert_eq!(s.screen(), Screen::Play);
+    assert_eq!(s.bricks_alive(), 14);
+
+    // no duel to report when the balls run out
+    s.game_mut().handle(ScreenMsg::Goto(Screen::GameOver));
+    assert_eq!(s.game().screens().game_over.result(), None);
+}
+
+#[test]
+fn a_code_can_be_put_together_with_the_arrows() {
+    let mut level = Level::new("Arrows");
+    level.fill_row(2, Some(BrickSpec::new(BrickKind::Tough, PALETTE[1], 50)));
+    let code = encode(&level);
+
+    let mut s = Script::new(6);
+    s.press(Key::C);
+    for c in code.chars() {
+        // a new `0` turned the short way round the alphabet
+        s.press(Key::Right);
+        let at = ALPHABET.find(c).unwrap();
+        let (key, turns) = if at <= ALPHABET.len() / 2 {
+            (Key::Up, at)
+        } else {
+            (Key::Down, ALPHABET.len() - at)
+        };
+        for _ in 0..turns {
+            s.press(key);
+        }
+    }
+    assert_eq!(s.game().screens().code.code(), code);
+    s.press(Key::Enter);
+    assert_eq!(s.screen(), Screen::Play);
+    assert_eq!(s.bricks_alive(), 14);
+}
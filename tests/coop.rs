@@ -0,0 +1,80 @@
+mod support;
+
+use breakout::{
+    common::{FRAME_OFFSET, HEIGHT, WIDTH},
+    input::Key,
+    mode::Mode,
+    screens::Screen,
+};
+use support::script::Script;
+
+fn paddle_x(s: &Script) -> f32 {
+    s.play().paddle().rect.pos.x
+}
+
+fn partner_x(s: &Script) -> f32 {
+    s.play().partner().unwrap().rect.pos.x
+}
+
+#[test]
+fn each_paddle_has_its_own_keys() {
+    let mut s = Script::in_mode(1, Mode::Coop);
+    let (paddle, partner) = (paddle_x(&s), partner_x(&s));
+    assert!(partner < paddle);
+
+    s.hold(Key::A, 10);
+    assert!(partner_x(&s) < partner);
+    assert_eq!(paddle_x(&s), paddle);
+
+    s.hold(Key::Right, 10);
+    assert!(paddle_x(&s) > paddle);
+
+    let partner = partner_x(&s);
+    s.hold(Key::D, 10);
+    assert!(partner_x(&s) > partner);
+
+    let mut s = Script::new(1);
+    s.start();
+    assert!(s.play().partner().is_none());
+}
+
+#[test]
+fn the_paddles_do_not_overlap() {
+    let mut s = Script::in_mode(1, Mode::Coop);
+    let width = s.play().paddle().rect.size.x;
+    s.hold(Key::Left, 200);
+    assert!(paddle_x(&s) - partner_x(&s) >= width - 1e-3);
+
+    s.hold(Key::D, 200);
+    assert!(paddle_x(&s) - partner_x(&s) >= width - 1e-3);
+    assert!(paddle_x(&s) + width * 0.5 <= WIDTH - FRAME_OFFSET);
+}
+
+#[test]
+fn the_partner_returns_the_ball_too() {
+    let mut s = Script::in_mode(3, Mode::Coop);
+    s.press(Key::Space);
+    let mut returned = false;
+    for _ in 0..3000 {
+        // the arrows paddle waits at the right wall, the partner plays
+        let ball = s.play().ball().circle.pos;
+        let partner = partner_x(&s);
+        let input = s.input();
+        input.release_all();
+        input.press(Key::Right);
+        if ball.x > partner + 4.0 {
+            input.press(Key::D);
+        } else if ball.x < partner - 4.0 {
+            input.press(Key::A);
+        }
+        let falling = s.play().ball().velocity.y > 0.0;
+        s.wait(1);
+        let pos = s.play().ball().circle.pos;
+        let near = (pos.x - partner_x(&s)).abs() < 60.0 && pos.y > HEIGHT * 0.8;
+        if falling && s.play().ball().velocity.y < 0.0 && near {
+            returned = true;
+        }
+    }
+    assert!(returned);
+    assert_eq!(s.screen(), Screen::Play);
+}
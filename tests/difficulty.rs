@@ -0,0 +1,96 @@
+mod support;
+
+use breakout::{
+    common::{BALL_BASE_VEL_X, BALL_BASE_VEL_Y, PADDLE_VEL, ScreenMsg},
+    difficulty::Difficulty,
+    input::Key,
+    screens::Screen,
+};
+use support::script::Script;
+
+fn base_speed() -> f32 {
+    BALL_BASE_VEL_X.hypot(BALL_BASE_VEL_Y)
+}
+
+#[test]
+fn curves_only_get_faster_and_stay_under_the_cap() {
+    for difficulty in Difficulty::ALL {
+        let curve = difficulty.curve();
+        assert_eq!(curve.steps[0].score, 0, "{}", difficulty.name());
+        let mut last = curve.speeds(0);
+        for step in &curve.steps[1..] {
+            let speeds = curve.speeds(step.score);
+            assert_eq!(speeds.level, last.level + 1);
+            assert!(speeds.ball >= last.ball && speeds.paddle >= last.paddle);
+            assert!(speeds.ball <= curve.max_speed);
+            last = speeds;
+        }
+        assert_eq!(curve.speeds(usize::MAX), last);
+    }
+}
+
+#[test]
+fn normal_keeps_the_old_ball_speeds() {
+    let curve = Difficulty::Normal.curve();
+    for (score, multi, level) in [(0, 1.0, 1), (99, 1.0, 1), (100, 1.5, 2), (650, 3.0, 4)] {
+        let speeds = curve.speeds(score);
+        assert_eq!(speeds.level, level, "score {score}");
+        assert!((speeds.ball - multi * base_speed()).abs() < 1e-4, "score {score}");
+    }
+}
+
+#[test]
+fn harder_presets_start_no_slower() {
+    let starts: Vec<f32> = Difficulty::ALL
+        .iter()
+        .map(|d| d.curve().speeds(0).ball)
+        .collect();
+    assert!(starts.windows(2).all(|w| w[0] <= w[1]), "{starts:?}");
+}
+
+#[test]
+fn level_ups_do_not_stack_the_paddle_speed() {
+    let mut s = Script::new(3).layout(&[]);
+    s.start();
+    let curve = Difficulty::Normal.curve();
+    for score in [100, 200, 300] {
+        s.game_mut().handle(ScreenMsg::Points(100));
+        assert_eq!(s.score(), score);
+        let paddle = s.play().paddle().velocity();
+        assert!((paddle - curve.speeds(score).paddle).abs() < 1e-4);
+    }
+    assert_eq!(s.game().level(), 3);
+    assert!((s.play().paddle().velocity() - PADDLE_VEL * 1.5).abs() < 1e-4);
+}
+
+#[test]
+fn a_new_game_starts_at_the_base_speed() {
+    let mut s = Script::new(3).layout(&[]);
+    s.start();
+    s.game_mut().handle(ScreenMsg::Points(900));
+    assert_eq!(s.game().level(), 5);
+
+    s.game_mut().handle(ScreenMsg::Goto(Screen::Menu));
+    s.start().press(Key::Space).wait(2);
+    assert_eq!(s.game().level(), 1);
+    assert!((s.play().paddle().velocity() - PADDLE_VEL).abs() < 1e-4);
+    assert!((s.play().ball().velocity.length() - base_speed()).abs() < 1e-3);
+}
+
+#[test]
+fn the_menu_cycles_the_difficulty() {
+    let mut s = Script::new(3).layout(&[]);
+    assert_eq!(s.game().difficulty(), Difficulty::Normal);
+    s.press(Key::D).press(Key::D);
+    assert_eq!(s.game().difficulty(), Difficulty::Insane);
+    assert_eq!(s.game().screens().menu.difficulty(), Difficulty::Insane);
+
+    s.start().press(Key::Space).wait(2);
+    let speed = s.play().ball().velocity.length();
+    assert!((speed - Difficulty::Insane.curve().speeds(0).ball).abs() < 1e-3);
+
+    s.game_mut().set_difficulty(Difficulty::Easy);
+    assert_eq!(s.game().screens().menu.difficulty(), Difficulty::Easy);
+    assert_eq!(Difficulty::from_name("hard"), Some(Difficulty::Hard));
+    assert_eq!(Difficulty::from_name("brutal"), None);
+}
@@ -0,0 +1,89 @@
+mod support;
+
+use breakout::{
+    input::Key,
+    level::{BrickKind, Level},
+    screens::Screen,
+};
+use support::script::Script;
+
+fn editor() -> Script {
+    let mut s = Script::new(3);
+    s.press(Key::E);
+    assert_eq!(s.screen(), Screen::Editor);
+    s
+}
+
+#[test]
+fn keys_paint_erase_and_fill() {
+    let mut s = editor();
+    s.press(Key::Space);
+    assert!(s.editor().level().get(0, 0).is_some());
+
+    s.press(Key::Down).press(Key::Right).press(Key::Right);
+    assert_eq!(s.editor().cursor(), (1, 2));
+    s.press(Key::K).press(Key::Space);
+    assert_eq!(s.editor().level().get(1, 2).unwrap().kind, BrickKind::Tough);
+
+    s.press(Key::X);
+    assert!(s.editor().level().get(1, 2).is_none());
+
+    s.press(Key::F);
+    assert_eq!(s.editor().level().brick_count(), 1 + 14);
+}
+
+#[test]
+fn undo_and_redo() {
+    let mut s = editor();
+    s.press(Key::Space).press(Key::Right).press(Key::Space);
+    assert_eq!(s.editor().level().brick_count(), 2);
+
+    s.press(Key::Z);
+    assert_eq!(s.editor().level().brick_count(), 1);
+    s.press(Key::Z).press(Key::Z);
+    assert_eq!(s.editor().level().brick_count(), 0);
+    s.press(Key::Y);
+    assert_eq!(s.editor().level().brick_count(), 1);
+
+    // a new edit drops what could be redone
+    s.press(Key::F).press(Key::Y);
+    assert_eq!(s.editor().level().brick_count(), 14);
+}
+
+#[test]
+fn test_play_uses_the_edited_wall_and_comes_back() {
+    let mut s = editor();
+    s.press(Key::F).press(Key::Down).press(Key::Space);
+    s.press(Key::Enter);
+    assert_eq!(s.screen(), Screen::Play);
+    assert_eq!(s.bricks_alive(), 15);
+
+    s.press(Key::Escape);
+    assert_eq!(s.screen(), Screen::Editor);
+    assert_eq!(s.editor().level().brick_count(), 15);
+
+    // the normal game gets the classic wall back
+    s.press(Key::Escape).start();
+    assert_eq!(s.bricks_alive(), Level::classic().brick_count());
+}
+
+#[test]
+fn save_and_load() {
+    let path = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("editor/saved.level");
+    let _ = std::fs::remove_file(&path);
+
+    let mut s = editor();
+    s.editor_mut().set_path(path.clone());
+    s.press(Key::L);
+    assert!(s.editor().status().starts_with("Load failed"));
+
+    s.press(Key::C).press(Key::P).press(Key::F).press(Key::S);
+    assert!(s.editor().status().starts_with("Saved"));
+    let saved = s.editor().level().clone();
+    assert_eq!(Level::load(&path).unwrap(), saved);
+
+    s.press(Key::X).press(Key::L);
+    assert_eq!(s.editor().level(), &saved);
+    s.press(Key::Z);
+    assert_eq!(s.editor().level().brick_count(), 13);
+}
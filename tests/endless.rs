@@ -0,0 +1,118 @@
+mod support;
+
+use breakout::{
+    common::{PADDLE_WIDTH, ball_base_speed},
+    generator::{generate, rate},
+    input::Key,
+    mode::{BALL_EVERY, EndlessStage, Mode},
+    rules::Ruleset,
+    screens::Screen,
+};
+use support::script::Script;
+
+/// Swaps the wall for two bricks and plays until it is cleared.
+fn clear_stage(s: &mut Script) {
+    let stage = s.game().stage();
+    s.game_mut()
+        .screens_mut()
+        .play
+        .bricks_mut()
+        .set_layout(|row, col| row == 7 && col < 2);
+    if !s.play().has_started() {
+        s.press(Key::Space);
+    }
+    s.follow_ball_until("the next stage", 6000, |s| s.game().stage() > stage);
+}
+
+#[test]
+fn stages_get_harder() {
+    let stages: Vec<_> = (0..40).map(EndlessStage::new).collect();
+    for pair in stages.windows(2) {
+        assert!(pair[1].difficulty >= pair[0].difficulty);
+        assert!(pair[1].speed >= pair[0].speed);
+        assert!(pair[1].paddle_width <= pair[0].paddle_width);
+    }
+    assert_eq!(stages[0].paddle_width, PADDLE_WIDTH);
+    assert!(stages[39].paddle_width >= PADDLE_WIDTH * 0.5);
+
+    let mean_rate = |stage: usize| {
+        let d = EndlessStage::new(stage).difficulty;
+        (0..20).map(|seed| rate(&generate(seed, d))).sum::<f32>() / 20.0
+    };
+    assert!(mean_rate(0) < mean_rate(5) && mean_rate(5) < mean_rate(10));
+
+    let grants: Vec<usize> = (0..=3 * BALL_EVERY)
+        .filter(|&n| EndlessStage::grants_ball(n))
+        .collect();
+    assert_eq!(grants, [BALL_EVERY, 2 * BALL_EVERY, 3 * BALL_EVERY]);
+}
+
+#[test]
+fn a_cleared_wall_brings_a_faster_stage() {
+    let mut s = Script::in_mode(3, Mode::Endless);
+    assert!(s.play().bricks().breakable_count() > 0);
+    s.press(Key::Space).wait(1);
+    let speed = s.play().ball().velocity.length();
+    assert!((speed - ball_base_speed()).abs() < 1e-3);
+
+    clear_stage(&mut s);
+    assert_eq!(s.screen(), Screen::Play);
+    assert_eq!(s.game().stage(), 1);
+    assert_eq!(s.game().level(), 2);
+    assert!(s.play().bricks().breakable_count() > 0);
+    assert_eq!(
+        s.play().paddle().rect.size.x,
+        EndlessStage::new(1).paddle_width
+    );
+    s.press(Key::Space).wait(1);
+    assert!(s.play().ball().velocity.length() > speed + 0.1);
+}
+
+#[test]
+fn stages_speed_up_under_the_classic_rules_too() {
+    let mut s = Script::new(3);
+    s.game_mut().set_mode(Mode::Endless);
+    s.game_mut().set_ruleset(Ruleset::Classic);
+    s.start();
+    s.press(Key::Space).wait(1);
+    let speed = s.play().ball().velocity.length();
+
+    clear_stage(&mut s);
+    assert_eq!(s.game().stage(), 1);
+    assert_eq!(s.game().level(), 2);
+    assert_eq!(
+        s.play().paddle().rect.size.x,
+        EndlessStage::new(1).paddle_width
+    );
+    s.press(Key::Space).wait(1);
+    assert!(s.play().ball().velocity.length() > speed + 0.1);
+}
+
+#[test]
+fn milestones_give_an_extra_ball() {
+    let mut s = Script::in_mode(8, Mode::Endless);
+    for _ in 1..BALL_EVERY {
+        clear_stage(&mut s);
+    }
+    let lives = s.lives();
+    clear_stage(&mut s);
+    assert_eq!(s.game().stage(), BALL_EVERY);
+    assert_eq!(s.lives(), lives + 1);
+}
+
+#[test]
+fn game_over_tells_the_stages_survived() {
+    let mut s = Script::in_mode(5, Mode::Endless);
+    clear_stage(&mut s);
+    // the paddle stays put until every ball is gone
+    while s.screen() == Screen::Play {
+        s.press(Key::Space)
+            .wait_until_ball_lost(3000)
+            .wait_until_ready(200);
+    }
+    assert_eq!(s.screen(), Screen::GameOver);
+    assert_eq!(
+        s.game().screens().game_over.result(),
+        Some("Stages Survived: 1")
+    );
+}
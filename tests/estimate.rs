@@ -0,0 +1,103 @@
+use std::{fs, path::Path, process::Command};
+
+use breakout::{
+    estimate::{Run, SkillReport, estimate_with, simulate},
+    level::{BrickKind, BrickSpec, Level, PALETTE},
+};
+
+const TICKS: usize = 20_000;
+
+/// One row of normal bricks in the middle of the wall.
+fn one_row() -> Level {
+    let mut level = Level::new("One Row");
+    level.fill_row(5, Some(BrickSpec::new(BrickKind::Normal, PALETTE[3], 10)));
+    level
+}
+
+#[test]
+fn simulate_is_repeatable() {
+    let a = simulate(&one_row(), 0.5, 4, TICKS);
+    assert_eq!(a, simulate(&one_row(), 0.5, 4, TICKS));
+}
+
+#[test]
+fn a_good_bot_clears_an_easy_wall() {
+    let run = simulate(&one_row(), 0.9, 0, TICKS);
+    assert!(run.cleared, "{run:?}");
+    assert_eq!(run.score, 14 * 10);
+    assert_eq!(run.balls_lost, 0);
+}
+
+#[test]
+fn a_bot_that_barely_moves_loses_every_ball() {
+    let run = simulate(&Level::classic(), 0.0, 1, TICKS);
+    assert!(!run.cleared);
+    assert_eq!(run.balls_lost, 3);
+}
+
+#[test]
+fn harder_walls_rate_higher() {
+    let skills = [0.3, 0.9];
+    let easy = estimate_with(&one_row(), &skills, 2, 0, TICKS);
+    let hard = estimate_with(&Level::classic(), &skills, 2, 0, TICKS);
+    assert_eq!(easy.skills.len(), 2);
+    assert!(easy.skills.iter().all(|s| s.runs.len() == 2));
+    assert!((0.0..=1.0).contains(&easy.rating));
+    assert!(
+        easy.rating < hard.rating,
+        "{} >= {}",
+        easy.rating,
+        hard.rating
+    );
+}
+
+#[test]
+fn report_statistics() {
+    let run = |cleared, ticks, balls_lost, score| Run {
+        cleared,
+        ticks,
+        balls_lost,
+        score,
+    };
+    let report = SkillReport {
+        skill: 0.5,
+        runs: vec![
+            run(true, 600, 0, 300),
+            run(false, 900, 3, 100),
+            run(true, 1200, 1, 500),
+            run(true, 1800, 2, 400),
+        ],
+    };
+    assert_eq!(report.clear_rate(), 0.75);
+    assert_eq!(report.median_time(), Some(20.0));
+    assert_eq!(report.mean_balls_lost(), 1.5);
+    assert_eq!(report.scores(), [100, 300, 400, 400, 500]);
+
+    let none = SkillReport {
+        skill: 0.5,
+        runs: vec![run(false, 900, 3, 100)],
+    };
+    assert_eq!(none.median_time(), None);
+}
+
+#[test]
+fn cli_stores_the_rating() {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("estimate");
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("one_row.level");
+    one_row().save(&path).unwrap();
+
+    let out = Command::new(env!("CARGO_BIN_EXE_breakout"))
+        .arg("--estimate")
+        .arg(&path)
+        .args(["--runs", "1"])
+        .output()
+        .unwrap();
+    let text = String::from_utf8_lossy(&out.stdout);
+    assert!(out.status.success(), "{text}");
+    assert!(text.contains("rating"), "{text}");
+
+    let rated = Level::load(&path).unwrap();
+    assert!(rated.rating.is_some());
+    assert_eq!(rated.brick_count(), one_row().brick_count());
+}
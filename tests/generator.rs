@@ -0,0 +1,99 @@
+mod support;
+
+use breakout::{
+    canvas::Rgba,
+    generator::{Pattern, generate, generate_with, rate},
+    input::Key,
+    level::{BrickKind, BrickSpec, Level},
+    screens::Screen,
+};
+use support::script::Script;
+
+const DIFFICULTIES: [f32; 5] = [0.0, 0.25, 0.5, 0.75, 1.0];
+
+#[test]
+fn same_seed_same_wall() {
+    assert_eq!(generate(42, 0.6), generate(42, 0.6));
+    assert_ne!(generate(42, 0.6), generate(43, 0.6));
+}
+
+#[test]
+fn every_breakable_brick_is_reachable() {
+    for pattern in Pattern::ALL {
+        for d in DIFFICULTIES {
+            for seed in 0..40 {
+                let level = generate_with(pattern, seed, d);
+                assert!(level.brick_count() > 0, "{pattern:?} {d} {seed}");
+                assert_eq!(level.unreachable(), vec![], "{pattern:?} {d} {seed}");
+            }
+        }
+    }
+}
+
+#[test]
+fn harder_targets_give_harder_walls() {
+    let mean = |d: f32| (0..50).map(|seed| rate(&generate(seed, d))).sum::<f32>() / 50.0;
+    let rates = DIFFICULTIES.map(mean);
+    assert!(rates.windows(2).all(|w| w[0] < w[1]), "{rates:?}");
+    for (d, r) in DIFFICULTIES.iter().zip(rates) {
+        assert!(r <= 0.1 + 0.6 * d + 0.03, "{d}: {r}");
+    }
+}
+
+#[test]
+fn hard_walls_mix_brick_kinds() {
+    let level = generate_with(Pattern::Cavities, 5, 1.0);
+    let count = |kind| level.bricks().filter(|(_, _, b)| b.kind == kind).count();
+    assert!(count(BrickKind::Tough) > 0);
+    assert!(count(BrickKind::Solid) > 0);
+    assert!(count(BrickKind::Normal) > 0);
+}
+
+#[test]
+fn walled_in_bricks_are_unreachable() {
+    let solid = Some(BrickSpec::new(BrickKind::Solid, Rgba::TEAL, 0));
+    let mut level = Level::new("Vault");
+    level.set(
+        0,
+        5,
+        Some(BrickSpec::new(BrickKind::Normal, Rgba::TEAL, 10)),
+    );
+    level.set(0, 4, solid);
+    level.set(0, 6, solid);
+    level.set(1, 5, solid);
+    level.set(
+        0,
+        9,
+        Some(BrickSpec::new(BrickKind::Normal, Rgba::TEAL, 10)),
+    );
+    assert_eq!(level.unreachable(), vec![(0, 5)]);
+
+    level.set(1, 5, None);
+    assert_eq!(level.unreachable(), vec![]);
+}
+
+#[test]
+fn clearing_the_wall_brings_a_generated_one() {
+    let mut s = Script::new(5).layout(&["", "", "", "", "", "", "", "......##......"]);
+    s.start()
+        .press(Key::Space)
+        .follow_ball_until("the wall is cleared", 20_000, |s| s.game().stage() == 1);
+    let next = generate(5 + 1, 0.3);
+    let breakable = next
+        .bricks()
+        .filter(|(_, _, b)| b.kind != BrickKind::Solid)
+        .count();
+    assert_eq!(s.play().bricks().breakable_count(), breakable);
+    assert!(!s.play().has_started());
+
+    // a new game starts on the classic wall again
+    for _ in 0..3 {
+        s.wait_until_ready(200)
+            .press(Key::Space)
+            .wait_until_ball_lost(5_000);
+    }
+    assert_eq!(s.screen(), Screen::GameOver);
+    s.press(Key::Enter).start();
+    assert_eq!(s.game().stage(), 0);
+    assert_eq!(s.bricks_alive(), Level::classic().brick_count());
+}
@@ -0,0 +1,78 @@
+mod support;
+
+use breakout::{input::Key, mode::Mode, screens::Screen};
+use support::script::Script;
+
+fn active(s: &Script) -> usize {
+    s.play().hot_seat().unwrap().active()
+}
+
+/// Serves and lets the ball drop, returns the bricks left just before.
+fn play_turn(s: &mut Script) -> usize {
+    let player = active(s);
+    s.wait_until_ready(200).press(Key::Space);
+    let mut alive = s.bricks_alive();
+    while s.screen() == Screen::Play && active(s) == player {
+        assert!(s.tick() < 100_000, "the turn never ended");
+        alive = s.bricks_alive();
+        s.wait(1);
+    }
+    alive
+}
+
+#[test]
+fn players_take_turns_with_their_own_wall() {
+    let mut s = Script::in_mode(6, Mode::HotSeat);
+    let full = s.bricks_alive();
+    assert_eq!(active(&s), 0);
+    assert_eq!(s.game().heading().player(0), Some(("P1: 0", true)));
+    assert_eq!(s.game().heading().player(1), Some(("P2: 0", false)));
+
+    let left_by_one = play_turn(&mut s);
+    assert!(left_by_one < full);
+    let first = s.game().player_scores()[0];
+    assert!(first > 0);
+    assert_eq!(active(&s), 1);
+    assert_eq!(s.score(), 0);
+    assert_eq!(s.lives(), 3);
+    assert_eq!(s.bricks_alive(), full);
+    let shown = format!("P1: {first}");
+    assert_eq!(s.game().heading().player(0), Some((shown.as_str(), false)));
+    assert_eq!(s.game().heading().player(1), Some(("P2: 0", true)));
+
+    play_turn(&mut s);
+    assert_eq!(active(&s), 0);
+    assert_eq!(s.score(), first);
+    assert_eq!(s.lives(), 2);
+    assert_eq!(s.bricks_alive(), left_by_one);
+}
+
+#[test]
+fn game_over_names_the_winner() {
+    let mut s = Script::in_mode(9, Mode::HotSeat);
+    let mut turns = 0;
+    while s.screen() == Screen::Play {
+        play_turn(&mut s);
+        turns += 1;
+    }
+    assert_eq!(turns, 6);
+    assert_eq!(s.screen(), Screen::GameOver);
+    let [a, b] = s.game().player_scores();
+    let winner = match a.cmp(&b) {
+        std::cmp::Ordering::Greater => "Player 1 Wins",
+        std::cmp::Ordering::Less => "Player 2 Wins",
+        std::cmp::Ordering::Equal => "A Draw",
+    };
+    assert_eq!(
+        s.game().screens().game_over.result(),
+        Some(format!("{winner}, {a} To {b}").as_str())
+    );
+}
+
+#[test]
+fn one_player_games_have_no_turns() {
+    let mut s = Script::new(1);
+    s.start();
+    assert!(s.play().hot_seat().is_none());
+    assert_eq!(s.game().heading().player(0), None);
+}
@@ -0,0 +1,185 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use breakout::{
+    canvas::Rgba,
+    import::{ImportError, KindMap, from_pixels, import_png},
+    level::{BrickKind, Level, POINTS},
+};
+
+const RED: [u8; 4] = [0xDA, 0x2A, 0x47, 0xFF];
+const GREY: [u8; 4] = [0x80, 0x80, 0x80, 0xFF];
+const WHITE: [u8; 4] = [0xFF, 0xFF, 0xFF, 0xFF];
+const CLEAR: [u8; 4] = [0x12, 0x34, 0x56, 0x00];
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR"))
+        .join("import")
+        .join(name);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn write_png(path: &Path, width: u32, height: u32, color: png::ColorType, data: &[u8]) {
+    let file = fs::File::create(path).unwrap();
+    let mut encoder = png::Encoder::new(file, width, height);
+    encoder.set_color(color);
+    encoder.set_depth(png::BitDepth::Eight);
+    if color == png::ColorType::Indexed {
+        encoder.set_palette(vec![0xDA, 0x2A, 0x47, 0xFF, 0xFF, 0xFF]);
+        encoder.set_trns(vec![0xFF, 0x00]);
+    }
+    encoder
+        .write_header()
+        .and_then(|mut w| w.write_image_data(data))
+        .unwrap();
+}
+
+fn pixels(rows: &[&[[u8; 4]]]) -> Vec<u8> {
+    rows.iter()
+        .flat_map(|r| r.iter().flatten().copied())
+        .collect()
+}
+
+#[test]
+fn pixels_become_bricks() {
+    let data = pixels(&[&[RED, CLEAR, GREY], &[WHITE, RED, CLEAR]]);
+    let level = from_pixels("Art", 3, 2, &data, &KindMap::default()).unwrap();
+    assert_eq!(level.name, "Art");
+    assert_eq!(level.brick_count(), 4);
+
+    let red = level.get(0, 0).unwrap();
+    assert_eq!(red.kind, BrickKind::Normal);
+    assert_eq!(red.color, Rgba::new(0xDA, 0x2A, 0x47, 0xFF));
+    assert_eq!(red.points, POINTS[0]);
+    assert!(level.get(0, 1).is_none());
+    assert_eq!(level.get(0, 2).unwrap().kind, BrickKind::Tough);
+    assert_eq!(level.get(0, 2).unwrap().points, 2 * POINTS[0]);
+    assert_eq!(level.get(1, 0).unwrap().kind, BrickKind::Solid);
+    assert_eq!(level.get(1, 1).unwrap().points, POINTS[1]);
+}
+
+#[test]
+fn kinds_are_configurable() {
+    let kinds =
+        KindMap::parse("# reds never break\n#da2a47 = solid\n\n#808080 = normal\n").unwrap();
+    assert_eq!(
+        kinds.kind(Rgba::new(0xDA, 0x2A, 0x47, 0xFF)),
+        BrickKind::Solid
+    );
+    assert_eq!(
+        kinds.kind(Rgba::new(0x80, 0x80, 0x80, 0xFF)),
+        BrickKind::Normal
+    );
+    assert_eq!(
+        kinds.kind(Rgba::new(0xFF, 0xFF, 0xFF, 0xFF)),
+        BrickKind::Normal
+    );
+
+    for (text, line) in [
+        ("#12345 = tough\n", 1),
+        ("\n#123456 = wobbly\n", 2),
+        ("#123456\n", 1),
+    ] {
+        match KindMap::parse(text) {
+            Err(ImportError::Kinds { line: l, .. }) => assert_eq!(l, line, "{text}"),
+            other => panic!("{text}: expected an error, got {other:?}"),
+        }
+    }
+}
+
+#[test]
+fn images_larger_than_the_wall_are_refused() {
+    let data = vec![0xFF; 15 * 4];
+    assert!(matches!(
+        from_pixels("Wide", 15, 1, &data, &KindMap::default()),
+        Err(ImportError::TooLarge {
+            width: 15,
+            height: 1
+        })
+    ));
+}
+
+#[test]
+fn reads_png_colour_types() {
+    let dir = temp_dir("colour_types");
+    let rgba = dir.join("rgba.png");
+    write_png(&rgba, 2, 1, png::ColorType::Rgba, &pixels(&[&[RED, CLEAR]]));
+    let rgb = dir.join("rgb.png");
+    write_png(
+        &rgb,
+        2,
+        1,
+        png::ColorType::Rgb,
+        &[0xDA, 0x2A, 0x47, 0x80, 0x80, 0x80],
+    );
+    let indexed = dir.join("indexed.png");
+    write_png(&indexed, 2, 1, png::ColorType::Indexed, &[0, 1]);
+
+    let kinds = KindMap::default();
+    let level = import_png(&rgba, &kinds).unwrap();
+    assert_eq!(level.name, "rgba");
+    assert_eq!(level.brick_count(), 1);
+
+    let level = import_png(&rgb, &kinds).unwrap();
+    assert_eq!(level.brick_count(), 2);
+    assert_eq!(level.get(0, 1).unwrap().kind, BrickKind::Tough);
+
+    // the second palette entry is transparent
+    let level = import_png(&indexed, &kinds).unwrap();
+    assert_eq!(level.brick_count(), 1);
+    assert_eq!(
+        level.get(0, 0).unwrap().color,
+        Rgba::new(0xDA, 0x2A, 0x47, 0xFF)
+    );
+
+    assert!(matches!(
+        import_png(&dir.join("missing.png"), &kinds),
+        Err(ImportError::Io(_))
+    ));
+    fs::write(dir.join("broken.png"), b"not a png").unwrap();
+    assert!(matches!(
+        import_png(&dir.join("broken.png"), &kinds),
+        Err(ImportError::Png(_))
+    ));
+}
+
+#[test]
+fn cli_writes_a_level_file() {
+    let dir = temp_dir("cli");
+    let image = dir.join("heart.png");
+    write_png(
+        &image,
+        3,
+        2,
+        png::ColorType::Rgba,
+        &pixels(&[&[RED, CLEAR, RED], &[CLEAR, WHITE, CLEAR]]),
+    );
+    let kinds = dir.join("kinds");
+    fs::write(&kinds, "#ffffff = tough\n").unwrap();
+    let out = dir.join("heart.level");
+
+    let run = Command::new(env!("CARGO_BIN_EXE_breakout"))
+        .arg("--import")
+        .arg(&image)
+        .arg("--kinds")
+        .arg(&kinds)
+        .arg("--out")
+        .arg(&out)
+        .output()
+        .unwrap();
+    assert!(
+        run.status.success(),
+        "{}",
+        String::from_utf8_lossy(&run.stdout)
+    );
+
+    let level = Level::load(&out).unwrap();
+    assert_eq!(level.name, "heart");
+    assert_eq!(level.brick_count(), 3);
+    assert_eq!(level.get(1, 1).unwrap().kind, BrickKind::Tough);
+}
@@ -0,0 +1,104 @@
+use breakout::{
+    canvas::Rgba,
+    common::{BRICK_COLS, BRICK_ROWS},
+    level::{BrickKind, BrickSpec, Level, LevelError, POINTS},
+    sprites::bricks::Bricks,
+};
+
+fn sample() -> Level {
+    let mut level = Level::new("Sample");
+    let tough = BrickSpec::new(BrickKind::Tough, Rgba::new(0x12, 0xAB, 0xEF, 0xFF), 200);
+    level.fill_row(2, Some(tough));
+    level.set(0, 0, Some(BrickSpec::new(BrickKind::Solid, Rgba::TEAL, 0)));
+    level.set(
+        7,
+        13,
+        Some(BrickSpec::new(BrickKind::Normal, Rgba::MAROON, 10)),
+    );
+    level
+}
+
+#[test]
+fn classic_is_the_full_wall() {
+    let level = Level::classic();
+    assert_eq!(level.brick_count(), BRICK_ROWS * BRICK_COLS);
+    for (row, _, brick) in level.bricks() {
+        assert_eq!(brick.kind, BrickKind::Normal);
+        assert_eq!(brick.points, POINTS[row]);
+    }
+    assert_eq!(Bricks::init().alive_count(), BRICK_ROWS * BRICK_COLS);
+}
+
+#[test]
+fn text_round_trip() {
+    let level = sample();
+    let text = level.to_string();
+    assert!(text.contains("brick = 2 0 tough #12abef 200"));
+    assert_eq!(Level::parse(&text).unwrap(), level);
+
+    let mut rated = sample();
+    rated.rating = Some(0.25);
+    assert!(rated.to_string().contains("rating = 0.25"));
+    assert_eq!(Level::parse(&rated.to_string()).unwrap(), rated);
+}
+
+#[test]
+fn file_round_trip() {
+    let path = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("levels/sample.level");
+    sample().save(&path).unwrap();
+    assert_eq!(Level::load(&path).unwrap(), sample());
+}
+
+#[test]
+fn comments_and_blank_lines_are_skipped() {
+    let level =
+        Level::parse("# a level\n\nname = Dots\n  # indented\nbrick = 1 1 normal #ffffff 5\n")
+            .unwrap();
+    assert_eq!(level.name, "Dots");
+    assert_eq!(level.brick_count(), 1);
+}
+
+#[test]
+fn parse_errors_name_the_line() {
+    for (text, line, needle) in [
+        ("name = A\nbrick = 8 0 normal #ffffff 5", 2, "row `8`"),
+        ("brick = 0 14 normal #ffffff 5", 1, "column `14`"),
+        ("brick = 0 0 glass #ffffff 5", 1, "glass"),
+        ("brick = 0 0 normal red 5", 1, "colour"),
+        ("brick = 0 0 normal #ffffff", 1, "expected"),
+        ("\nspeed = 3", 2, "unknown key"),
+        ("just words", 1, "key = value"),
+        ("name = A\nrating = 1.5", 2, "rating"),
+    ] {
+        match Level::parse(text) {
+            Err(LevelError::Parse { line: l, msg }) => {
+                assert_eq!(l, line, "{text}");
+                assert!(msg.contains(needle), "{msg}");
+            }
+            other => panic!("{text}: {other:?}"),
+        }
+    }
+}
+
+#[test]
+fn tough_bricks_take_two_hits_and_solid_ones_stay() {
+    use breakout::{common::Sprite, input::FakeInput, sprites::ball::Ball};
+
+    let mut bricks = Bricks::init();
+    bricks.set_level(&sample());
+    let input = FakeInput::new();
+    let alive = bricks.alive_count();
+
+    let hit = |bricks: &mut Bricks, row: usize, col: usize| {
+        let pos = bricks.get(row * BRICK_COLS + col).rect().pos;
+        assert!(bricks.ball_hits(&Ball::new(pos)).is_some());
+        bricks.update(&input);
+        bricks.take_broken()
+    };
+    assert_eq!(hit(&mut bricks, 2, 3), None);
+    assert_eq!(hit(&mut bricks, 2, 3), Some(2 * BRICK_COLS + 3));
+    assert_eq!(bricks.get(2 * BRICK_COLS + 3).points(), 200);
+    assert_eq!(hit(&mut bricks, 0, 0), None);
+    assert_eq!(hit(&mut bricks, 0, 0), None);
+    assert_eq!(bricks.alive_count(), alive - 1);
+}
@@ -0,0 +1,160 @@
+mod support;
+
+use std::{
+    net::{Ipv4Addr, SocketAddr, UdpSocket},
+    thread,
+    time::Duration,
+};
+
+use breakout::{
+    input::Key,
+    lobby::{Announcer, Finder, Lobby},
+    mode::Mode,
+    net::PROTOCOL_VERSION,
+};
+use support::script::Script;
+
+#[test]
+fn lobbies_are_announced_in_plain_text() {
+    let lobby = Lobby::new("Ada's Game", 7811);
+    assert_eq!(lobby.version, PROTOCOL_VERSION);
+    assert!(lobby.is_compatible());
+    assert_eq!(Lobby::parse(&lobby.encode()), Some(lobby.clone()));
+
+    assert_eq!(Lobby::parse(""), None);
+    assert_eq!(Lobby::parse("name = Ada's Game\nport = 7811"), None);
+    let no_port = lobby.encode().replace("port = 7811\n", "");
+    assert_eq!(Lobby::parse(&no_port), None);
+    let bad_version = lobby.encode().replace("version = ", "version = x");
+    assert_eq!(Lobby::parse(&bad_version), None);
+}
+
+#[test]
+fn a_hosted_lobby_is_listed_and_joined() {
+    let mut guest = Script::in_mode(2, Mode::Online);
+    let finder = guest
+        .game_mut()
+        .screens_mut()
+        .online
+        .find("127.0.0.1:0")
+        .unwrap();
+    let mut host = Script::in_mode(1, Mode::Online);
+    let online = &mut host.game_mut().screens_mut().online;
+    online.set_lobby_name("Ada's Game");
+    online.set_announce_addr(finder);
+    let addr = online.host("127.0.0.1:0").unwrap();
+
+    for _ in 0..1000 {
+        if !guest.game().screens().online.lobbies().is_empty() {
+            break;
+        }
+        host.wait(1);
+        guest.wait(1);
+    }
+    let lobbies = guest.game().screens().online.lobbies();
+    assert_eq!(lobbies.len(), 1);
+    let found = &lobbies[0];
+    assert_eq!(found.addr, addr);
+    assert_eq!(found.lobby.name, "Ada's Game");
+    assert_eq!(found.lobby.version, PROTOCOL_VERSION);
+
+    // the host keeps announcing, the lobby stays listed once
+    host.wait(200);
+    guest.wait(1);
+    assert_eq!(guest.game().screens().online.lobbies().len(), 1);
+
+    guest.press(Key::Enter);
+    for _ in 0..1000 {
+        if host.is_online_ready() && guest.is_online_ready() {
+            assert_eq!(guest.game().screens().online.address(), addr.to_string());
+            return;
+        }
+        host.wait(1);
+        guest.wait(1);
+    }
+    panic!("the games never connected");
+}
+
+#[test]
+fn a_lobby_of_another_version_is_not_joined() {
+    let mut guest = Script::in_mode(3, Mode::Online);
+    let finder = guest
+        .game_mut()
+        .screens_mut()
+        .online
+        .find("127.0.0.1:0")
+        .unwrap();
+    let mut lobby = Lobby::new("Future Game", 1);
+    lobby.version = PROTOCOL_VERSION + 1;
+    assert!(!lobby.is_compatible());
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    socket.send_to(b"not a lobby", finder).unwrap();
+    socket.send_to(lobby.encode().as_bytes(), finder).unwrap();
+
+    guest.wait_until("the lobby", 1000, |s| {
+        !s.game().screens().online.lobbies().is_empty()
+    });
+    let lobbies = guest.game().screens().online.lobbies();
+    assert_eq!(lobbies.len(), 1);
+    assert_eq!(lobbies[0].lobby, lobby);
+
+    guest.press(Key::Enter);
+    let online = &guest.game().screens().online;
+    assert!(online.session().is_none());
+    assert!(online.error().is_none());
+    let notice = online.notice().unwrap();
+    assert!(notice.contains("version"), "{notice}");
+    assert!(
+        notice.contains(&(PROTOCOL_VERSION + 1).to_string()),
+        "{notice}"
+    );
+    assert_eq!(online.lobbies().len(), 1);
+}
+
+#[test]
+fn a_host_address_can_be_put_together_with_the_arrows() {
+    let mut s = Script::in_mode(4, Mode::Online);
+    s.game_mut()
+        .screens_mut()
+        .online
+        .find("127.0.0.1:0")
+        .unwrap();
+    s.game_mut()
+        .screens_mut()
+        .online
+        .set_address("127.0.0.1:7811");
+    s.press(Key::A);
+    s.press(Key::Backspace).press(Key::Backspace);
+    for _ in 0..2 {
+        s.press(Key::Right)
+            .press(Key::Up)
+            .press(Key::Up)
+            .press(Key::Up);
+    }
+    assert_eq!(s.game().screens().online.address(), "127.0.0.1:7822");
+    s.press(Key::Down);
+    assert_eq!(s.game().screens().online.address(), "127.0.0.1:7821");
+}
+
+#[test]
+fn games_on_one_computer_look_for_lobbies_together() {
+    let mut first = Finder::bind("0.0.0.0:0").unwrap();
+    let port = first.addr().port();
+    let mut second = Finder::bind(&format!("0.0.0.0:{port}")).unwrap();
+    let target = SocketAddr::from((Ipv4Addr::BROADCAST, port));
+    let mut announcer = Announcer::new(&Lobby::new("Ada's Game", 7811), target).unwrap();
+
+    for _ in 0..1000 {
+        announcer.tick();
+        first.poll();
+        second.poll();
+        if !first.lobbies().is_empty() && !second.lobbies().is_empty() {
+            break;
+        }
+        thread::sleep(Duration::from_millis(1));
+    }
+    for finder in [&first, &second] {
+        assert_eq!(finder.lobbies().len(), 1);
+        assert_eq!(finder.lobbies()[0].lobby.name, "Ada's Game");
+    }
+}
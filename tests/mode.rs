@@ -0,0 +1,100 @@
+mod support;
+
+use breakout::{
+    common::{BRICK_COLS, BRICK_HEIGHT, BRICK_MARGIN, BRICK_ROWS},
+    input::Key,
+    level::{BrickKind, BrickSpec, PALETTE},
+    mode::{Mode, Progressive, SHIFT_FRAMES},
+    screens::Screen,
+    sprites::bricks::Bricks,
+};
+use support::script::Script;
+
+const PITCH: f32 = BRICK_HEIGHT + 2.0 * BRICK_MARGIN;
+
+#[test]
+fn pushed_rows_move_the_wall_down() {
+    let mut bricks = Bricks::init();
+    let bottom = bricks.bottom().unwrap();
+    let row = Progressive::new(3).next_row();
+    let added = row.iter().flatten().count();
+    assert!(added > 0);
+
+    bricks.push_row(&row);
+    assert_eq!(bricks.rows(), BRICK_ROWS + 1);
+    assert_eq!(bricks.alive_count(), BRICK_ROWS * BRICK_COLS + added);
+    assert!((bricks.bottom().unwrap() - bottom - PITCH).abs() < 1e-3);
+
+    // empty rows at the bottom go, the wall keeps its size
+    bricks.set_layout(|row, _| row == 0);
+    bricks.push_row(&[None; BRICK_COLS]);
+    assert_eq!(bricks.rows(), BRICK_ROWS);
+    assert_eq!(bricks.alive_count(), BRICK_COLS);
+}
+
+#[test]
+fn progressive_rows_come_at_a_fixed_pace() {
+    let mut p = Progressive::new(9);
+    let shifts: Vec<usize> = (1..=3 * SHIFT_FRAMES).filter(|_| p.tick()).collect();
+    assert_eq!(shifts, [SHIFT_FRAMES, 2 * SHIFT_FRAMES, 3 * SHIFT_FRAMES]);
+
+    let rows: Vec<_> = (0..20).map(|_| p.next_row()).collect();
+    assert_eq!(p.rows_added(), 20);
+    assert_eq!(rows[0], Progressive::new(9).next_row());
+    assert!(
+        rows.iter()
+            .flatten()
+            .flatten()
+            .all(|b| b.kind != BrickKind::Solid)
+    );
+}
+
+#[test]
+fn the_wall_comes_down_while_playing() {
+    let mut s = Script::in_mode(4, Mode::Progressive);
+    let bottom = s.play().bricks().bottom().unwrap();
+    s.press(Key::Space)
+        .follow_ball_until("the wall to move", SHIFT_FRAMES + 200, |s| {
+            s.play().progressive().unwrap().rows_added() == 1
+        });
+    assert_eq!(s.screen(), Screen::Play);
+    assert!(s.play().bricks().bottom().unwrap() >= bottom + PITCH - 1e-3);
+}
+
+#[test]
+fn a_cleared_wall_brings_in_the_next_row() {
+    let mut s = Script::in_mode(11, Mode::Progressive);
+    let play = &mut s.game_mut().screens_mut().play;
+    play.bricks_mut().set_layout(|row, col| row == 7 && col < 2);
+    s.press(Key::Space)
+        .follow_ball_until("the wall to be cleared", 4000, |s| {
+            s.play().progressive().unwrap().rows_added() > 0
+        });
+    assert_eq!(s.screen(), Screen::Play);
+    assert_eq!(s.game().stage(), 0);
+    assert!(s.play().bricks().breakable_count() > 0);
+}
+
+#[test]
+fn the_game_ends_when_the_wall_reaches_the_paddle() {
+    let mut s = Script::in_mode(2, Mode::Progressive);
+    let paddle = s.play().paddle().rect;
+    let paddle_top = paddle.pos.y - paddle.size.y * 0.5;
+    let play = &mut s.game_mut().screens_mut().play;
+    // bricks at the sides only, leaving the ball room to fly
+    let side = |col| col == 0 || col == BRICK_COLS - 1;
+    play.bricks_mut()
+        .set_layout(|row, col| row == 0 && side(col));
+    let brick = BrickSpec::new(BrickKind::Normal, PALETTE[0], 10);
+    let row: [_; BRICK_COLS] = std::array::from_fn(|col| side(col).then_some(brick));
+    while play.bricks().bottom().unwrap() + PITCH < paddle_top {
+        play.bricks_mut().push_row(&row);
+    }
+
+    s.press(Key::Space)
+        .follow_ball_until("the end", SHIFT_FRAMES + 200, |s| {
+            s.screen() == Screen::GameOver
+        });
+    assert_eq!(s.lives(), 3);
+    assert_eq!(s.play().progressive().unwrap().rows_added(), 1);
+}
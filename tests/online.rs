@@ -0,0 +1,214 @@
+mod support;
+
+use std::{
+    io::Write,
+    net::{SocketAddr, TcpStream},
+};
+
+use breakout::{
+    input::{Key, Pad},
+    mode::Mode,
+    net::{Msg, PROTOCOL_VERSION},
+    screens::Screen,
+};
+use support::script::Script;
+
+fn hosting() -> (Script, SocketAddr) {
+    let mut host = Script::in_mode(1, Mode::Online);
+    let addr = host
+        .game_mut()
+        .screens_mut()
+        .online
+        .host("127.0.0.1:0")
+        .unwrap();
+    (host, addr)
+}
+
+/// The lobby list, on a port of its own so tests can run side by side.
+fn find(s: &mut Script) -> SocketAddr {
+    let online = &mut s.game_mut().screens_mut().online;
+    online.find("127.0.0.1:0").unwrap()
+}
+
+fn frame(s: &Script) -> usize {
+    s.game().screens().online.session().unwrap().frame()
+}
+
+/// Two games on loopback, both with the game on.
+fn connected() -> (Script, Script) {
+    let (mut host, addr) = hosting();
+    let mut guest = Script::in_mode(2, Mode::Online);
+    find(&mut guest);
+    guest.press(Key::A);
+    let online = &mut guest.game_mut().screens_mut().online;
+    online.set_address(&addr.to_string());
+    guest.press(Key::Enter);
+    for _ in 0..1000 {
+        if host.is_online_ready() && guest.is_online_ready() {
+            return (host, guest);
+        }
+        host.wait(1);
+        guest.wait(1);
+    }
+    panic!("the games never connected");
+}
+
+#[test]
+fn pads_travel_in_a_byte() {
+    for bits in 0..8 {
+        assert_eq!(Pad::from_bits(bits).bits(), bits);
+    }
+    let msgs = [
+        Msg::Hello {
+            version: PROTOCOL_VERSION,
+            seed: u64::MAX - 7,
+        },
+        Msg::Pad {
+            frame: 70_000,
+            pad: Pad::from_bits(5),
+        },
+        Msg::Ping(3),
+        Msg::Pong(3),
+        Msg::Bye,
+    ];
+    let mut buf = Vec::new();
+    for msg in msgs {
+        msg.encode(&mut buf);
+    }
+    let mut decoded = Vec::new();
+    let mut at = 0;
+    while let Some((msg, len)) = Msg::decode(&buf[at..]).unwrap() {
+        decoded.push(msg);
+        at += len;
+    }
+    assert_eq!(decoded, msgs);
+    assert_eq!(Msg::decode(&buf[..3]).unwrap(), None);
+    assert!(Msg::decode(&[0xEE]).is_err());
+}
+
+#[test]
+fn a_serve_while_waiting_for_the_other_game_is_kept() {
+    let (mut host, mut guest) = connected();
+    // the guest goes quiet, the host runs out of its pads
+    host.wait(20);
+    let stalled = frame(&host);
+    host.press(Key::Space).wait(5);
+    assert_eq!(frame(&host), stalled);
+
+    for _ in 0..30 {
+        host.wait(1);
+        guest.wait(1);
+    }
+    for s in [&host, &guest] {
+        let versus = s.game().screens().online.session().unwrap().versus();
+        assert!(versus.side(0).has_started());
+    }
+}
+
+#[test]
+fn both_games_play_the_same_game() {
+    let (mut host, mut guest) = connected();
+    assert_eq!(host.game().screens().online.session().unwrap().side(), 0);
+    assert_eq!(guest.game().screens().online.session().unwrap().side(), 1);
+
+    // both serve and sweep, the guest out of step with the host
+    let end = 600;
+    while frame(&host) < end || frame(&guest) < end {
+        for (i, s) in [&mut host, &mut guest].into_iter().enumerate() {
+            if frame(s) >= end {
+                continue;
+            }
+            let t = s.tick() + i * 17;
+            let input = s.input();
+            input.release_all();
+            input.press(if t % 90 < 45 { Key::Left } else { Key::Right });
+            if t % 120 == 0 {
+                input.press(Key::Space);
+            }
+            s.wait(1);
+        }
+    }
+    let a = host.game().screens().online.session().unwrap();
+    let b = guest.game().screens().online.session().unwrap();
+    assert!(a.latency().is_some() && b.latency().is_some());
+    let (a, b) = (a.versus(), b.versus());
+    assert_eq!(a.scores(), b.scores());
+    assert!(a.scores().iter().sum::<usize>() > 0);
+    for side in 0..2 {
+        let (x, y) = (a.side(side), b.side(side));
+        assert_eq!(x.ball().circle.pos, y.ball().circle.pos);
+        assert_eq!(x.paddle().rect.pos, y.paddle().rect.pos);
+        assert_eq!(x.bricks().alive_count(), y.bricks().alive_count());
+        assert_eq!(x.balls_left(), y.balls_left());
+    }
+}
+
+#[test]
+fn both_games_end_with_the_same_winner() {
+    let (mut host, mut guest) = connected();
+    // the guest serves and never moves, the host never serves
+    while host.screen() == Screen::Online || guest.screen() == Screen::Online {
+        assert!(host.tick() < 20_000, "the guest never ran out of balls");
+        host.wait(1);
+        if guest.screen() == Screen::Online {
+            guest.press(Key::Space);
+        }
+    }
+    for s in [&host, &guest] {
+        assert_eq!(s.screen(), Screen::GameOver);
+        let versus = s.game().screens().online.session().unwrap().versus();
+        assert_eq!(versus.winner(), Some(0));
+        let [a, b] = versus.scores();
+        assert_eq!(
+            s.game().screens().game_over.result(),
+            Some(format!("Player 1 Wins, {a} To {b}").as_str())
+        );
+    }
+}
+
+#[test]
+fn a_player_leaving_ends_the_game_for_the_other() {
+    let (mut host, mut guest) = connected();
+    host.wait(10);
+    guest.wait(10);
+    guest.press(Key::Escape);
+    assert_eq!(guest.screen(), Screen::Menu);
+    host.wait_until("the guest to be gone", 1000, |s| {
+        s.game().screens().online.error().is_some()
+    });
+    assert_eq!(
+        host.game().screens().online.error(),
+        Some("the other player left")
+    );
+    host.press(Key::Enter);
+    assert_eq!(host.screen(), Screen::Menu);
+}
+
+#[test]
+fn a_different_version_is_turned_away() {
+    let (mut host, addr) = hosting();
+    let mut stream = TcpStream::connect(addr).unwrap();
+    let mut hello = Vec::new();
+    Msg::Hello {
+        version: PROTOCOL_VERSION + 1,
+        seed: 0,
+    }
+    .encode(&mut hello);
+    stream.write_all(&hello).unwrap();
+    host.wait_until("the version check", 1000, |s| {
+        s.game().screens().online.error().is_some()
+    });
+    let error = host.game().screens().online.error().unwrap();
+    assert!(error.contains("version"), "{error}");
+
+    let mut guest = Script::in_mode(3, Mode::Online);
+    find(&mut guest);
+    guest.press(Key::A);
+    guest
+        .game_mut()
+        .screens_mut()
+        .online
+        .set_address("127.0.0.1:1");
+    guest.press(Key::Enter);
+    assert!(guest.game().screens().online.error().is_some());
+}
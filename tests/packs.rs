@@ -0,0 +1,243 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use breakout::{
+    breakout::Breakout,
+    common::ScreenMsg,
+    level::{BrickKind, BrickSpec, Level, PALETTE, checksum},
+    mode::Mode,
+    pack::{MANIFEST, Pack, PackError, Progress, StageError, scan},
+    screens::Screen,
+};
+
+/// A fresh directory for one test.
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR"))
+        .join("packs")
+        .join(name);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn level(name: &str, row: usize) -> Level {
+    let mut level = Level::new(name);
+    level.fill_row(row, Some(BrickSpec::new(BrickKind::Normal, PALETTE[3], 10)));
+    level
+}
+
+/// Writes `levels` into `dir` with a manifest listing them, extra manifest
+/// lines go after each stage.
+fn write_pack(dir: &Path, levels: &[(&str, Level, &str)]) {
+    fs::create_dir_all(dir).unwrap();
+    let mut manifest = String::from("name = Test Pack\nauthor = Tests\n");
+    for (file, level, extra) in levels {
+        let text = level.to_string();
+        fs::write(dir.join(file), &text).unwrap();
+        manifest += &format!(
+            "stage = {file}\nchecksum = {:08x}\n{extra}",
+            checksum(text.as_bytes())
+        );
+    }
+    fs::write(dir.join(MANIFEST), manifest).unwrap();
+}
+
+#[test]
+fn parse_manifest() {
+    let text = "\
+# a comment
+name = Starter
+author = Someone
+description = Two walls
+stage = 01.level
+title = Opening
+par = 2000
+music = calm
+theme = teal
+stage = 02.level
+";
+    let pack = Pack::parse("starter", text).unwrap();
+    assert_eq!(pack.id, "starter");
+    assert_eq!(pack.name, "Starter");
+    assert_eq!(pack.author, "Someone");
+    assert_eq!(pack.description, "Two walls");
+    assert_eq!(pack.stages.len(), 2);
+    let first = &pack.stages[0];
+    assert_eq!(first.title, "Opening");
+    assert_eq!(first.par, Some(2000));
+    assert_eq!(first.music.as_deref(), Some("calm"));
+    assert_eq!(first.theme.as_deref(), Some("teal"));
+    assert_eq!(pack.stages[1].title, "02.level");
+    assert_eq!(pack.stages[1].par, None);
+}
+
+#[test]
+fn manifest_errors_name_the_line() {
+    for (text, line) in [
+        ("name = A\n", 1),
+        ("name = A\nstage = a.level\npar = lots\n", 3),
+        ("name = A\nstage = a.level\nchecksum = xyz\n", 3),
+        ("stage = a.level\nauthor = B\n", 2),
+        ("name A\n", 1),
+    ] {
+        match Pack::parse("a", text) {
+            Err(PackError::Parse { line: l, .. }) => assert_eq!(l, line, "{text}"),
+            other => panic!("{text}: expected a parse error, got {other:?}"),
+        }
+    }
+}
+
+#[test]
+fn broken_stages_are_reported_not_fatal() {
+    let dir = temp_dir("broken_stages").join("mixed");
+    write_pack(
+        &dir,
+        &[
+            ("good.level", level("Good", 0), ""),
+            ("edited.level", level("Edited", 1), ""),
+            ("empty.level", Level::new("Empty"), ""),
+        ],
+    );
+    fs::write(dir.join("edited.level"), level("Edited", 2).to_string()).unwrap();
+    let mut manifest = fs::read_to_string(dir.join(MANIFEST)).unwrap();
+    manifest += "stage = missing.level\n";
+    fs::write(dir.join(MANIFEST), manifest).unwrap();
+
+    let pack = Pack::load(&dir).unwrap();
+    assert_eq!(pack.stages.len(), 4);
+    assert_eq!(pack.stages[0].level.as_ref().unwrap().name, "Good");
+    assert!(matches!(
+        pack.stages[1].level,
+        Err(StageError::Checksum { .. })
+    ));
+    assert!(matches!(pack.stages[2].level, Err(StageError::Empty)));
+    assert!(matches!(pack.stages[3].level, Err(StageError::Level(_))));
+    assert_eq!(pack.broken_count(), 3);
+    assert_eq!(pack.next_playable(1), None);
+}
+
+#[test]
+fn scan_keeps_going_past_a_bad_manifest() {
+    let dir = temp_dir("scan");
+    write_pack(&dir.join("b_good"), &[("1.level", level("One", 0), "")]);
+    fs::create_dir_all(dir.join("a_bad")).unwrap();
+    fs::write(dir.join("a_bad").join(MANIFEST), "name = Bad\n").unwrap();
+    fs::create_dir_all(dir.join("c_not_a_pack")).unwrap();
+
+    let packs = scan(&dir);
+    assert_eq!(packs.len(), 2);
+    assert!(matches!(&packs[0], Err((id, _)) if id == "a_bad"));
+    assert_eq!(packs[1].as_ref().unwrap().id, "b_good");
+}
+
+#[test]
+fn progress_round_trip() {
+    let path = temp_dir("progress").join("progress");
+    let mut progress = Progress::new(path.clone());
+    progress.record_clear("starter", 0);
+    progress.record_clear("starter", 2);
+    progress.record_clear("starter", 1);
+    progress.record_score("starter", 900);
+    progress.record_score("starter", 400);
+    progress.save().unwrap();
+
+    let loaded = Progress::load(path);
+    let starter = loaded.get("starter");
+    assert_eq!((starter.furthest, starter.best), (3, 900));
+    assert_eq!(loaded.get("other").furthest, 0);
+}
+
+fn game_with_packs(name: &str) -> (Breakout, PathBuf) {
+    let dir = temp_dir(name);
+    write_pack(
+        &dir.join("pack"),
+        &[
+            ("1.level", level("One", 0), "par = 100\n"),
+            ("2.level", level("Two", 1), ""),
+            ("3.level", level("Three", 2), ""),
+        ],
+    );
+    let progress = dir.join("progress");
+    let mut game = Breakout::new();
+    game.screens_mut()
+        .packs
+        .set_dirs(dir.clone(), progress.clone());
+    game.handle(ScreenMsg::Goto(Screen::Packs));
+    (game, progress)
+}
+
+#[test]
+fn playing_a_pack_saves_progress() {
+    let (mut game, progress) = game_with_packs("play");
+    assert_eq!(game.screens().packs.packs().len(), 1);
+
+    game.handle(ScreenMsg::PlayPack { pack: 0, stage: 0 });
+    assert_eq!(*game.screen(), Screen::Play);
+    assert_eq!(game.pack_stage(), Some(("pack", 0)));
+    assert_eq!(game.screens().play.bricks().breakable_count(), 14);
+
+    game.handle(ScreenMsg::WallCleared);
+    assert_eq!(game.pack_stage(), Some(("pack", 1)));
+    game.handle(ScreenMsg::Points(300));
+    game.handle(ScreenMsg::Goto(Screen::GameOver));
+    assert_eq!(game.pack_stage(), None);
+
+    let saved = Progress::load(progress);
+    assert_eq!(
+        (saved.get("pack").furthest, saved.get("pack").best),
+        (1, 300)
+    );
+
+    // the next run picks up at the stage not cleared yet
+    game.handle(ScreenMsg::Goto(Screen::Packs));
+    assert_eq!(game.screens().packs.progress().get("pack").furthest, 1);
+}
+
+#[test]
+fn a_pack_plays_its_own_walls_in_online_mode() {
+    let (mut game, _) = game_with_packs("online");
+    game.set_mode(Mode::Online);
+    game.handle(ScreenMsg::PlayPack { pack: 0, stage: 0 });
+    assert_eq!(*game.screen(), Screen::Play);
+    assert_eq!(game.pack_stage(), Some(("pack", 0)));
+    assert_eq!(game.screens().play.bricks().breakable_count(), 14);
+}
+
+#[test]
+fn a_broken_stage_is_skipped_not_cleared() {
+    let (mut game, progress) = game_with_packs("skip");
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("packs/skip/pack");
+    fs::write(dir.join("2.level"), level("Edited", 5).to_string()).unwrap();
+    game.handle(ScreenMsg::Goto(Screen::Packs));
+    assert!(
+        game.screens().packs.pack(0).unwrap().stages[1]
+            .level
+            .is_err()
+    );
+
+    game.handle(ScreenMsg::PlayPack { pack: 0, stage: 0 });
+    game.handle(ScreenMsg::WallCleared);
+    assert_eq!(game.pack_stage(), Some(("pack", 2)));
+    game.handle(ScreenMsg::WallCleared);
+    assert_eq!(*game.screen(), Screen::Menu);
+    // the furthest stage counts, the broken one in between was only skipped
+    assert_eq!(Progress::load(progress).get("pack").furthest, 3);
+}
+
+#[test]
+fn clearing_the_last_stage_ends_the_run() {
+    let (mut game, progress) = game_with_packs("last");
+    game.handle(ScreenMsg::PlayPack { pack: 0, stage: 1 });
+    game.handle(ScreenMsg::WallCleared);
+    assert_eq!(game.pack_stage(), Some(("pack", 2)));
+    game.handle(ScreenMsg::WallCleared);
+    assert_eq!(game.pack_stage(), None);
+    assert_eq!(*game.screen(), Screen::Menu);
+    assert_eq!(Progress::load(progress).get("pack").furthest, 3);
+
+    // a normal game gets the classic wall back
+    game.handle(ScreenMsg::Goto(Screen::Play));
+    assert_eq!(game.screens().play.bricks().breakable_count(), 112);
+}
@@ -0,0 +1,180 @@
+use breakout::{
+    common::{
+        BALL_RADIUS, BRICK_COLS, BRICK_HEIGHT, BRICK_MARGIN, BRICK_ROWS, FRAME_OFFSET, GameScreen,
+        HEADING_SIZE, HEIGHT, PADDLE_HEIGHT, PADDLE_WIDTH, WIDTH, frame_segments,
+    },
+    input::{FakeInput, Key},
+    screens::play::{MIN_VY_RATIO, Play, paddle_bounce, reflect_off},
+};
+use grapes::{
+    linal::vertx2::VX2,
+    objects::{line::Line2d, rectangle::Rectangle},
+    vx2,
+};
+use proptest::prelude::*;
+
+const TICKS: usize = 400;
+const EPS: f32 = 1e-3;
+
+/// Lowest y a ball can start at without touching the wall.
+const BELOW_WALL: f32 =
+    HEADING_SIZE + FRAME_OFFSET + BRICK_ROWS as f32 * (BRICK_HEIGHT + 2.0 * BRICK_MARGIN);
+
+fn velocity(speed: f32, angle: f32) -> VX2 {
+    vx2!(speed * angle.cos(), speed * angle.sin())
+}
+
+/// Angles whose vertical share is at least `MIN_VY_RATIO`, up or down.
+fn steep_angle() -> impl Strategy<Value = f32> {
+    let max = MIN_VY_RATIO.acos();
+    (-max..max, any::<bool>()).prop_map(|(a, up)| {
+        let a = std::f32::consts::FRAC_PI_2 + a;
+        if up { -a } else { a }
+    })
+}
+
+fn rect() -> impl Strategy<Value = Rectangle> {
+    (
+        100.0f32..600.0,
+        100.0f32..600.0,
+        10.0f32..120.0,
+        10.0f32..60.0,
+    )
+        .prop_map(|(x, y, w, h)| Rectangle::new(vx2!(x, y), vx2!(w, h)))
+}
+
+/// A launched ball somewhere between the wall and the paddle.
+fn play(layout: &[bool], seed: u64, pos: VX2, vel: VX2) -> Play {
+    let frame = frame_segments().map(|(a, b)| Line2d::new(a, b));
+    let mut play = Play::init(frame);
+    play.seed(seed);
+    play.bricks_mut()
+        .set_layout(|row, col| layout[row * BRICK_COLS + col]);
+
+    let mut input = FakeInput::new();
+    input.press(Key::Space);
+    play.update(&input, &mut Vec::new());
+    assert!(play.has_started());
+
+    play.ball_mut().set_ball_pos(pos);
+    play.ball_mut().set_ball_vel(vel);
+    play
+}
+
+fn check_ball(play: &Play, speed: f32) -> Result<(), TestCaseError> {
+    let ball = play.ball();
+    let pos = ball.circle.pos;
+    let s = ball.velocity.length();
+    prop_assert!((s - speed).abs() <= EPS * speed, "speed {s} != {speed}");
+    prop_assert!(
+        ball.velocity.y.abs() >= MIN_VY_RATIO * s - EPS,
+        "too flat: {:?}",
+        ball.velocity
+    );
+    prop_assert!(
+        pos.x > FRAME_OFFSET && pos.x < WIDTH - FRAME_OFFSET,
+        "left the frame: {pos:?}"
+    );
+    prop_assert!(pos.y > HEADING_SIZE, "went through the top: {pos:?}");
+    for rect in play.bricks().alive_rects() {
+        let inside = (pos.x - rect.pos.x).abs() < rect.size.x * 0.5
+            && (pos.y - rect.pos.y).abs() < rect.size.y * 0.5;
+        prop_assert!(!inside, "inside a brick at {:?}: {pos:?}", rect.pos);
+    }
+    Ok(())
+}
+
+#[test]
+fn a_hit_near_the_centre_barely_deflects() {
+    let half = PADDLE_WIDTH * 0.5;
+    let down = vx2!(0.0, 6.0);
+    let sideways = |offset| paddle_bounce(down, offset, half).x;
+    assert_eq!(sideways(0.0), 0.0);
+    assert!(sideways(2.0) > 0.0);
+    assert!(sideways(2.0) < sideways(half * 0.5) * 0.25);
+    assert!(sideways(half * 0.5) < sideways(half));
+    assert_eq!(sideways(half), sideways(half * 2.0));
+    assert_eq!(sideways(-half), -sideways(half));
+}
+
+proptest! {
+    #[test]
+    fn paddle_bounce_keeps_speed_and_goes_up(
+        speed in 0.5f32..40.0,
+        angle in steep_angle(),
+        offset in -80.0f32..80.0,
+    ) {
+        let v = paddle_bounce(velocity(speed, angle), offset, PADDLE_WIDTH * 0.5);
+        prop_assert!((v.length() - speed).abs() <= EPS * speed);
+        prop_assert!(v.y < 0.0);
+        prop_assert!(v.y.abs() >= MIN_VY_RATIO * speed - EPS);
+    }
+
+    #[test]
+    fn paddle_bounce_deflects_more_further_out(
+        speed in 0.5f32..40.0,
+        angle in steep_angle(),
+        a in -80.0f32..80.0,
+        b in -80.0f32..80.0,
+        half_width in 10.0f32..80.0,
+    ) {
+        let (near, far) = if a.abs() <= b.abs() { (a, b) } else { (b, a) };
+        let v = velocity(speed, angle);
+        let bounce = |offset| paddle_bounce(v, offset, half_width);
+        let (lo, hi) = (a.min(b), a.max(b));
+        prop_assert!(bounce(lo).x <= bounce(hi).x + EPS * speed);
+        prop_assert!(bounce(near).x.abs() <= bounce(far).x.abs() + EPS * speed);
+    }
+
+    #[test]
+    fn reflect_off_keeps_speed_and_settles(
+        speed in 0.5f32..40.0,
+        angle in -3.2f32..3.2,
+        rect in rect(),
+        dx in -1.5f32..1.5,
+        dy in -1.5f32..1.5,
+    ) {
+        let center = vx2!(
+            rect.pos.x + dx * rect.size.x * 0.5,
+            rect.pos.y + dy * rect.size.y * 0.5
+        );
+        let v = reflect_off(velocity(speed, angle), center, &rect);
+        prop_assert!((v.length() - speed).abs() <= EPS * speed);
+        let again = reflect_off(v, center, &rect);
+        prop_assert_eq!((again.x, again.y), (v.x, v.y));
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn ball_stays_in_play(
+        layout in proptest::collection::vec(any::<bool>(), BRICK_ROWS * BRICK_COLS),
+        seed in any::<u64>(),
+        x in FRAME_OFFSET + 2.0 * BALL_RADIUS..WIDTH - FRAME_OFFSET - 2.0 * BALL_RADIUS,
+        y in BELOW_WALL + 2.0 * BALL_RADIUS..HEIGHT - 3.0 * PADDLE_HEIGHT,
+        speed in 1.0f32..30.0,
+        angle in steep_angle(),
+        keys in proptest::collection::vec(0u8..8, TICKS),
+    ) {
+        let mut play = play(&layout, seed, vx2!(x, y), velocity(speed, angle));
+        let mut input = FakeInput::new();
+        let mut msgs = Vec::new();
+        for bits in keys {
+            input.release_all();
+            for (bit, key) in [(1, Key::Left), (2, Key::Right), (4, Key::Space)] {
+                if bits & bit != 0 {
+                    input.press(key);
+                }
+            }
+            play.update(&input, &mut msgs);
+            if play.in_transition() {
+                // lost at the bottom, the only way out
+                prop_assert!(play.ball().circle.pos.y > HEIGHT - FRAME_OFFSET - BALL_RADIUS - EPS);
+                break;
+            }
+            check_ball(&play, speed)?;
+        }
+    }
+}
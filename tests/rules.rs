@@ -0,0 +1,119 @@
+mod support;
+
+use breakout::{
+    common::{PADDLE_WIDTH, ScreenMsg, ball_base_speed},
+    input::Key,
+    rules::{CLASSIC_SPEEDS, Classic, Ruleset},
+    screens::Screen,
+};
+use support::script::Script;
+
+const FULL_ROW: &str = "##############";
+
+fn classic(seed: u64, rows: &[&str]) -> Script {
+    let mut s = Script::new(seed).layout(rows);
+    s.game_mut().set_ruleset(Ruleset::Classic);
+    s
+}
+
+#[test]
+fn classic_speeds_up_after_4_and_12_paddle_hits() {
+    let mut c = Classic::default();
+    let hits: Vec<bool> = (1..=20).map(|_| c.paddle_hit()).collect();
+    assert!(hits[3] && hits[11]);
+    assert_eq!(hits.iter().filter(|h| **h).count(), 2);
+    assert_eq!(c.speedups(), 2);
+}
+
+#[test]
+fn classic_speeds_up_once_for_orange_and_once_for_red() {
+    let mut c = Classic::default();
+    assert!(!c.brick_hit(7));
+    assert!(c.brick_hit(3));
+    assert!(!c.brick_hit(2));
+    assert!(c.brick_hit(1));
+    assert!(!c.brick_hit(0));
+    assert_eq!(c.speed(), CLASSIC_SPEEDS[2]);
+
+    // a new ball starts slow, but the paddle stays small
+    assert!(c.back_wall());
+    assert!(!c.back_wall());
+    c.new_ball();
+    assert_eq!(c.speedups(), 0);
+    assert!(c.is_shrunk());
+    assert!(c.brick_hit(0));
+}
+
+#[test]
+fn classic_rows_pay_the_atari_points() {
+    let mut s = classic(11, &["", "", "", "", "", "", "", FULL_ROW]);
+    s.start()
+        .press(Key::Space)
+        .follow_ball_until("a brick is hit", 2000, |s| s.score() > 0);
+    assert_eq!(s.score(), 1);
+    assert_eq!(s.game().level(), 1);
+
+    let mut s = classic(11, &[FULL_ROW]);
+    s.start()
+        .press(Key::Space)
+        .follow_ball_until("a brick is hit", 2000, |s| s.score() > 0);
+    assert_eq!(s.score(), 7);
+    // first touch of the red rows
+    assert_eq!(s.game().level(), 2);
+    let speed = s.play().ball().velocity.length();
+    assert!((speed - CLASSIC_SPEEDS[1] * ball_base_speed()).abs() < 1e-3);
+}
+
+#[test]
+fn reaching_the_back_wall_halves_the_paddle() {
+    let mut s = classic(5, &[]);
+    s.start()
+        .press(Key::Space)
+        .follow_ball_until("the back wall", 2000, |s| {
+            s.play().paddle().rect.size.x < PADDLE_WIDTH
+        });
+    assert_eq!(s.play().paddle().rect.size.x, PADDLE_WIDTH * 0.5);
+
+    // a new game brings the full paddle back
+    s.game_mut().handle(ScreenMsg::Goto(Screen::Menu));
+    s.start();
+    assert_eq!(s.play().paddle().rect.size.x, PADDLE_WIDTH);
+}
+
+#[test]
+fn classic_games_have_five_balls_and_two_walls() {
+    let mut s = classic(42, &[]);
+    s.start();
+    assert_eq!(s.lives(), 5);
+    for lives in [4, 3, 2, 1] {
+        s.press(Key::Space).wait_until_ball_lost(2000);
+        assert_eq!(s.lives(), lives);
+        s.wait_until_ready(200);
+    }
+    s.press(Key::Space).wait_until_ball_lost(2000);
+    assert_eq!(s.screen(), Screen::GameOver);
+
+    let mut s = classic(42, &[]);
+    s.start();
+    s.game_mut().handle(ScreenMsg::WallCleared);
+    assert_eq!(s.screen(), Screen::Play);
+    assert_eq!(s.bricks_alive(), 14 * 8);
+    s.game_mut().handle(ScreenMsg::WallCleared);
+    assert_eq!(s.screen(), Screen::GameOver);
+}
+
+#[test]
+fn the_menu_switches_the_rules() {
+    let mut s = Script::new(3).layout(&[]);
+    assert_eq!(s.game().ruleset(), Ruleset::Standard);
+    s.press(Key::R);
+    assert_eq!(s.game().ruleset(), Ruleset::Classic);
+    assert_eq!(s.game().screens().menu.ruleset(), Ruleset::Classic);
+    s.start();
+    assert_eq!(s.lives(), 5);
+
+    s.game_mut().set_ruleset(Ruleset::Standard);
+    s.game_mut().handle(ScreenMsg::Goto(Screen::Menu));
+    s.start();
+    assert_eq!(s.lives(), 3);
+}
@@ -0,0 +1,158 @@
+mod support;
+
+use breakout::{
+    common::{FRAME_OFFSET, PADDLE_WIDTH, WIDTH},
+    input::{FakeInput, Key, spin_text},
+    screens::Screen,
+};
+use support::script::Script;
+
+const FULL_ROW: &str = "##############";
+
+#[test]
+fn losing_a_ball_runs_the_transition() {
+    let mut s = Script::new(7).layout(&[]);
+    s.start().press(Key::Space).wait_until_ball_lost(1000);
+
+    assert_eq!(s.screen(), Screen::Play);
+    assert_eq!(s.lives(), 2);
+    assert!(s.play().in_transition());
+
+    s.wait_until_ready(200);
+    assert!(!s.play().has_started());
+    assert_eq!(s.play().paddle().rect.pos.x, WIDTH * 0.5);
+
+    s.press(Key::Space);
+    assert!(s.play().has_started());
+}
+
+#[test]
+fn holding_right_moves_the_paddle_to_the_wall() {
+    let mut s = Script::new(1);
+    s.start().hold(Key::Right, 10);
+    assert_eq!(s.play().paddle().rect.pos.x, WIDTH * 0.5 + 80.0);
+
+    s.hold(Key::Right, 200);
+    let right = s.play().paddle().rect.pos.x + PADDLE_WIDTH * 0.5;
+    assert!(right < WIDTH - FRAME_OFFSET);
+    assert!(right > WIDTH - FRAME_OFFSET - 10.0);
+
+    s.hold(Key::Left, 5);
+    assert!(s.play().paddle().rect.pos.x + PADDLE_WIDTH * 0.5 < right);
+}
+
+#[test]
+fn the_ball_rides_the_paddle_until_launched() {
+    let mut s = Script::new(3);
+    s.start().hold(Key::Left, 20);
+    let paddle = s.play().paddle().rect.pos;
+    let ball = s.play().ball().circle.pos;
+    assert_eq!(ball.x, paddle.x);
+    assert!(ball.y < paddle.y);
+    assert!(!s.play().has_started());
+}
+
+#[test]
+fn hitting_a_red_brick_levels_up() {
+    let mut s = Script::new(11).layout(&[FULL_ROW]);
+    assert_eq!(s.bricks_alive(), 14);
+
+    s.start()
+        .press(Key::Space)
+        .follow_ball_until("a brick is hit", 2000, |s| s.score() > 0);
+
+    assert_eq!(s.score(), 100);
+    assert_eq!(s.game().level(), 2);
+    assert_eq!(s.bricks_alive(), 13);
+    assert_eq!(s.lives(), 3);
+}
+
+#[test]
+fn pausing_freezes_the_ball() {
+    let mut s = Script::new(5).layout(&[]);
+    s.start().press(Key::Space).wait(10).press(Key::Space);
+    assert!(s.play().is_paused());
+
+    let pos = s.play().ball().circle.pos;
+    s.wait(30);
+    assert_eq!(s.play().ball().circle.pos, pos);
+
+    s.press(Key::Space);
+    assert!(!s.play().is_paused());
+    assert_ne!(s.play().ball().circle.pos, pos);
+}
+
+#[test]
+fn three_lost_balls_end_the_game_and_enter_returns_to_the_menu() {
+    let mut s = Script::new(42).layout(&[]);
+    s.start();
+    for lives in [2, 1] {
+        s.press(Key::Space).wait_until_ball_lost(1000);
+        assert_eq!(s.lives(), lives);
+        s.wait_until_ready(200);
+    }
+    s.press(Key::Space).wait_until_ball_lost(1000);
+    assert_eq!(s.screen(), Screen::GameOver);
+
+    s.press(Key::Enter);
+    assert_eq!(s.screen(), Screen::Menu);
+    assert_eq!(s.score(), 0);
+}
+
+#[test]
+fn holding_enter_on_game_over_does_not_start_the_next_game() {
+    let mut s = Script::new(42).layout(&[]);
+    s.start();
+    while s.screen() == Screen::Play {
+        s.press(Key::Space).wait_until_ball_lost(1000);
+        if s.screen() == Screen::Play {
+            s.wait_until_ready(200);
+        }
+    }
+    assert_eq!(s.screen(), Screen::GameOver);
+
+    s.hold(Key::Enter, 5);
+    assert_eq!(s.screen(), Screen::Menu);
+    s.wait(2);
+    assert_eq!(s.screen(), Screen::Menu);
+    s.press(Key::Enter);
+    assert_eq!(s.screen(), Screen::Play);
+}
+
+#[test]
+fn escape_quits_from_play() {
+    let mut s = Script::new(9);
+    s.start().press(Key::Escape);
+    assert!(s.has_quit());
+}
+
+#[test]
+fn the_arrows_spell_text_without_a_keyboard() {
+    let mut input = FakeInput::new();
+    let mut text = String::new();
+    let mut spin = |text: &mut String, keys: &[Key]| {
+        for key in keys {
+            input.press(*key);
+        }
+        let changed = spin_text(text, &input, "ABC");
+        input.release_all();
+        changed
+    };
+    assert!(spin(&mut text, &[Key::Down]));
+    assert_eq!(text, "C");
+    spin(&mut text, &[Key::Up]);
+    assert_eq!(text, "A");
+    spin(&mut text, &[Key::Right]);
+    spin(&mut text, &[Key::Up]);
+    spin(&mut text, &[Key::Up]);
+    assert_eq!(text, "AC");
+    spin(&mut text, &[Key::Right, Key::Down]);
+    assert_eq!(text, "ACC");
+    assert!(!spin(&mut text, &[Key::Up, Key::Down]));
+    assert!(!spin(&mut text, &[Key::Left]));
+    assert_eq!(text, "ACC");
+
+    text.push('z');
+    spin(&mut text, &[Key::Up]);
+    assert_eq!(text, "ACCA");
+}
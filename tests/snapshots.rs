@@ -0,0 +1,209 @@
+mod support;
+
+use breakout::{
+    breakout::{Breakout, capture},
+    canvas::{Canvas, Rgba, soft::frame_path},
+    code::encode,
+    common::{BRICK_COLS, Drawable, HEADING_SIZE, HEIGHT, ScreenMsg, WIDTH},
+    heading::Heading,
+    input::Key,
+    level::{BrickKind, BrickSpec, Level, PALETTE, checksum},
+    pack::{MANIFEST, Progress},
+    replay::Replay,
+    screens::Screen,
+};
+use grapes::vx2;
+use std::{fs, path::Path};
+use support::{assert_snapshot, canvas, canvas_sized};
+
+fn playing() -> Breakout {
+    let mut game = Breakout::new();
+    game.handle(ScreenMsg::Goto(Screen::Play));
+    game
+}
+
+fn mid_game() -> Breakout {
+    let mut game = playing();
+    game.handle(ScreenMsg::Points(350));
+    game.handle(ScreenMsg::BallLost(1));
+
+    let play = &mut game.screens_mut().play;
+    for idx in [
+        0,
+        1,
+        2,
+        5,
+        6,
+        13,
+        BRICK_COLS + 1,
+        BRICK_COLS + 2,
+        3 * BRICK_COLS + 7,
+    ] {
+        play.bricks_mut().kill(idx);
+    }
+    play.paddle_mut().set_x(WIDTH * 0.3);
+    play.ball_mut()
+        .set_ball_pos(vx2!(WIDTH * 0.6, HEIGHT * 0.6));
+    game
+}
+
+#[test]
+fn menu() {
+    let mut canvas = canvas();
+    Breakout::new().draw(&mut canvas);
+    assert_snapshot("menu", &canvas);
+}
+
+#[test]
+fn menu_after_game() {
+    let mut game = playing();
+    game.handle(ScreenMsg::Points(125));
+    game.handle(ScreenMsg::Goto(Screen::Menu));
+
+    let mut canvas = canvas();
+    game.draw(&mut canvas);
+    assert_snapshot("menu_after_game", &canvas);
+}
+
+#[test]
+fn play_fresh_wall() {
+    let mut canvas = canvas();
+    playing().draw(&mut canvas);
+    assert_snapshot("play_fresh_wall", &canvas);
+}
+
+#[test]
+fn play_mid_game() {
+    let mut canvas = canvas();
+    mid_game().draw(&mut canvas);
+    assert_snapshot("play_mid_game", &canvas);
+}
+
+#[test]
+fn play_paused() {
+    let mut game = mid_game();
+    game.screens_mut().play.set_paused(true);
+
+    let mut canvas = canvas();
+    game.draw(&mut canvas);
+    assert_snapshot("play_paused", &canvas);
+}
+
+#[test]
+fn heading_ball_counts() {
+    for lost in 0..=3 {
+        let mut heading = Heading::init();
+        heading.set_ball(lost);
+
+        let mut canvas = canvas_sized(WIDTH as u32, HEADING_SIZE as u32);
+        canvas.clear(Rgba::BLACK);
+        heading.draw(&mut canvas);
+        assert_snapshot(&format!("heading_{}_balls", 3 - lost), &canvas);
+    }
+}
+
+#[test]
+fn game_over() {
+    let mut game = playing();
+    game.handle(ScreenMsg::Points(600));
+    game.handle(ScreenMsg::Goto(Screen::GameOver));
+
+    let mut canvas = canvas();
+    game.draw(&mut canvas);
+    assert_snapshot("game_over", &canvas);
+}
+
+#[test]
+fn editor() {
+    let mut level = Level::new("Snapshot");
+    level.fill_row(1, Some(BrickSpec::new(BrickKind::Normal, PALETTE[4], 50)));
+    level.set(
+        3,
+        4,
+        Some(BrickSpec::new(BrickKind::Tough, PALETTE[5], 200)),
+    );
+    level.set(3, 9, Some(BrickSpec::new(BrickKind::Solid, PALETTE[0], 0)));
+
+    let mut game = Breakout::new();
+    game.handle(ScreenMsg::Goto(Screen::Editor));
+    game.screens_mut().editor.set_level(level);
+
+    let mut canvas = canvas();
+    game.draw(&mut canvas);
+    assert_snapshot("editor", &canvas);
+}
+
+#[test]
+fn packs() {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("snapshot_packs");
+    let _ = fs::remove_dir_all(&dir);
+    let starter = dir.join("starter");
+    fs::create_dir_all(&starter).unwrap();
+    let mut level = Level::new("Row");
+    level.fill_row(2, Some(BrickSpec::new(BrickKind::Normal, PALETTE[3], 10)));
+    let text = level.to_string();
+    fs::write(starter.join("1.level"), &text).unwrap();
+    fs::write(starter.join("2.level"), &text).unwrap();
+    let manifest = format!(
+        "name = Starter\nauthor = Tests\ndescription = Two rows to warm up\n\
+         stage = 1.level\ntitle = Opening\npar = 140\nchecksum = {:08x}\n\
+         stage = 2.level\ntitle = Edited\nchecksum = 00000000\n",
+        checksum(text.as_bytes())
+    );
+    fs::write(starter.join(MANIFEST), manifest).unwrap();
+    fs::create_dir_all(dir.join("unfinished")).unwrap();
+    fs::write(dir.join("unfinished").join(MANIFEST), "name = Unfinished\n").unwrap();
+
+    let mut progress = Progress::new(dir.join("progress"));
+    progress.record_clear("starter", 0);
+    progress.record_score("starter", 140);
+    progress.save().unwrap();
+
+    let mut game = Breakout::new();
+    game.screens_mut()
+        .packs
+        .set_dirs(dir.clone(), dir.join("progress"));
+    game.handle(ScreenMsg::Goto(Screen::Packs));
+
+    let mut canvas = canvas();
+    game.draw(&mut canvas);
+    assert_snapshot("packs", &canvas);
+}
+
+#[test]
+fn code_entry() {
+    let mut game = Breakout::new();
+    game.handle(ScreenMsg::Goto(Screen::Code));
+    let code = &mut game.screens_mut().code;
+    code.set_code(&encode(&Level::classic()));
+
+    let mut canvas = canvas();
+    game.draw(&mut canvas);
+    assert_snapshot("code_entry", &canvas);
+}
+
+#[test]
+fn a_replay_is_captured_frame_by_frame() {
+    let replay = Replay::parse(
+        "# start, serve and chase right
+        seed = 3
+        hold = 1 Enter
+        hold = 2
+        hold = 1 Space
+        hold = 20 Right",
+    )
+    .unwrap();
+    assert_eq!(replay.frames().count(), 24);
+    assert!(Replay::parse("hold = 2 Jump").is_err());
+    assert!(Replay::parse("hold = Left").is_err());
+
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("capture");
+    let _ = fs::remove_dir_all(&dir);
+    assert_eq!(capture(&replay, &dir).unwrap(), 24);
+    assert!(!frame_path(&dir, 24).exists());
+    let frame = |n| fs::read(frame_path(&dir, n)).unwrap();
+    assert!(frame(3) != frame(23), "the paddle did not move");
+    let mut quit = replay.clone();
+    quit.holds.insert(3, (1, vec![Key::Escape]));
+    assert_eq!(capture(&quit, &dir).unwrap(), 4);
+}
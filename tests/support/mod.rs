@@ -0,0 +1,141 @@
+//! Helpers shared by the integration tests.
+#![allow(dead_code)]
+
+pub mod script;
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use breakout::{
+    assets::{AssetId, Assets},
+    canvas::soft::SoftCanvas,
+    common::{HEIGHT, WIDTH},
+};
+
+/// Set to write missing or changed reference images instead of failing.
+pub const BLESS_ENV: &str = "BREAKOUT_BLESS";
+/// Largest per channel difference that still counts as the same pixel.
+pub const CHANNEL_TOLERANCE: u8 = 16;
+/// Share of pixels allowed to differ before a snapshot fails.
+pub const PIXEL_TOLERANCE: f64 = 0.002;
+
+pub fn canvas() -> SoftCanvas {
+    canvas_sized(WIDTH as u32, HEIGHT as u32)
+}
+
+pub fn canvas_sized(width: u32, height: u32) -> SoftCanvas {
+    let font = Assets::new(Vec::new()).bytes(AssetId::MonoFont);
+    SoftCanvas::new(width, height, &font).expect("embedded font parses")
+}
+
+fn reference_path(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/snapshots")
+        .join(format!("{name}.png"))
+}
+
+fn output_dir() -> PathBuf {
+    Path::new(env!("CARGO_TARGET_TMPDIR")).join("snapshots")
+}
+
+struct Image {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+fn load_png(path: &Path) -> Option<Image> {
+    let decoder = png::Decoder::new(fs::File::open(path).ok()?);
+    let mut reader = decoder.read_info().ok()?;
+    let mut pixels = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut pixels).ok()?;
+    if info.color_type != png::ColorType::Rgba || info.bit_depth != png::BitDepth::Eight {
+        return None;
+    }
+    pixels.truncate(info.buffer_size());
+    Some(Image {
+        width: info.width,
+        height: info.height,
+        pixels,
+    })
+}
+
+fn save_png(path: &Path, width: u32, height: u32, pixels: &[u8]) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    let file = fs::File::create(path).unwrap();
+    let mut encoder = png::Encoder::new(file, width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .write_header()
+        .and_then(|mut w| w.write_image_data(pixels))
+        .unwrap();
+}
+
+/// Red where the frames differ, a dimmed copy of the actual frame elsewhere.
+fn diff_image(expected: &[u8], actual: &[u8]) -> (Vec<u8>, usize) {
+    let mut diff = Vec::with_capacity(actual.len());
+    let mut mismatches = 0;
+    for (e, a) in expected.chunks_exact(4).zip(actual.chunks_exact(4)) {
+        let same = e
+            .iter()
+            .zip(a)
+            .all(|(e, a)| e.abs_diff(*a) <= CHANNEL_TOLERANCE);
+        if same {
+            diff.extend_from_slice(&[a[0] / 4, a[1] / 4, a[2] / 4, 0xFF]);
+        } else {
+            mismatches += 1;
+            diff.extend_from_slice(&[0xFF, 0x00, 0x00, 0xFF]);
+        }
+    }
+    (diff, mismatches)
+}
+
+/// Compares `canvas` with `tests/snapshots/<name>.png`. On failure the actual
+/// frame and a diff image are written to the test tmp dir.
+pub fn assert_snapshot(name: &str, canvas: &SoftCanvas) {
+    let reference = reference_path(name);
+    let bless = env::var_os(BLESS_ENV).is_some();
+    let (width, height) = (canvas.width(), canvas.height());
+
+    let Some(expected) = load_png(&reference) else {
+        if bless {
+            canvas.save_png(&reference).unwrap();
+            return;
+        }
+        panic!(
+            "no reference image at {}, run with {BLESS_ENV}=1 to create it",
+            reference.display()
+        );
+    };
+
+    let actual_path = output_dir().join(format!("{name}.actual.png"));
+    if expected.width != width || expected.height != height {
+        canvas.save_png(&actual_path).unwrap();
+        panic!(
+            "snapshot '{name}' is {width}x{height}, reference is {}x{}, actual frame in {}",
+            expected.width,
+            expected.height,
+            actual_path.display()
+        );
+    }
+
+    let (diff, mismatches) = diff_image(&expected.pixels, canvas.pixels());
+    let allowed = ((width * height) as f64 * PIXEL_TOLERANCE) as usize;
+    if mismatches > allowed {
+        if bless {
+            canvas.save_png(&reference).unwrap();
+            return;
+        }
+        let diff_path = output_dir().join(format!("{name}.diff.png"));
+        canvas.save_png(&actual_path).unwrap();
+        save_png(&diff_path, width, height, &diff);
+        panic!(
+            "snapshot '{name}' differs in {mismatches} pixels (allowed {allowed}), see {} and {}",
+            actual_path.display(),
+            diff_path.display()
+        );
+    }
+}
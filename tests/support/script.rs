@@ -0,0 +1,219 @@
+//! Drives `Breakout` through its real update path from a timeline of inputs.
+
+use breakout::{
+    breakout::Breakout,
+    common::{BRICK_COLS, BRICK_ROWS, PADDLE_VEL},
+    input::{FakeInput, Key},
+    mode::Mode,
+    screens::{Screen, editor::Editor, play::Play},
+};
+
+/// Ticks `follow_ball_until` sends the ball the same way.
+const SWEEP: usize = 1000;
+
+pub struct Script {
+    game: Breakout,
+    input: FakeInput,
+    tick: usize,
+    quit: bool,
+}
+
+impl Script {
+    pub fn new(seed: u64) -> Self {
+        let mut game = Breakout::new();
+        game.seed(seed);
+        Self {
+            game,
+            input: FakeInput::new(),
+            tick: 0,
+            quit: false,
+        }
+    }
+
+    /// A game of `mode`, on its first frame of play.
+    pub fn in_mode(seed: u64, mode: Mode) -> Self {
+        let mut s = Self::new(seed);
+        s.game.set_mode(mode);
+        s.start();
+        s
+    }
+
+    /// Rows of `#` (brick) and `.` (gap), top row first. Rows or columns that
+    /// are left out are empty.
+    pub fn layout(mut self, rows: &[&str]) -> Self {
+        assert!(rows.len() <= BRICK_ROWS, "at most {BRICK_ROWS} rows");
+        let rows: Vec<Vec<bool>> = rows
+            .iter()
+            .map(|row| {
+                assert!(row.len() <= BRICK_COLS, "at most {BRICK_COLS} columns");
+                row.bytes().map(|b| b == b'#').collect()
+            })
+            .collect();
+        self.game
+            .screens_mut()
+            .play
+            .bricks_mut()
+            .set_layout(|row, col| rows.get(row).and_then(|r| r.get(col)) == Some(&true));
+        self
+    }
+
+    pub fn game(&self) -> &Breakout {
+        &self.game
+    }
+
+    pub fn game_mut(&mut self) -> &mut Breakout {
+        &mut self.game
+    }
+
+    pub fn play(&self) -> &Play {
+        &self.game.screens().play
+    }
+
+    pub fn editor(&self) -> &Editor {
+        &self.game.screens().editor
+    }
+
+    pub fn editor_mut(&mut self) -> &mut Editor {
+        &mut self.game.screens_mut().editor
+    }
+
+    /// For the pointer, keys go through `press` and `hold`.
+    pub fn input(&mut self) -> &mut FakeInput {
+        &mut self.input
+    }
+
+    pub fn tick(&self) -> usize {
+        self.tick
+    }
+
+    pub fn has_quit(&self) -> bool {
+        self.quit
+    }
+
+    /// Whether an online session is connected and both games are on.
+    pub fn is_online_ready(&self) -> bool {
+        let session = self.game.screens().online.session();
+        session.is_some_and(|session| session.is_ready())
+    }
+
+    pub fn screen(&self) -> Screen {
+        self.game.screen().clone()
+    }
+
+    pub fn score(&self) -> usize {
+        self.game.score()
+    }
+
+    pub fn lives(&self) -> usize {
+        self.play().balls_left()
+    }
+
+    pub fn bricks_alive(&self) -> usize {
+        self.play().bricks().alive_count()
+    }
+
+    fn step(&mut self) {
+        assert!(!self.quit, "tick {}: the game has already quit", self.tick);
+        self.quit = self.game.update(&self.input);
+        self.input.next_frame();
+        self.tick += 1;
+    }
+
+    /// Leaves the menu and waits for the first frame of play.
+    pub fn start(&mut self) -> &mut Self {
+        assert_eq!(self.screen(), Screen::Menu);
+        self.press(Key::Enter)
+    }
+
+    /// Presses and releases `key` within one tick.
+    pub fn press(&mut self, key: Key) -> &mut Self {
+        self.hold(key, 1)
+    }
+
+    /// Types `text` in one frame.
+    pub fn type_text(&mut self, text: &str) -> &mut Self {
+        self.input.type_text(text);
+        self.step();
+        self
+    }
+
+    pub fn hold(&mut self, key: Key, ticks: usize) -> &mut Self {
+        self.input.press(key);
+        for _ in 0..ticks {
+            self.step();
+        }
+        self.input.release(key);
+        self
+    }
+
+    pub fn wait(&mut self, ticks: usize) -> &mut Self {
+        for _ in 0..ticks {
+            self.step();
+        }
+        self
+    }
+
+    /// Ticks until `done` holds, panics if that takes more than `limit` ticks.
+    pub fn wait_until<F>(&mut self, what: &str, limit: usize, done: F) -> &mut Self
+    where
+        F: Fn(&Self) -> bool,
+    {
+        let start = self.tick;
+        while !done(self) {
+            assert!(
+                self.tick - start < limit,
+                "tick {}: gave up waiting for {what} after {limit} ticks",
+                self.tick
+            );
+            self.step();
+        }
+        self
+    }
+
+    /// Waits until a ball drops past the paddle, including the last one.
+    pub fn wait_until_ball_lost(&mut self, limit: usize) -> &mut Self {
+        let lives = self.lives();
+        self.wait_until("a lost ball", limit, |s| {
+            s.lives() < lives || s.screen() == Screen::GameOver
+        })
+    }
+
+    /// Waits until the paddle is back in the middle after a lost ball.
+    pub fn wait_until_ready(&mut self, limit: usize) -> &mut Self {
+        self.wait_until("the next ball", limit, |s| !s.play().in_transition())
+    }
+
+    /// Steers the paddle under the ball for up to `limit` ticks, until `done`.
+    /// It catches the ball near one end to send it sideways, the other end
+    /// every `SWEEP` ticks, so the ball gets around the wall.
+    pub fn follow_ball_until<F>(&mut self, what: &str, limit: usize, done: F) -> &mut Self
+    where
+        F: Fn(&Self) -> bool,
+    {
+        let start = self.tick;
+        while !done(self) {
+            assert!(
+                self.tick - start < limit,
+                "tick {}: gave up following the ball until {what} after {limit} ticks",
+                self.tick
+            );
+            let side = if (self.tick / SWEEP).is_multiple_of(2) {
+                1.0
+            } else {
+                -1.0
+            };
+            let paddle = self.play().paddle().rect;
+            let ball = self.play().ball().circle.pos.x + side * paddle.size.x * 0.35;
+            let paddle = paddle.pos.x;
+            self.input.release_all();
+            if ball > paddle + PADDLE_VEL {
+                self.input.press(Key::Right);
+            } else if ball < paddle - PADDLE_VEL {
+                self.input.press(Key::Left);
+            }
+            self.step();
+        }
+        self.input.release_all();
+        self
+    }
+}
@@ -0,0 +1,142 @@
+use std::{fs, path::Path, process::Command};
+
+use breakout::{
+    common::{BRICK_COLS, HEADING_SIZE, WIDTH},
+    generator::{Pattern, generate_with},
+    level::{BrickKind, BrickSpec, Level, PALETTE},
+    sprites::bricks::brick_rect,
+    validate::{Bounds, Problem, validate, validate_with},
+};
+use grapes::{objects::rectangle::Rectangle, vx2};
+
+fn brick(kind: BrickKind) -> Option<BrickSpec> {
+    Some(BrickSpec::new(kind, PALETTE[2], 10))
+}
+
+/// A normal brick at (2, 5) boxed in by solid ones.
+fn walled_in() -> Level {
+    let mut level = Level::new("Walled In");
+    level.set(2, 5, brick(BrickKind::Normal));
+    for (row, col) in [(1, 5), (3, 5), (2, 4), (2, 6)] {
+        level.set(row, col, brick(BrickKind::Solid));
+    }
+    level
+}
+
+#[test]
+fn built_in_walls_are_fine() {
+    assert_eq!(validate(&Level::classic()), []);
+    for (i, pattern) in Pattern::ALL.into_iter().enumerate() {
+        let level = generate_with(pattern, i as u64, 1.0);
+        assert_eq!(validate(&level), [], "{}", level.name);
+    }
+}
+
+#[test]
+fn bricks_boxed_in_by_solid_ones() {
+    assert_eq!(validate(&walled_in()), [Problem::Unreachable((2, 5))]);
+}
+
+#[test]
+fn holes_smaller_than_the_ball() {
+    let mut level = Level::new("Two Rows");
+    level.fill_row(6, brick(BrickKind::Normal));
+    level.fill_row(7, brick(BrickKind::Normal));
+    assert_eq!(validate(&level), []);
+
+    let big_ball = Bounds {
+        ball_radius: 40.0,
+        ..Bounds::default()
+    };
+    let problems = validate_with(&level, brick_rect, &big_ball);
+    assert_eq!(problems.len(), BRICK_COLS);
+    assert!(
+        problems
+            .iter()
+            .all(|p| matches!(p, Problem::Unreachable((6, _))))
+    );
+}
+
+#[test]
+fn gaps_in_the_wall_are_open_to_any_ball() {
+    // a gap column up to a brick above it
+    let mut level = Level::new("Gap");
+    level.fill_row(6, brick(BrickKind::Normal));
+    level.fill_row(7, brick(BrickKind::Normal));
+    level.set(6, 3, None);
+    level.set(7, 3, None);
+    level.set(5, 3, brick(BrickKind::Normal));
+    assert_eq!(validate(&level), []);
+
+    let big_ball = Bounds {
+        ball_radius: 40.0,
+        ..Bounds::default()
+    };
+    let unreachable: Vec<Problem> = (0..BRICK_COLS)
+        .filter(|col| !(2..=4).contains(col))
+        .map(|col| Problem::Unreachable((6, col)))
+        .collect();
+    assert_eq!(validate_with(&level, brick_rect, &big_ball), unreachable);
+}
+
+#[test]
+fn geometry_problems() {
+    let mut level = Level::new("Corners");
+    level.set(0, 0, brick(BrickKind::Normal));
+    level.set(0, 1, brick(BrickKind::Normal));
+    level.set(7, BRICK_COLS - 1, brick(BrickKind::Normal));
+
+    // bricks twice as wide, moved up and down and past the right edge
+    let stretched = |row: usize, col: usize| {
+        let r = brick_rect(row, col);
+        let y = if row == 0 {
+            HEADING_SIZE
+        } else {
+            r.pos.y + 600.0
+        };
+        let x = if col == BRICK_COLS - 1 {
+            WIDTH
+        } else {
+            r.pos.x
+        };
+        Rectangle::new(vx2!(x, y), vx2!(r.size.x * 2.0, r.size.y))
+    };
+    let problems = validate_with(&level, stretched, &Bounds::default());
+    assert_eq!(
+        problems,
+        [
+            Problem::OutsideFrame((0, 0)),
+            Problem::InHeading((0, 0)),
+            Problem::Overlap((0, 0), (0, 1)),
+            Problem::InHeading((0, 1)),
+            Problem::OutsideFrame((7, BRICK_COLS - 1)),
+            Problem::InPaddleZone((7, BRICK_COLS - 1)),
+        ]
+    );
+    assert_eq!(problems[2].to_string(), "brick 0 0 overlaps brick 0 1");
+}
+
+#[test]
+fn cli_reports_problems() {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("validate");
+    fs::create_dir_all(&dir).unwrap();
+    let bad = dir.join("walled_in.level");
+    walled_in().save(&bad).unwrap();
+    let good = dir.join("classic.level");
+    Level::classic().save(&good).unwrap();
+
+    let run = |path: &Path| {
+        Command::new(env!("CARGO_BIN_EXE_breakout"))
+            .arg("--validate")
+            .arg(path)
+            .output()
+            .unwrap()
+    };
+    let out = run(&bad);
+    assert!(!out.status.success());
+    let text = String::from_utf8_lossy(&out.stdout);
+    assert!(text.contains("brick 2 5 can not be reached"), "{text}");
+
+    assert!(run(&good).status.success());
+    assert!(!run(&dir.join("missing.level")).status.success());
+}
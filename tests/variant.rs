@@ -0,0 +1,137 @@
+mod support;
+
+use breakout::{
+    canvas::{Canvas, Rgba},
+    common::{BRICK_COLS, BRICK_ROWS, Drawable, ball_base_speed},
+    input::Key,
+    screens::{Screen, play::MIN_VY_RATIO},
+    sprites::bricks::brick_rect,
+    variant::{FLASH_FRAMES, STEER_ANGLE, TIMED_FRAMES, VariantKind, steer},
+};
+use grapes::vx2;
+use support::script::Script;
+
+const FULL_ROW: &str = "##############";
+
+fn variant(seed: u64, rows: &[&str], variant: VariantKind) -> Script {
+    let mut s = Script::new(seed).layout(rows);
+    s.game_mut().set_variant(variant);
+    s
+}
+
+#[test]
+fn steering_turns_the_ball_but_never_flat() {
+    for v in [vx2!(1.0, -6.0), vx2!(-2.0, 5.0)] {
+        let right = steer(v, STEER_ANGLE);
+        let left = steer(v, -STEER_ANGLE);
+        assert!(right.x > v.x && left.x < v.x, "{v:?}");
+        assert_eq!(right.y.signum(), v.y.signum());
+        assert!((right.length() - v.length()).abs() < 1e-4);
+    }
+
+    let mut v = vx2!(0.0, -6.0);
+    for _ in 0..500 {
+        v = steer(v, STEER_ANGLE);
+    }
+    assert!(v.y < 0.0 && v.y.abs() >= MIN_VY_RATIO * v.length() - 1e-4);
+}
+
+#[test]
+fn breakthru_keeps_going_through_broken_bricks() {
+    let rows = ["", "", "", "", "", FULL_ROW, FULL_ROW, FULL_ROW];
+    let mut s = variant(11, &rows, VariantKind::Breakthru);
+    s.start()
+        .press(Key::Space)
+        .follow_ball_until("a brick is hit", 2000, |s| s.score() > 0);
+    assert!(s.play().ball().velocity.y < 0.0);
+
+    let mut s = variant(11, &rows, VariantKind::Regular);
+    s.start()
+        .press(Key::Space)
+        .follow_ball_until("a brick is hit", 2000, |s| s.score() > 0);
+    assert!(s.play().ball().velocity.y > 0.0);
+}
+
+#[test]
+fn catch_holds_the_ball_until_space() {
+    let mut s = variant(5, &[], VariantKind::Catch);
+    s.start()
+        .press(Key::Space)
+        .follow_ball_until("a catch", 2000, |s| s.play().holds_ball());
+    s.wait(1);
+    let offset = s.play().ball().circle.pos.x - s.play().paddle().rect.pos.x;
+    s.hold(Key::Left, 10);
+    assert!(s.play().holds_ball());
+    let now = s.play().ball().circle.pos.x - s.play().paddle().rect.pos.x;
+    assert!((now - offset).abs() < 1e-3);
+
+    s.press(Key::Space);
+    assert!(!s.play().holds_ball());
+    assert!(!s.play().is_paused());
+    assert!(s.play().ball().velocity.y < 0.0);
+    assert!((s.play().ball().velocity.length() - ball_base_speed()).abs() < 1e-3);
+}
+
+#[test]
+fn invisible_bricks_only_show_when_hit() {
+    let draw = |s: &Script| {
+        let mut canvas = support::canvas();
+        canvas.clear(Rgba::BLACK);
+        s.play().draw(&mut canvas);
+        let pos = brick_rect(0, 0).pos;
+        canvas.pixel(pos.x as u32, pos.y as u32)
+    };
+    let s = variant(1, &[FULL_ROW], VariantKind::Regular);
+    assert_ne!(draw(&s), Rgba::BLACK);
+
+    let mut s = variant(1, &[FULL_ROW], VariantKind::Invisible);
+    s.start();
+    assert_eq!(draw(&s), Rgba::BLACK);
+    assert_eq!(s.bricks_alive(), 14);
+}
+
+#[test]
+fn a_hit_brick_flashes_up_in_invisible() {
+    let lit = |s: &Script| -> Vec<usize> {
+        let mut canvas = support::canvas();
+        canvas.clear(Rgba::BLACK);
+        s.play().draw(&mut canvas);
+        (0..BRICK_COLS)
+            .filter(|&col| {
+                let pos = brick_rect(BRICK_ROWS - 1, col).pos;
+                canvas.pixel(pos.x as u32, pos.y as u32) != Rgba::BLACK
+            })
+            .collect()
+    };
+    let rows = ["", "", "", "", "", "", "", FULL_ROW];
+    let mut s = variant(3, &rows, VariantKind::Invisible);
+    s.start()
+        .press(Key::Space)
+        .follow_ball_until("a brick is hit", 2000, |s| s.score() > 0);
+    // a normal brick breaks on the hit and still shows
+    assert_eq!(s.bricks_alive(), BRICK_COLS - 1);
+    let hit = lit(&s);
+    assert_eq!(hit.len(), 1);
+    s.wait(FLASH_FRAMES - 1);
+    assert_eq!(lit(&s), hit);
+    s.wait(1);
+    assert!(lit(&s).is_empty());
+}
+
+#[test]
+fn timed_games_end_with_the_clock_not_the_balls() {
+    let mut s = variant(42, &[], VariantKind::Timed);
+    s.start();
+    assert_eq!(s.play().time_left(), Some(TIMED_FRAMES));
+    // serve every ball that drops, the clock only runs while one is in play
+    while s.screen() == Screen::Play {
+        assert!(s.tick() < 3 * TIMED_FRAMES, "the clock never ran out");
+        if s.play().has_started() || s.play().in_transition() {
+            s.wait(1);
+        } else {
+            s.press(Key::Space);
+        }
+    }
+    assert_eq!(s.screen(), Screen::GameOver);
+    assert_eq!(s.lives(), 3);
+}
@@ -0,0 +1,125 @@
+mod support;
+
+use breakout::{
+    common::{BRICK_COLS, BRICK_ROWS, Field, WIDTH, frame_segments},
+    difficulty::Difficulty,
+    input::{Key, Pad},
+    mode::Mode,
+    rules::Ruleset,
+    screens::{Screen, versus::Versus},
+    sprites::bricks::field_brick_rect,
+    variant::VariantKind,
+};
+use grapes::vx2;
+use support::script::Script;
+
+fn paddle_x(s: &Script, side: usize) -> f32 {
+    s.game().screens().versus.side(side).paddle().rect.pos.x
+}
+
+fn serve(side: usize) -> [Pad; 2] {
+    let mut pads = [Pad::default(); 2];
+    pads[side].serve = true;
+    pads
+}
+
+#[test]
+fn each_side_plays_in_its_own_half() {
+    assert_eq!(Field::FULL.frame_segments(), frame_segments());
+    let [left, right] = Field::HALVES;
+    assert_eq!(left.left + left.width, right.left);
+    assert_eq!(right.left + right.width, WIDTH);
+
+    let mut s = Script::in_mode(1, Mode::Versus);
+    assert_eq!(s.screen(), Screen::Versus);
+    for (side, field) in Field::HALVES.iter().enumerate() {
+        let play = s.game().screens().versus.side(side);
+        assert_eq!(paddle_x(&s, side), field.center_x());
+        for rect in play.bricks().alive_rects() {
+            assert!(rect.pos.x > field.min_x() && rect.pos.x < field.max_x());
+        }
+    }
+
+    let (a, b) = (paddle_x(&s, 0), paddle_x(&s, 1));
+    s.hold(Key::A, 10);
+    assert!(paddle_x(&s, 0) < a);
+    assert_eq!(paddle_x(&s, 1), b);
+    s.hold(Key::Right, 200);
+    assert!(paddle_x(&s, 1) > b);
+    assert!(paddle_x(&s, 1) <= right.max_x());
+    s.hold(Key::D, 200);
+    assert!(paddle_x(&s, 0) <= left.max_x());
+}
+
+#[test]
+fn a_cleared_row_goes_to_the_other_wall() {
+    let mut versus = Versus::init();
+    versus.seed(4);
+    versus.reset();
+    let field = Field::HALVES[0];
+    versus
+        .side_mut(0)
+        .bricks_mut()
+        .set_layout(|row, col| (row == 7 && col == 0) || (row == 0 && col == 13));
+    let mut msgs = Vec::new();
+    versus.step(serve(0), &mut msgs);
+    assert!(versus.side(0).has_started());
+
+    // straight up into the lone brick of the bottom row
+    let below = field_brick_rect(&field, 7, 0).pos;
+    let ball = versus.side_mut(0).ball_mut();
+    ball.set_ball_pos(vx2!(below.x, below.y + 40.0));
+    ball.set_ball_vel(vx2!(0.0, -6.0));
+    for _ in 0..20 {
+        versus.step([Pad::default(); 2], &mut msgs);
+    }
+    assert!(versus.side(0).bricks().row_cleared(7));
+    assert!(versus.scores()[0] > 0);
+
+    let garbage = versus.side(1).bricks();
+    assert_eq!(garbage.rows(), BRICK_ROWS + 1);
+    assert_eq!(
+        garbage.alive_count(),
+        BRICK_ROWS * BRICK_COLS + BRICK_COLS - 1
+    );
+    assert_eq!(versus.side(0).bricks().rows(), BRICK_ROWS);
+    assert!(!versus.is_over());
+    assert!(msgs.is_empty());
+}
+
+#[test]
+fn the_last_ball_lost_decides_the_game() {
+    let mut s = Script::in_mode(2, Mode::Versus);
+    // the right player serves and never moves, the left one never serves
+    while s.screen() == Screen::Versus {
+        assert!(s.tick() < 20_000, "the right player never ran out of balls");
+        s.press(Key::Up);
+    }
+    assert_eq!(s.screen(), Screen::GameOver);
+    let versus = &s.game().screens().versus;
+    assert_eq!(versus.winner(), Some(0));
+    let [a, b] = versus.scores();
+    assert_eq!(a, 0);
+    assert_eq!(
+        s.game().screens().game_over.result(),
+        Some(format!("Player 1 Wins, {a} To {b}").as_str())
+    );
+}
+
+#[test]
+fn the_menu_has_no_settings_for_versus() {
+    let mut s = Script::new(1);
+    s.game_mut().set_mode(Mode::Versus);
+    s.press(Key::D).press(Key::R).press(Key::V);
+    let menu = &s.game().screens().menu;
+    assert_eq!(menu.difficulty(), Difficulty::default());
+    assert_eq!(menu.ruleset(), Ruleset::default());
+    assert_eq!(menu.variant(), VariantKind::default());
+    assert_eq!(s.game().ruleset(), Ruleset::default());
+
+    // Online Versus has none either, Standard does again
+    s.press(Key::M).press(Key::R);
+    assert_eq!(s.game().ruleset(), Ruleset::default());
+    s.press(Key::M).press(Key::R);
+    assert_eq!(s.game().ruleset(), Ruleset::Classic);
+}